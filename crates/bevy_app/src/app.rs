@@ -4,6 +4,7 @@ use crate::{
 };
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+    error::ErrorHandler,
     event::{event_update_system, ManualEventReader},
     intern::Interned,
     prelude::*,
@@ -342,6 +343,79 @@ impl App {
         self
     }
 
+    /// Creates a [`ComponentIndex`] for the component `C` and schedules a
+    /// [`sync_component_index`] system in [`Last`](crate::Last) to pick up in-place mutations.
+    ///
+    /// The index can then be read through the [`Index`] system parameter.
+    /// See the [`index`](bevy_ecs::index) module for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` is already used by an entity, or if it already has an `on_insert` or
+    /// `on_replace` hook.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::index::Index;
+    /// #
+    /// #[derive(Component, Clone, PartialEq, Eq, Hash)]
+    /// struct NetworkId(u64);
+    ///
+    /// fn resolve(index: Index<NetworkId>) {
+    ///     let _entity = index.get_single(&NetworkId(42));
+    /// }
+    ///
+    /// App::new()
+    ///     .add_component_index::<NetworkId>()
+    ///     .add_systems(Update, resolve);
+    /// ```
+    ///
+    /// [`ComponentIndex`]: bevy_ecs::index::ComponentIndex
+    /// [`sync_component_index`]: bevy_ecs::index::sync_component_index
+    /// [`Index`]: bevy_ecs::index::Index
+    pub fn add_component_index<C>(&mut self) -> &mut Self
+    where
+        C: Component + Eq + std::hash::Hash + Clone,
+    {
+        self.main_mut().add_component_index::<C>();
+        self
+    }
+
+    /// Sets the [`ErrorHandler`] for errors returned by fallible systems in the app, along with
+    /// the name of the failing system. By default, such errors cause a panic.
+    ///
+    /// The handler can be overridden for a single schedule with
+    /// [`Schedule::set_error_handler`], for example through [`App::edit_schedule`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// use bevy_ecs::error::{error_handler, Result};
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// fn despawn_player(mut commands: Commands, player: Query<Entity, With<Player>>) -> Result {
+    ///     commands.entity(player.get_single()?).despawn();
+    ///     Ok(())
+    /// }
+    ///
+    /// App::new()
+    ///     .set_error_handler(error_handler::warn)
+    ///     .add_systems(Update, despawn_player);
+    /// ```
+    ///
+    /// [`ErrorHandler`]: bevy_ecs::error::ErrorHandler
+    pub fn set_error_handler(&mut self, error_handler: ErrorHandler) -> &mut Self {
+        self.main_mut().set_error_handler(error_handler);
+        self
+    }
+
     /// Inserts the [`Resource`] into the app, overwriting any existing resource of the same type.
     ///
     /// There is also an [`init_resource`](Self::init_resource) for resources that have
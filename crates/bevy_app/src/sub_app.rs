@@ -1,6 +1,8 @@
-use crate::{App, InternedAppLabel, Plugin, Plugins, PluginsState};
+use crate::{App, InternedAppLabel, Last, Plugin, Plugins, PluginsState};
 use bevy_ecs::{
+    error::{ErrorHandler, SystemErrorHandler},
     event::EventRegistry,
+    index::{sync_component_index, ComponentIndex},
     prelude::*,
     schedule::{InternedScheduleLabel, ScheduleBuildSettings, ScheduleLabel},
     system::SystemId,
@@ -312,6 +314,26 @@ impl SubApp {
         self
     }
 
+    /// See [`App::add_component_index`].
+    pub fn add_component_index<C>(&mut self) -> &mut Self
+    where
+        C: Component + Eq + std::hash::Hash + Clone,
+    {
+        if !self.world.contains_resource::<ComponentIndex<C>>() {
+            self.world.init_component_index::<C>();
+            self.add_systems(Last, sync_component_index::<C>);
+        }
+
+        self
+    }
+
+    /// See [`App::set_error_handler`].
+    pub fn set_error_handler(&mut self, error_handler: ErrorHandler) -> &mut Self {
+        self.world
+            .insert_resource(SystemErrorHandler(error_handler));
+        self
+    }
+
     /// See [`App::add_plugins`].
     pub fn add_plugins<M>(&mut self, plugins: impl Plugins<M>) -> &mut Self {
         self.run_as_app(|app| plugins.add_to_app(app));
@@ -490,6 +490,13 @@ impl<A: Asset> Assets<A> {
         self.dense_storage.len() + self.hash_map.len()
     }
 
+    /// Returns an estimate of the bytes of CPU memory used by the assets in this collection, as the sum of
+    /// their [`Asset::memory_usage`]. This visits every asset, so it should not be called on large collections
+    /// every frame outside of diagnostics.
+    pub fn memory_usage(&self) -> usize {
+        self.iter().map(|(_, asset)| asset.memory_usage()).sum()
+    }
+
     /// Returns an iterator over the [`AssetId`] of every [`Asset`] stored in this collection.
     pub fn ids(&self) -> impl Iterator<Item = AssetId<A>> + '_ {
         self.dense_storage
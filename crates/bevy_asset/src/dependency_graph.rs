@@ -0,0 +1,73 @@
+use crate::{AssetPath, UntypedAssetId};
+use serde::{Serialize, Serializer};
+use std::fmt::Write;
+
+/// A snapshot of the dependency graph of assets loaded by the [`AssetServer`](crate::AssetServer), returned by
+/// [`AssetServer::dependency_graph`](crate::AssetServer::dependency_graph).
+///
+/// The graph can be exported in the [DOT](https://graphviz.org/doc/info/lang.html) format with
+/// [`AssetDependencyGraph::to_dot`], or with any [`serde`] format, such as JSON.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AssetDependencyGraph {
+    /// The assets of the graph.
+    pub nodes: Vec<AssetGraphNode>,
+    /// The `(dependant, dependency)` edges of the graph, as indices into [`AssetDependencyGraph::nodes`].
+    pub edges: Vec<(usize, usize)>,
+}
+
+/// An asset of an [`AssetDependencyGraph`].
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetGraphNode {
+    /// The id of the asset.
+    #[serde(serialize_with = "serialize_id")]
+    pub id: UntypedAssetId,
+    /// The path of the asset, if it has one.
+    pub path: Option<AssetPath<'static>>,
+}
+
+impl AssetGraphNode {
+    /// Returns the path of the asset, or its id for assets without a path.
+    pub fn name(&self) -> String {
+        match &self.path {
+            Some(path) => path.to_string(),
+            None => self.id.to_string(),
+        }
+    }
+}
+
+impl AssetDependencyGraph {
+    /// Returns the indices of the direct dependencies of the node at `index`.
+    pub fn dependencies(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges
+            .iter()
+            .filter(move |(dependant, _)| *dependant == index)
+            .map(|(_, dependency)| *dependency)
+    }
+
+    /// Returns the indices of the direct dependants of the node at `index`.
+    pub fn dependants(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges
+            .iter()
+            .filter(move |(_, dependency)| *dependency == index)
+            .map(|(dependant, _)| *dependant)
+    }
+
+    /// Returns the graph in the [DOT](https://graphviz.org/doc/info/lang.html) format, with edges going from
+    /// dependants to their dependencies.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph assets {\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let name = node.name().replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(dot, "    {index} [label=\"{name}\"];").unwrap();
+        }
+        for (dependant, dependency) in &self.edges {
+            writeln!(dot, "    {dependant} -> {dependency};").unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn serialize_id<S: Serializer>(id: &UntypedAssetId, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(id)
+}
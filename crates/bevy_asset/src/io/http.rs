@@ -0,0 +1,412 @@
+use crate::io::{
+    get_meta_path, AssetReader, AssetReaderError, AssetSource, EmptyPathStream, PathStream, Reader,
+    VecReader,
+};
+use crate::AssetApp;
+use bevy_app::{App, Plugin};
+use bevy_utils::{
+    tracing::{error, warn},
+    HashSet,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Adds the `http` and `https` asset sources, loading assets from URLs like
+/// `https://example.com/dlc/level.gltf`.
+///
+/// This plugin must be added before [`AssetPlugin`](crate::AssetPlugin), typically added as part
+/// of `DefaultPlugins`, since asset sources are built when it is added.
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{io::http::HttpSourcePlugin, AssetPlugin};
+/// App::new()
+///     .add_plugins((HttpSourcePlugin::default(), AssetPlugin::default()));
+/// ```
+pub struct HttpSourcePlugin {
+    /// The directory where downloaded assets are cached, or `None` to disable the cache.
+    ///
+    /// Defaults to a `bevy_http_asset_cache` directory in the temporary directory of the system.
+    pub cache_dir: Option<PathBuf>,
+    /// How long to wait for a server before giving up on a request.
+    pub timeout: Duration,
+}
+
+impl Default for HttpSourcePlugin {
+    fn default() -> Self {
+        Self {
+            cache_dir: Some(std::env::temp_dir().join("bevy_http_asset_cache")),
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Plugin for HttpSourcePlugin {
+    fn build(&self, app: &mut App) {
+        for scheme in ["http", "https"] {
+            let reader = HttpAssetReader::new(scheme)
+                .with_timeout(self.timeout)
+                .with_cache(self.cache_dir.clone());
+            app.register_asset_source(
+                scheme,
+                AssetSource::build().with_reader(move || Box::new(reader.clone())),
+            );
+        }
+    }
+}
+
+/// Reader implementation for loading assets over HTTP on native platforms.
+///
+/// The first component of the asset path is the host, so the asset path
+/// `https://example.com/dlc/level.gltf` reads the URL `https://example.com/dlc/level.gltf`
+/// when this reader is used by the `https` asset source.
+///
+/// When a cache directory is set, downloaded assets are stored there and later requests are made
+/// conditional on the `ETag` and `Last-Modified` headers of the cached response, so unchanged
+/// assets aren't downloaded again. The cached asset is also used when the server can't be reached.
+///
+/// Most assets don't have a `.meta` file, so a missing `.meta` file is only requested once by each
+/// reader. When the server can't be reached and no `.meta` file is cached, the asset is loaded
+/// as if it didn't have one.
+///
+/// Directories can't be read over HTTP.
+#[derive(Clone)]
+pub struct HttpAssetReader {
+    scheme: &'static str,
+    agent: ureq::Agent,
+    cache_dir: Option<Arc<Path>>,
+    /// The `.meta` files the server answered `404 Not Found` for.
+    missing_meta: Arc<RwLock<HashSet<PathBuf>>>,
+}
+
+impl HttpAssetReader {
+    /// Creates a new `HttpAssetReader` for the given URL scheme, `http` or `https`.
+    pub fn new(scheme: &'static str) -> Self {
+        Self {
+            scheme,
+            agent: ureq::Agent::new(),
+            cache_dir: None,
+            missing_meta: Default::default(),
+        }
+    }
+
+    /// Sets how long to wait for a server before giving up on a request.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.agent = ureq::AgentBuilder::new().timeout(timeout).build();
+        self
+    }
+
+    /// Sets the directory where downloaded assets are cached, or `None` to disable the cache.
+    pub fn with_cache(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.map(Into::into);
+        self
+    }
+
+    /// Returns the URL of the asset at `path`.
+    pub fn url(&self, path: &Path) -> String {
+        let path = path.to_string_lossy().replace('\\', "/");
+        format!("{}://{}", self.scheme, path)
+    }
+
+    async fn fetch<'a>(&self, path: &Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let url = self.url(path);
+        let cache = self
+            .cache_dir
+            .as_deref()
+            .map(|cache_dir| CacheEntry::new(cache_dir, &url));
+        let cached = match &cache {
+            Some(cache) => cache.load_response().await,
+            None => None,
+        };
+
+        let agent = self.agent.clone();
+        let request_url = url.clone();
+        let validators = cached.clone();
+        let result =
+            blocking::unblock(move || request(&agent, &request_url, validators.as_ref())).await;
+
+        let bytes = match (result, cache) {
+            (Ok(Response::NotModified), Some(cache)) => cache.load_body().await?,
+            (Ok(Response::NotModified), None) => {
+                // Only cached responses have validators, so this is a misbehaving server.
+                return Err(AssetReaderError::HttpError(304));
+            }
+            (Ok(Response::Body { bytes, cached }), cache) => {
+                if let Some(cache) = cache {
+                    if let Err(err) = cache.store(&cached, &bytes).await {
+                        warn!("Failed to cache {url}: {err}");
+                    }
+                }
+                bytes
+            }
+            (Err(AssetReaderError::HttpError(404)), _) => {
+                return Err(AssetReaderError::NotFound(path.to_owned()));
+            }
+            (Err(err), Some(cache)) if cached.is_some() && is_unavailable(&err) => {
+                warn!("Failed to fetch {url}, using the cached asset instead: {err}");
+                cache.load_body().await?
+            }
+            (Err(err), _) => return Err(err),
+        };
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
+    }
+}
+
+impl AssetReader for HttpAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        self.fetch(path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let meta_path = get_meta_path(path);
+        if self.missing_meta.read().contains(&meta_path) {
+            return Err(AssetReaderError::NotFound(meta_path));
+        }
+        match self.fetch(&meta_path).await {
+            Err(AssetReaderError::NotFound(_)) => {
+                self.missing_meta.write().insert(meta_path.clone());
+                Err(AssetReaderError::NotFound(meta_path))
+            }
+            // The cached `.meta` file would have been used if there was one, so the asset is
+            // loaded with its default settings rather than failing too.
+            Err(err) if is_unavailable(&err) => {
+                warn!(
+                    "Failed to fetch {}, loading the asset without it: {err}",
+                    self.url(&meta_path)
+                );
+                Err(AssetReaderError::NotFound(meta_path))
+            }
+            result => result,
+        }
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let stream: Box<PathStream> = Box::new(EmptyPathStream);
+        error!("Reading directories is not supported with the HttpAssetReader");
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, _path: &'a Path) -> Result<bool, AssetReaderError> {
+        error!("Reading directories is not supported with the HttpAssetReader");
+        Ok(false)
+    }
+}
+
+/// The headers of a cached response, used to make conditional requests.
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+struct CachedResponse {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+enum Response {
+    NotModified,
+    Body {
+        bytes: Vec<u8>,
+        cached: CachedResponse,
+    },
+}
+
+/// Sends a blocking request for `url`, conditional on the `cached` response if any.
+fn request(
+    agent: &ureq::Agent,
+    url: &str,
+    cached: Option<&CachedResponse>,
+) -> Result<Response, AssetReaderError> {
+    let mut request = agent.get(url);
+    if let Some(cached) = cached {
+        if let Some(etag) = &cached.etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.set("If-Modified-Since", last_modified);
+        }
+    }
+
+    match request.call() {
+        Ok(response) if response.status() == 304 => Ok(Response::NotModified),
+        Ok(response) => {
+            let cached = CachedResponse {
+                url: url.to_owned(),
+                etag: response.header("ETag").map(ToOwned::to_owned),
+                last_modified: response.header("Last-Modified").map(ToOwned::to_owned),
+            };
+            let mut bytes = Vec::new();
+            response.into_reader().read_to_end(&mut bytes)?;
+            Ok(Response::Body { bytes, cached })
+        }
+        Err(ureq::Error::Status(status, _)) => Err(AssetReaderError::HttpError(status)),
+        Err(err @ ureq::Error::Transport(_)) => {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, err).into())
+        }
+    }
+}
+
+/// Returns `true` if `err` means that the server couldn't be reached or had an internal error,
+/// in which case a cached asset is better than nothing.
+fn is_unavailable(err: &AssetReaderError) -> bool {
+    match err {
+        AssetReaderError::Io(_) => true,
+        AssetReaderError::HttpError(status) => *status >= 500,
+        AssetReaderError::NotFound(_) => false,
+    }
+}
+
+/// The files caching the response for a URL, named after the hash of the URL.
+struct CacheEntry {
+    cache_dir: PathBuf,
+    body_path: PathBuf,
+    response_path: PathBuf,
+}
+
+impl CacheEntry {
+    fn new(cache_dir: &Path, url: &str) -> Self {
+        let hash = blake3::hash(url.as_bytes()).to_hex();
+        Self {
+            cache_dir: cache_dir.to_owned(),
+            body_path: cache_dir.join(format!("{hash}.bin")),
+            response_path: cache_dir.join(format!("{hash}.ron")),
+        }
+    }
+
+    async fn load_response(&self) -> Option<CachedResponse> {
+        let response = async_fs::read_to_string(&self.response_path).await.ok()?;
+        ron::from_str(&response).ok()
+    }
+
+    async fn load_body(&self) -> Result<Vec<u8>, AssetReaderError> {
+        Ok(async_fs::read(&self.body_path).await?)
+    }
+
+    async fn store(&self, response: &CachedResponse, bytes: &[u8]) -> std::io::Result<()> {
+        let response = ron::to_string(response)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+        async_fs::create_dir_all(&self.cache_dir).await?;
+        // The response is written last, so that a cached response always has a complete body.
+        let _ = async_fs::remove_file(&self.response_path).await;
+        async_fs::write(&self.body_path, bytes).await?;
+        async_fs::write(&self.response_path, response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::{future::block_on, AsyncReadExt};
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
+
+    /// Serves `hello.txt` with an `ETag`, answering `304 Not Modified` to conditional requests.
+    fn serve(requests: Arc<AtomicUsize>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.unwrap();
+                let mut lines = BufReader::new(&stream).lines().map(Result::unwrap);
+                let request_line = lines.next().unwrap();
+                let conditional = lines
+                    .take_while(|line| !line.is_empty())
+                    .any(|line| line.eq_ignore_ascii_case("if-none-match: \"v1\""));
+                requests.fetch_add(1, Ordering::SeqCst);
+
+                let (status, body) = if !request_line.starts_with("GET /hello.txt ") {
+                    ("404 Not Found", "")
+                } else if conditional {
+                    ("304 Not Modified", "")
+                } else {
+                    ("200 OK", "hello")
+                };
+                let mut writer = &stream;
+                write!(
+                    writer,
+                    "HTTP/1.1 {status}\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        host
+    }
+
+    fn read(reader: &HttpAssetReader, path: &str) -> Result<String, AssetReaderError> {
+        block_on(async {
+            let mut bytes = Vec::new();
+            reader
+                .read(Path::new(path))
+                .await?
+                .read_to_end(&mut bytes)
+                .await?;
+            Ok(String::from_utf8(bytes).unwrap())
+        })
+    }
+
+    #[test]
+    fn conditional_requests() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let host = serve(requests.clone());
+        let cache_dir = std::env::temp_dir().join(format!("bevy_http_asset_test_{host}"));
+        let cache_dir = PathBuf::from(cache_dir.to_string_lossy().replace(':', "_"));
+        let _ = std::fs::remove_dir_all(&cache_dir);
+        let reader = HttpAssetReader::new("http").with_cache(Some(cache_dir.clone()));
+
+        let path = format!("{host}/hello.txt");
+        assert_eq!(reader.url(Path::new(&path)), format!("http://{path}"));
+        assert_eq!(read(&reader, &path).unwrap(), "hello");
+        // The second response is `304 Not Modified`, so the body comes from the cache.
+        assert_eq!(read(&reader, &path).unwrap(), "hello");
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+
+        // A missing `.meta` file is only requested once.
+        let meta = PathBuf::from(format!("{host}/hello.txt.meta"));
+        for _ in 0..2 {
+            assert_eq!(
+                block_on(reader.read_meta(Path::new(&path))).err(),
+                Some(AssetReaderError::NotFound(meta.clone()))
+            );
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+        let missing = format!("{host}/missing.txt");
+        assert_eq!(
+            read(&reader, &missing),
+            Err(AssetReaderError::NotFound(missing.into()))
+        );
+
+        std::fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn offline() {
+        // Nothing listens on the port of a dropped listener.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let host = listener.local_addr().unwrap().to_string();
+        drop(listener);
+        let reader = HttpAssetReader::new("http");
+
+        let path = format!("{host}/hello.txt");
+        // Without a cached `.meta` file, the asset is loaded as if it didn't have one.
+        assert_eq!(
+            block_on(reader.read_meta(Path::new(&path))).err(),
+            Some(AssetReaderError::NotFound(
+                format!("{host}/hello.txt.meta").into()
+            ))
+        );
+        assert!(matches!(read(&reader, &path), Err(AssetReaderError::Io(_))));
+    }
+}
@@ -0,0 +1,550 @@
+use crate::io::{
+    get_meta_path, AssetReader, AssetReaderError, AssetSource, AssetSourceBuilder,
+    ErasedAssetReader, PathStream, Reader, VecReader,
+};
+use bevy_utils::HashMap;
+use futures_lite::{AsyncReadExt, AsyncSeekExt};
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+/// The first bytes of every pack, followed by the offset of the index.
+const MAGIC: &[u8; 8] = b"BVYPACK1";
+
+/// The length of the header of a pack: its magic bytes and the offset of its index.
+const HEADER_LEN: u64 = MAGIC.len() as u64 + 8;
+
+/// The length of an index entry with an empty path: the lengths of the path and the offsets and
+/// lengths of the file, and its compression.
+const MIN_INDEX_ENTRY_LEN: u64 = 4 * 8 + 1;
+
+/// How the files of a pack are compressed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PackCompression {
+    /// Files are stored as is.
+    #[default]
+    None,
+    /// Files are compressed with [zstd](https://facebook.github.io/zstd/). This requires the
+    /// `pack_zstd` feature, both to write and to read the pack.
+    Zstd,
+}
+
+impl PackCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            PackCompression::None => 0,
+            PackCompression::Zstd => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> io::Result<Self> {
+        match byte {
+            0 => Ok(PackCompression::None),
+            1 => Ok(PackCompression::Zstd),
+            _ => Err(invalid_data("unknown compression")),
+        }
+    }
+}
+
+/// The location of a file in a pack.
+#[derive(Debug, Clone, Copy)]
+struct PackEntry {
+    offset: u64,
+    stored_len: u64,
+    len: u64,
+    compression: PackCompression,
+}
+
+/// Writes files into a pack, a single file that can be read with a [`PackAssetReader`].
+///
+/// A pack starts with a header, followed by the contents of the files and an index of their
+/// paths and locations. Asset metadata is stored next to the assets, as `.meta` files.
+pub struct PackWriter<W: Write + Seek> {
+    writer: W,
+    entries: Vec<(String, PackEntry)>,
+    position: u64,
+}
+
+impl<W: Write + Seek> PackWriter<W> {
+    /// Starts writing a pack to `writer`.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        // The offset of the index, written by `finish`.
+        writer.write_all(&0u64.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            entries: Vec::new(),
+            position: HEADER_LEN,
+        })
+    }
+
+    /// Adds the file at `path` to the pack. Files that don't get smaller when compressed are
+    /// stored uncompressed.
+    pub fn add(
+        &mut self,
+        path: &Path,
+        bytes: &[u8],
+        compression: PackCompression,
+    ) -> io::Result<()> {
+        let compressed = compress(bytes, compression)?;
+        let (stored, compression) = match &compressed {
+            Some(compressed) if compressed.len() < bytes.len() => (&compressed[..], compression),
+            _ => (bytes, PackCompression::None),
+        };
+        self.writer.write_all(stored)?;
+        self.entries.push((
+            pack_path(path),
+            PackEntry {
+                offset: self.position,
+                stored_len: stored.len() as u64,
+                len: bytes.len() as u64,
+                compression,
+            },
+        ));
+        self.position += stored.len() as u64;
+        Ok(())
+    }
+
+    /// Writes the index of the pack and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let index_offset = self.position;
+        self.writer
+            .write_all(&(self.entries.len() as u64).to_le_bytes())?;
+        for (path, entry) in &self.entries {
+            self.writer.write_all(&(path.len() as u64).to_le_bytes())?;
+            self.writer.write_all(path.as_bytes())?;
+            self.writer.write_all(&entry.offset.to_le_bytes())?;
+            self.writer.write_all(&entry.stored_len.to_le_bytes())?;
+            self.writer.write_all(&entry.len.to_le_bytes())?;
+            self.writer.write_all(&[entry.compression.to_byte()])?;
+        }
+        self.writer.seek(SeekFrom::Start(MAGIC.len() as u64))?;
+        self.writer.write_all(&index_offset.to_le_bytes())?;
+        self.writer.seek(SeekFrom::End(0))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// The paths of a pack, read from its index.
+#[derive(Debug, Default)]
+struct PackIndex {
+    files: HashMap<String, PackEntry>,
+    /// The assets and directories in each directory, without `.meta` files.
+    directories: HashMap<String, Vec<PathBuf>>,
+}
+
+impl PackIndex {
+    /// Reads the index of a pack, checking that every length and offset it contains is within the pack.
+    fn read(reader: &mut (impl Read + Seek)) -> io::Result<Self> {
+        let pack_len = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a pack"));
+        }
+        let index_offset = read_u64(reader)?;
+        if index_offset < HEADER_LEN || index_offset > pack_len {
+            return Err(invalid_data("index out of bounds"));
+        }
+        reader.seek(SeekFrom::Start(index_offset))?;
+
+        let mut index = Self::default();
+        let count = read_u64(reader)?;
+        if count > (pack_len - reader.stream_position()?) / MIN_INDEX_ENTRY_LEN {
+            return Err(invalid_data("too many files"));
+        }
+        for _ in 0..count {
+            let path_len = read_u64(reader)?;
+            if path_len > pack_len - reader.stream_position()? {
+                return Err(invalid_data("path out of bounds"));
+            }
+            let mut path = vec![0; path_len as usize];
+            reader.read_exact(&mut path)?;
+            let path = String::from_utf8(path).map_err(|_| invalid_data("invalid path"))?;
+            let entry = PackEntry {
+                offset: read_u64(reader)?,
+                stored_len: read_u64(reader)?,
+                len: read_u64(reader)?,
+                compression: {
+                    let mut byte = [0];
+                    reader.read_exact(&mut byte)?;
+                    PackCompression::from_byte(byte[0])?
+                },
+            };
+            // Files are stored between the header and the index.
+            let in_bounds = entry.offset >= HEADER_LEN
+                && entry
+                    .offset
+                    .checked_add(entry.stored_len)
+                    .is_some_and(|end| end <= index_offset);
+            if !in_bounds {
+                return Err(invalid_data("file out of bounds"));
+            }
+            if entry.compression == PackCompression::None && entry.stored_len != entry.len {
+                return Err(invalid_data("unexpected file length"));
+            }
+            index.insert(path, entry);
+        }
+        Ok(index)
+    }
+
+    fn insert(&mut self, path: String, entry: PackEntry) {
+        let is_meta = Path::new(&path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("meta"));
+        if !is_meta {
+            // Add the file to its directory, and new directories to their parent.
+            let mut child = path.as_str();
+            loop {
+                let parent = child.rfind('/').map_or("", |i| &child[..i]);
+                let is_new = !self.directories.contains_key(parent);
+                self.directories
+                    .entry(parent.to_string())
+                    .or_default()
+                    .push(PathBuf::from(child));
+                if !is_new || parent.is_empty() {
+                    break;
+                }
+                child = parent;
+            }
+        }
+        self.files.insert(path, entry);
+    }
+}
+
+/// Reader implementation for loading assets from a pack written by a [`PackWriter`], usually
+/// with [`AssetProcessor::write_pack`](crate::processor::AssetProcessor::write_pack).
+///
+/// Shipping a single pack instead of many loose files makes installing and opening games faster.
+/// The index of the pack is read when the reader is opened, and files are read from the pack as
+/// they are loaded.
+///
+/// To load processed assets from a pack in release builds, replace the default asset source
+/// before adding the [`AssetPlugin`](crate::AssetPlugin):
+///
+/// ```no_run
+/// # use bevy_app::App;
+/// # use bevy_asset::{io::{pack::PackAssetReader, AssetSourceId}, AssetApp, AssetMode, AssetPlugin};
+/// App::new()
+///     .register_asset_source(AssetSourceId::Default, PackAssetReader::source("assets.pack"))
+///     .add_plugins(AssetPlugin {
+///         mode: AssetMode::Processed,
+///         ..Default::default()
+///     });
+/// ```
+pub struct PackAssetReader {
+    file: async_lock::Mutex<async_fs::File>,
+    index: PackIndex,
+}
+
+impl PackAssetReader {
+    /// Opens the pack at `path` and reads its index.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let index = PackIndex::read(&mut reader)?;
+        Ok(Self {
+            file: async_lock::Mutex::new(reader.into_inner().into()),
+            index,
+        })
+    }
+
+    /// Returns an [`AssetSourceBuilder`] reading both unprocessed and processed assets from the
+    /// pack at `path`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the source is built if the pack can't be opened.
+    pub fn source(path: impl Into<PathBuf>) -> AssetSourceBuilder {
+        let path = path.into();
+        let open = move || -> Box<dyn ErasedAssetReader> {
+            match PackAssetReader::open(&path) {
+                Ok(reader) => Box::new(reader),
+                Err(err) => panic!("Failed to open asset pack {}: {err}", path.display()),
+            }
+        };
+        let processed_open = open.clone();
+        AssetSource::build()
+            .with_reader(open)
+            .with_processed_reader(processed_open)
+    }
+
+    async fn read_file<'a>(&'a self, path: &Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let entry = *self
+            .index
+            .files
+            .get(&pack_path(path))
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+        let mut stored = vec![0; entry.stored_len as usize];
+        {
+            let mut file = self.file.lock().await;
+            file.seek(SeekFrom::Start(entry.offset)).await?;
+            file.read_exact(&mut stored).await?;
+        }
+        let bytes = decompress(stored, entry)?;
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
+    }
+}
+
+impl AssetReader for PackAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        self.read_file(path).await
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        self.read_file(&get_meta_path(path)).await
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let children = self
+            .index
+            .directories
+            .get(&pack_path(path))
+            .ok_or_else(|| AssetReaderError::NotFound(path.to_owned()))?;
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(children.clone()));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+        Ok(self.index.directories.contains_key(&pack_path(path)))
+    }
+}
+
+/// Returns the path of a file in a pack, which uses `/` separators on every platform.
+fn pack_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid asset pack: {message}"),
+    )
+}
+
+#[cfg(not(feature = "pack_zstd"))]
+fn zstd_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "zstd compressed asset packs require the `pack_zstd` feature",
+    )
+}
+
+#[cfg_attr(not(feature = "pack_zstd"), allow(unused_variables))]
+fn compress(bytes: &[u8], compression: PackCompression) -> io::Result<Option<Vec<u8>>> {
+    match compression {
+        PackCompression::None => Ok(None),
+        #[cfg(feature = "pack_zstd")]
+        PackCompression::Zstd => zstd::encode_all(bytes, zstd::DEFAULT_COMPRESSION_LEVEL).map(Some),
+        #[cfg(not(feature = "pack_zstd"))]
+        PackCompression::Zstd => Err(zstd_unsupported()),
+    }
+}
+
+fn decompress(stored: Vec<u8>, entry: PackEntry) -> io::Result<Vec<u8>> {
+    let bytes = match entry.compression {
+        PackCompression::None => stored,
+        #[cfg(feature = "pack_zstd")]
+        PackCompression::Zstd => {
+            // Reading one more byte than expected detects corrupt files without decompressing all of them.
+            let mut bytes = Vec::new();
+            zstd::Decoder::new(&stored[..])?
+                .take(entry.len.saturating_add(1))
+                .read_to_end(&mut bytes)?;
+            bytes
+        }
+        #[cfg(not(feature = "pack_zstd"))]
+        PackCompression::Zstd => return Err(zstd_unsupported()),
+    };
+    if bytes.len() as u64 != entry.len {
+        return Err(invalid_data("unexpected file length"));
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PackAssetReader, PackCompression, PackWriter, HEADER_LEN, MAGIC};
+    use crate::io::{AssetReader, AssetReaderError};
+    use futures_lite::{AsyncReadExt, StreamExt};
+    use std::{
+        fs::File,
+        io::{self, Cursor},
+        path::{Path, PathBuf},
+    };
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "bevy_asset_pack_test_{name}_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    async fn read(reader: &PackAssetReader, path: &str) -> Result<Vec<u8>, AssetReaderError> {
+        let mut bytes = Vec::new();
+        let mut file = AssetReader::read(reader, Path::new(path)).await?;
+        file.read_to_end(&mut bytes).await.unwrap();
+        Ok(bytes)
+    }
+
+    #[test]
+    fn pack_round_trip() {
+        let dir = temp_dir("round_trip");
+        let pack_path = dir.join("assets.pack");
+
+        let mut writer = PackWriter::new(File::create(&pack_path).unwrap()).unwrap();
+        let texture = vec![7; 1000];
+        writer
+            .add(Path::new("textures/a.png"), &texture, PackCompression::None)
+            .unwrap();
+        writer
+            .add(
+                Path::new("textures/a.png.meta"),
+                b"(meta)",
+                PackCompression::None,
+            )
+            .unwrap();
+        writer
+            .add(Path::new("level.ron"), b"level", PackCompression::None)
+            .unwrap();
+        writer.finish().unwrap();
+
+        let reader = PackAssetReader::open(&pack_path).unwrap();
+        let reader = &reader;
+        bevy_tasks::block_on(async {
+            assert_eq!(read(reader, "textures/a.png").await.unwrap(), texture);
+            assert_eq!(read(reader, "level.ron").await.unwrap(), b"level");
+            assert_eq!(
+                read(reader, "missing.ron").await,
+                Err(AssetReaderError::NotFound("missing.ron".into()))
+            );
+            assert_eq!(
+                AssetReader::read_meta_bytes(reader, Path::new("textures/a.png"))
+                    .await
+                    .unwrap(),
+                b"(meta)"
+            );
+
+            let is_directory = |path: &'static str| async move {
+                AssetReader::is_directory(reader, Path::new(path))
+                    .await
+                    .unwrap()
+            };
+            assert!(is_directory("").await);
+            assert!(is_directory("textures").await);
+            assert!(!is_directory("level.ron").await);
+            let mut root: Vec<_> = AssetReader::read_directory(reader, Path::new(""))
+                .await
+                .unwrap()
+                .collect()
+                .await;
+            root.sort();
+            assert_eq!(
+                root,
+                [PathBuf::from("level.ron"), PathBuf::from("textures")]
+            );
+            let textures: Vec<_> = AssetReader::read_directory(reader, Path::new("textures"))
+                .await
+                .unwrap()
+                .collect()
+                .await;
+            assert_eq!(textures, [PathBuf::from("textures/a.png")]);
+        });
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "pack_zstd")]
+    #[test]
+    fn zstd_pack_round_trip() {
+        let dir = temp_dir("zstd");
+        let pack_path = dir.join("assets.pack");
+
+        let mut writer = PackWriter::new(File::create(&pack_path).unwrap()).unwrap();
+        let level = "level ".repeat(1000);
+        writer
+            .add(
+                Path::new("level.ron"),
+                level.as_bytes(),
+                PackCompression::Zstd,
+            )
+            .unwrap();
+        // Files that don't compress are stored as is.
+        writer
+            .add(Path::new("tiny.ron"), b"t", PackCompression::Zstd)
+            .unwrap();
+        writer.finish().unwrap();
+
+        let reader = PackAssetReader::open(&pack_path).unwrap();
+        let level_entry = reader.index.files["level.ron"];
+        assert_eq!(level_entry.compression, PackCompression::Zstd);
+        assert!(level_entry.stored_len < level_entry.len);
+        assert_eq!(
+            reader.index.files["tiny.ron"].compression,
+            PackCompression::None
+        );
+        bevy_tasks::block_on(async {
+            assert_eq!(read(&reader, "level.ron").await.unwrap(), level.as_bytes());
+            assert_eq!(read(&reader, "tiny.ron").await.unwrap(), b"t");
+        });
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(not(feature = "pack_zstd"))]
+    #[test]
+    fn zstd_requires_feature() {
+        let mut writer = PackWriter::new(Cursor::new(Vec::new())).unwrap();
+        let err = writer
+            .add(Path::new("level.ron"), b"level", PackCompression::Zstd)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn corrupt_packs_are_invalid_data() {
+        let mut writer = PackWriter::new(Cursor::new(Vec::new())).unwrap();
+        writer
+            .add(Path::new("level.ron"), b"level", PackCompression::None)
+            .unwrap();
+        let pack = writer.finish().unwrap().into_inner();
+        let index_offset = HEADER_LEN as usize + b"level".len();
+        // The index holds the file count, then the length of the first path.
+        let path_len_offset = index_offset + 8;
+        let file_offset = path_len_offset + 8 + b"level.ron".len();
+
+        let read_index = |pack: &[u8]| super::PackIndex::read(&mut Cursor::new(pack));
+        assert!(read_index(&pack).is_ok());
+
+        let corrupt = |offset: usize, value: u64| {
+            let mut pack = pack.clone();
+            pack[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+            read_index(&pack).unwrap_err().kind()
+        };
+        let invalid = io::ErrorKind::InvalidData;
+        assert_eq!(corrupt(MAGIC.len(), u64::MAX), invalid);
+        assert_eq!(corrupt(index_offset, u64::MAX), invalid);
+        assert_eq!(corrupt(path_len_offset, u64::MAX), invalid);
+        assert_eq!(corrupt(file_offset, u64::MAX), invalid);
+        assert_eq!(corrupt(file_offset + 8, u64::MAX), invalid);
+        assert!(read_index(&pack[..pack.len() - 1]).is_err());
+    }
+}
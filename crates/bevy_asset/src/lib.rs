@@ -21,6 +21,7 @@ pub mod prelude {
 }
 
 mod assets;
+mod dependency_graph;
 mod direct_access_ext;
 mod event;
 mod folder;
@@ -28,12 +29,14 @@ mod handle;
 mod id;
 mod loader;
 mod loader_builders;
+mod memory_diagnostics;
 mod path;
 mod reflect;
 mod server;
 
 pub use assets::*;
-pub use bevy_asset_macros::Asset;
+pub use bevy_asset_macros::{Asset, VisitAssetDependencies};
+pub use dependency_graph::*;
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
@@ -44,6 +47,7 @@ pub use loader::*;
 pub use loader_builders::{
     DirectNestedLoader, NestedLoader, UntypedDirectNestedLoader, UntypedNestedLoader,
 };
+pub use memory_diagnostics::AssetMemoryDiagnosticsPlugin;
 pub use path::*;
 pub use reflect::*;
 pub use server::*;
@@ -95,6 +99,8 @@ pub struct AssetPlugin {
     pub mode: AssetMode,
     /// How/If asset meta files should be checked.
     pub meta_check: AssetMetaCheck,
+    /// Limits on the loads the [`AssetServer`] runs at the same time. By default, loads are not limited.
+    pub load_budget: AssetLoadBudget,
 }
 
 #[derive(Debug)]
@@ -142,6 +148,7 @@ impl Default for AssetPlugin {
             processed_file_path: Self::DEFAULT_PROCESSED_FILE_PATH.to_string(),
             watch_for_changes_override: None,
             meta_check: AssetMetaCheck::default(),
+            load_budget: AssetLoadBudget::default(),
         }
     }
 }
@@ -216,6 +223,9 @@ impl Plugin for AssetPlugin {
                 }
             }
         }
+        app.world()
+            .resource::<AssetServer>()
+            .set_load_budget(self.load_budget);
         app.insert_resource(embedded)
             .init_asset::<LoadedFolder>()
             .init_asset::<LoadedUntypedAsset>()
@@ -232,7 +242,15 @@ impl Plugin for AssetPlugin {
     label = "invalid `Asset`",
     note = "consider annotating `{Self}` with `#[derive(Asset)]`"
 )]
-pub trait Asset: VisitAssetDependencies + TypePath + Send + Sync + 'static {}
+pub trait Asset: VisitAssetDependencies + TypePath + Send + Sync + 'static {
+    /// Returns an estimate of the bytes of CPU memory used by this asset, used by [`Assets::memory_usage`].
+    ///
+    /// Defaults to the size of the asset type, which doesn't include heap allocations. Assets owning large buffers
+    /// should implement [`Asset`] manually to include them, deriving [`VisitAssetDependencies`] instead.
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
 
 pub trait VisitAssetDependencies {
     fn visit_dependencies(&self, visit: &mut impl FnMut(UntypedAssetId));
@@ -446,12 +464,13 @@ mod tests {
             AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, DependencyLoadState, LoadState,
-        RecursiveDependencyLoadState,
+        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent,
+        AssetMemoryDiagnosticsPlugin, AssetPath, AssetPlugin, AssetServer, Assets,
+        DependencyLoadState, LoadState, RecursiveDependencyLoadState, VisitAssetDependencies,
     };
     use bevy_app::{App, Update};
     use bevy_core::TaskPoolPlugin;
+    use bevy_diagnostic::DiagnosticsStore;
     use bevy_ecs::prelude::*;
     use bevy_ecs::{
         event::ManualEventReader,
@@ -459,10 +478,14 @@ mod tests {
     };
     use bevy_log::LogPlugin;
     use bevy_reflect::TypePath;
+    use bevy_tasks::TaskPriority;
     use bevy_utils::{Duration, HashMap};
     use futures_lite::AsyncReadExt;
     use serde::{Deserialize, Serialize};
-    use std::{path::Path, sync::Arc};
+    use std::{
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
     use thiserror::Error;
 
     #[derive(Asset, TypePath, Debug, Default)]
@@ -1345,6 +1368,198 @@ mod tests {
         });
     }
 
+    /// An asset reader that waits for `gate` to open before reading, and records the paths it read.
+    #[derive(Clone)]
+    struct RecordingReader {
+        memory_reader: MemoryAssetReader,
+        gate: Arc<async_lock::RwLock<()>>,
+        reads: Arc<std::sync::Mutex<Vec<PathBuf>>>,
+    }
+
+    impl AssetReader for RecordingReader {
+        async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+            // Unlike `GatedReader`, this doesn't block the thread, so the load can be cancelled while it waits.
+            let _open = self.gate.read().await;
+            self.reads.lock().unwrap().push(path.to_owned());
+            self.memory_reader.read(path).await
+        }
+        async fn read_meta<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> Result<Box<Reader<'a>>, AssetReaderError> {
+            self.memory_reader.read_meta(path).await
+        }
+        async fn read_directory<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> Result<Box<bevy_asset::io::PathStream>, AssetReaderError> {
+            self.memory_reader.read_directory(path).await
+        }
+        async fn is_directory<'a>(&'a self, path: &'a Path) -> Result<bool, AssetReaderError> {
+            self.memory_reader.is_directory(path).await
+        }
+    }
+
+    #[test]
+    fn dropping_handles_cancels_load() {
+        // Loads are only cancelled on multi-threaded platforms
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise loads are not cancelled.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let dir = Dir::default();
+        let a_path = "a.cool.ron";
+        let b_path = "b.cool.ron";
+        for (path, text) in [(a_path, "a"), (b_path, "b")] {
+            let ron = format!(
+                r#"(
+    text: "{text}",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#
+            );
+            dir.insert_asset_text(Path::new(path), &ron);
+        }
+
+        let gate = Arc::new(async_lock::RwLock::new(()));
+        let closed_gate = gate.try_write().unwrap();
+        let reader = RecordingReader {
+            memory_reader: MemoryAssetReader { root: dir },
+            gate: gate.clone(),
+            reads: Default::default(),
+        };
+        let reads = reader.reads.clone();
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build().with_reader(move || Box::new(reader.clone())),
+        )
+        .add_plugins((
+            TaskPoolPlugin::default(),
+            LogPlugin::default(),
+            AssetPlugin::default(),
+        ))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a_handle: Handle<CoolText> =
+            asset_server.load_with_priority(a_path, TaskPriority::Background);
+        let a_id = a_handle.id();
+        asset_server.set_load_priority(a_id, TaskPriority::High);
+        let b_handle: Handle<CoolText> = asset_server.load(b_path);
+        app.update();
+        assert_eq!(asset_server.load_state(a_id), LoadState::Loading);
+
+        // The load doesn't keep the asset alive, so dropping the handle cancels the load waiting for the gate.
+        drop(a_handle);
+        run_app_until(&mut app, |_| {
+            asset_server.get_load_state(a_id).is_none().then_some(())
+        });
+
+        drop(closed_gate);
+        run_app_until(&mut app, |world| {
+            get::<CoolText>(world, b_handle.id()).map(|_| ())
+        });
+        assert!(get::<CoolText>(app.world(), a_id).is_none());
+        assert_eq!(*reads.lock().unwrap(), vec![PathBuf::from(b_path)]);
+    }
+
+    #[test]
+    fn dependency_graph() {
+        // The particular usage of GatedReader in this test will cause deadlocking if running single-threaded
+        #[cfg(not(feature = "multi_threaded"))]
+        panic!("This test requires the \"multi_threaded\" feature, otherwise it will deadlock.\ncargo test --package bevy_asset --features multi_threaded");
+
+        let dir = Dir::default();
+
+        let a_path = "a.cool.ron";
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: [
+        "b.cool.ron",
+    ],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_path = "b.cool.ron";
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new(a_path), a_ron);
+        dir.insert_asset_text(Path::new(b_path), b_ron);
+
+        let (mut app, gate_opener) = test_app(dir);
+        app.init_asset::<CoolText>()
+            .init_asset::<SubText>()
+            .register_asset_loader(CoolTextLoader);
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = asset_server.load(a_path);
+        gate_opener.open(a_path);
+        gate_opener.open(b_path);
+        run_app_until(&mut app, |_| {
+            (asset_server.recursive_dependency_load_state(&a)
+                == RecursiveDependencyLoadState::Loaded)
+                .then_some(())
+        });
+
+        let b = asset_server.get_handle::<CoolText>(b_path).unwrap();
+        assert_eq!(
+            asset_server.get_dependencies(&a),
+            Some(vec![b.id().untyped()])
+        );
+        assert_eq!(
+            asset_server.get_dependants(&b),
+            Some(vec![a.id().untyped()])
+        );
+        assert_eq!(asset_server.get_dependants(&a), Some(vec![]));
+
+        let graph = asset_server.dependency_graph([a.id().untyped()]);
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges, [(0, 1)]);
+        assert_eq!(graph.nodes[1].path, Some(AssetPath::from(b_path)));
+        assert_eq!(
+            graph.to_dot(),
+            "digraph assets {\n    0 [label=\"a.cool.ron\"];\n    1 [label=\"b.cool.ron\"];\n    0 -> 1;\n}\n"
+        );
+    }
+
+    #[derive(TypePath, VisitAssetDependencies)]
+    struct Buffer(Vec<u8>);
+
+    impl Asset for Buffer {
+        fn memory_usage(&self) -> usize {
+            std::mem::size_of::<Self>() + self.0.len()
+        }
+    }
+
+    #[test]
+    fn asset_memory_diagnostics() {
+        let (mut app, _) = test_app(Dir::default());
+        app.add_plugins(AssetMemoryDiagnosticsPlugin::<Buffer>::default())
+            .init_asset::<Buffer>();
+        let mut buffers = app.world_mut().resource_mut::<Assets<Buffer>>();
+        let _handles = [
+            buffers.add(Buffer(vec![0; 100])),
+            buffers.add(Buffer(vec![0; 28])),
+        ];
+        let expected = 2 * std::mem::size_of::<Buffer>() + 128;
+        assert_eq!(buffers.memory_usage(), expected);
+
+        app.update();
+        let diagnostics = app.world().resource::<DiagnosticsStore>();
+        let memory = diagnostics
+            .get(&AssetMemoryDiagnosticsPlugin::<Buffer>::diagnostic_path())
+            .unwrap();
+        assert_eq!(memory.value(), Some(expected as f64));
+    }
+
     /// Tests that `AssetLoadFailedEvent<A>` events are emitted and can be used to retry failed assets.
     #[test]
     fn load_error_events() {
@@ -7,6 +7,7 @@ use crate::{
     UntypedHandle,
 };
 use bevy_ecs::world::World;
+use bevy_tasks::TaskPriority;
 use bevy_utils::{BoxedFuture, ConditionalSendFuture, CowArc, HashMap, HashSet};
 use downcast_rs::{impl_downcast, Downcast};
 use futures_lite::AsyncReadExt;
@@ -311,6 +312,8 @@ pub struct LoadContext<'a> {
     pub(crate) asset_server: &'a AssetServer,
     pub(crate) should_load_dependencies: bool,
     populate_hashes: bool,
+    /// The priority of the load, which is also used for the loads of its dependencies.
+    pub(crate) priority: TaskPriority,
     asset_path: AssetPath<'static>,
     pub(crate) dependencies: HashSet<UntypedAssetId>,
     /// Direct dependencies used by this loader.
@@ -325,12 +328,14 @@ impl<'a> LoadContext<'a> {
         asset_path: AssetPath<'static>,
         should_load_dependencies: bool,
         populate_hashes: bool,
+        priority: TaskPriority,
     ) -> Self {
         Self {
             asset_server,
             asset_path,
             populate_hashes,
             should_load_dependencies,
+            priority,
             dependencies: HashSet::default(),
             loader_dependencies: HashMap::default(),
             labeled_assets: HashMap::default(),
@@ -372,6 +377,7 @@ impl<'a> LoadContext<'a> {
             self.asset_path.clone(),
             self.should_load_dependencies,
             self.populate_hashes,
+            self.priority,
         )
     }
 
@@ -530,6 +536,7 @@ impl<'a> LoadContext<'a> {
                 reader,
                 false,
                 self.populate_hashes,
+                self.priority,
             )
             .await
             .map_err(|error| LoadDirectError {
@@ -115,9 +115,12 @@ impl<'ctx, 'builder> NestedLoader<'ctx, 'builder> {
     pub fn load<'c, A: Asset>(self, path: impl Into<AssetPath<'c>>) -> Handle<A> {
         let path = path.into().to_owned();
         let handle = if self.load_context.should_load_dependencies {
-            self.load_context
-                .asset_server
-                .load_with_meta_transform(path, self.meta_transform, ())
+            self.load_context.asset_server.load_with_meta_transform(
+                path,
+                self.meta_transform,
+                self.load_context.priority,
+                (),
+            )
         } else {
             self.load_context
                 .asset_server
@@ -145,7 +148,11 @@ impl<'ctx, 'builder> UntypedNestedLoader<'ctx, 'builder> {
             self.base
                 .load_context
                 .asset_server
-                .load_untyped_with_meta_transform(path, self.base.meta_transform)
+                .load_untyped_with_meta_transform(
+                    path,
+                    self.base.meta_transform,
+                    self.base.load_context.priority,
+                )
         } else {
             self.base
                 .load_context
@@ -0,0 +1,48 @@
+use crate::{Asset, Assets};
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::system::Res;
+use std::marker::PhantomData;
+
+/// Adds an "asset memory" diagnostic for the assets of type `A` to an App, measuring the bytes of CPU memory
+/// used by [`Assets<A>`] as reported by [`Assets::memory_usage`].
+///
+/// The diagnostic is named `asset_memory/<type>`, using the short type path of `A`.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](bevy_diagnostic::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct AssetMemoryDiagnosticsPlugin<A: Asset> {
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A: Asset> Default for AssetMemoryDiagnosticsPlugin<A> {
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<A: Asset> Plugin for AssetMemoryDiagnosticsPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::diagnostic_path()).with_suffix("B"))
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl<A: Asset> AssetMemoryDiagnosticsPlugin<A> {
+    pub const ASSET_MEMORY: &'static str = "asset_memory";
+
+    /// Returns the [`DiagnosticPath`] of the memory used by the assets of type `A`.
+    pub fn diagnostic_path() -> DiagnosticPath {
+        DiagnosticPath::from_components([Self::ASSET_MEMORY, A::short_type_path()])
+    }
+
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, assets: Option<Res<Assets<A>>>) {
+        let Some(assets) = assets else {
+            return;
+        };
+        diagnostics.add_measurement(&Self::diagnostic_path(), || assets.memory_usage() as f64);
+    }
+}
@@ -6,19 +6,19 @@ pub use process::*;
 
 use crate::{
     io::{
-        AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent, AssetSourceId,
-        AssetSources, AssetWriterError, ErasedAssetReader, ErasedAssetWriter,
-        MissingAssetSourceError,
+        get_meta_path, AssetReaderError, AssetSource, AssetSourceBuilders, AssetSourceEvent,
+        AssetSourceId, AssetSources, AssetWriterError, ErasedAssetReader, ErasedAssetWriter,
+        MissingAssetSourceError, MissingProcessedAssetReaderError,
     },
     meta::{
         get_asset_hash, get_full_asset_hash, AssetAction, AssetActionMinimal, AssetHash, AssetMeta,
         AssetMetaDyn, AssetMetaMinimal, ProcessedInfo, ProcessedInfoMinimal,
     },
     AssetLoadError, AssetMetaCheck, AssetPath, AssetServer, AssetServerMode, DeserializeMetaError,
-    MissingAssetLoaderForExtensionError,
+    ErasedLoadedAsset, MissingAssetLoaderForExtensionError,
 };
 use bevy_ecs::prelude::*;
-use bevy_tasks::IoTaskPool;
+use bevy_tasks::{IoTaskPool, TaskPriority};
 use bevy_utils::tracing::{debug, error, trace, warn};
 #[cfg(feature = "trace")]
 use bevy_utils::{
@@ -508,49 +508,153 @@ impl AssetProcessor {
         processors.get(processor_type_name).cloned()
     }
 
-    /// Populates the initial view of each asset by scanning the unprocessed and processed asset folders.
-    /// This info will later be used to determine whether or not to re-process an asset
+    /// Writes the processed assets of the given source, and their metadata, into a pack at `path`
+    /// that can be read with a [`PackAssetReader`](crate::io::pack::PackAssetReader). This waits
+    /// until the processor has finished processing assets.
     ///
-    /// This will validate transactions and recover failed transactions when necessary.
-    #[allow(unused)]
-    async fn initialize(&self) -> Result<(), InitializeError> {
-        self.validate_transaction_log_and_recover().await;
-        let mut asset_infos = self.data.asset_infos.write().await;
-
-        /// Retrieves asset paths recursively. If `clean_empty_folders_writer` is Some, it will be used to clean up empty
-        /// folders when they are discovered.
-        async fn get_asset_paths<'a>(
+    /// This is typically used when preparing a release, so the processed assets can be shipped as
+    /// a single file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn write_pack<'a>(
+        &self,
+        source: impl Into<AssetSourceId<'a>>,
+        path: impl AsRef<Path>,
+        compression: crate::io::pack::PackCompression,
+    ) -> Result<(), WritePackError> {
+        /// Adds the assets in the directory at `path` to the pack, recursively.
+        async fn add_assets<'a>(
             reader: &'a dyn ErasedAssetReader,
-            clean_empty_folders_writer: Option<&'a dyn ErasedAssetWriter>,
             path: PathBuf,
-            paths: &'a mut Vec<PathBuf>,
-        ) -> Result<bool, AssetReaderError> {
+            pack: &'a mut crate::io::pack::PackWriter<std::io::BufWriter<std::fs::File>>,
+            compression: crate::io::pack::PackCompression,
+        ) -> Result<(), WritePackError> {
             if reader.is_directory(&path).await? {
                 let mut path_stream = reader.read_directory(&path).await?;
-                let mut contains_files = false;
-
                 while let Some(child_path) = path_stream.next().await {
-                    contains_files |= Box::pin(get_asset_paths(
-                        reader,
-                        clean_empty_folders_writer,
-                        child_path,
-                        paths,
-                    ))
-                    .await?;
+                    Box::pin(add_assets(reader, child_path, pack, compression)).await?;
                 }
-                if !contains_files && path.parent().is_some() {
-                    if let Some(writer) = clean_empty_folders_writer {
-                        // it is ok for this to fail as it is just a cleanup job.
-                        let _ = writer.remove_empty_directory(&path).await;
-                    }
+                return Ok(());
+            }
+            let mut bytes = Vec::new();
+            reader.read(&path).await?.read_to_end(&mut bytes).await?;
+            pack.add(&path, &bytes, compression)?;
+            match reader.read_meta_bytes(&path).await {
+                Ok(meta_bytes) => {
+                    pack.add(&get_meta_path(&path), &meta_bytes, compression)?;
                 }
-                Ok(contains_files)
-            } else {
-                paths.push(path);
-                Ok(true)
+                Err(AssetReaderError::NotFound(_)) => {}
+                Err(err) => return Err(err.into()),
             }
+            Ok(())
         }
 
+        self.data.wait_until_finished().await;
+        let source = self.get_source(source)?;
+        let reader = source.processed_reader()?;
+        let file = std::fs::File::create(path)?;
+        let mut pack = crate::io::pack::PackWriter::new(std::io::BufWriter::new(file))?;
+        add_assets(reader, PathBuf::from(""), &mut pack, compression).await?;
+        pack.finish()?;
+        Ok(())
+    }
+
+    /// Returns the processed assets of the given source that are never referenced by the `roots`, directly or
+    /// through other assets, sorted by path. These are the assets that an app only loading the `roots` never uses,
+    /// which can be pruned from the content repository. This waits until the processor has finished processing
+    /// assets.
+    ///
+    /// References are found by loading the processed assets with their loaders, without loading their
+    /// dependencies, so assets loaded by path from code must be part of the `roots`.
+    pub async fn unused_assets<'a>(
+        &self,
+        source: impl Into<AssetSourceId<'a>>,
+        roots: impl IntoIterator<Item = impl Into<AssetPath<'a>>>,
+    ) -> Result<Vec<AssetPath<'static>>, UnusedAssetsError> {
+        /// Adds the paths of the assets referenced by `asset` and its labeled assets to `paths`.
+        fn add_references(
+            server: &AssetServer,
+            asset: &ErasedLoadedAsset,
+            paths: &mut Vec<AssetPath<'static>>,
+        ) {
+            {
+                let infos = server.data.infos.read();
+                paths.extend(
+                    asset
+                        .dependencies
+                        .iter()
+                        .filter_map(|id| infos.get(*id)?.path.clone()),
+                );
+            }
+            paths.extend(asset.loader_dependencies.keys().cloned());
+            for labeled_asset in asset.labeled_assets.values() {
+                add_references(server, &labeled_asset.asset, paths);
+            }
+        }
+
+        self.data.wait_until_finished().await;
+        let source = self.get_source(source)?;
+        let mut processed_paths = Vec::new();
+        get_asset_paths(
+            source.processed_reader()?,
+            None,
+            PathBuf::from(""),
+            &mut processed_paths,
+        )
+        .await?;
+
+        let mut referenced = HashSet::new();
+        let mut queue: Vec<AssetPath<'static>> = roots
+            .into_iter()
+            .map(|path| Into::<AssetPath>::into(path).into_owned())
+            .collect();
+        while let Some(path) = queue.pop() {
+            let path = path.without_label().into_owned();
+            if !referenced.insert(path.clone()) {
+                continue;
+            }
+            // Assets that can't be loaded, such as files without a loader, don't reference other assets.
+            let Ok((meta, loader, mut reader)) =
+                self.server.get_meta_loader_and_reader(&path, None).await
+            else {
+                continue;
+            };
+            match self
+                .server
+                .load_with_meta_loader_and_reader(
+                    &path,
+                    meta,
+                    &*loader,
+                    &mut *reader,
+                    false,
+                    false,
+                    TaskPriority::default(),
+                )
+                .await
+            {
+                Ok(asset) => add_references(&self.server, &asset, &mut queue),
+                Err(err) => warn!("Failed to load {path} to find the assets it references: {err}"),
+            }
+        }
+        self.server.data.infos.write().consume_handle_drop_events();
+
+        let mut unused: Vec<_> = processed_paths
+            .into_iter()
+            .map(|path| AssetPath::from(path).with_source(source.id()))
+            .filter(|path| !referenced.contains(path))
+            .collect();
+        unused.sort_by_key(ToString::to_string);
+        Ok(unused)
+    }
+
+    /// Populates the initial view of each asset by scanning the unprocessed and processed asset folders.
+    /// This info will later be used to determine whether or not to re-process an asset
+    ///
+    /// This will validate transactions and recover failed transactions when necessary.
+    #[allow(unused)]
+    async fn initialize(&self) -> Result<(), InitializeError> {
+        self.validate_transaction_log_and_recover().await;
+        let mut asset_infos = self.data.asset_infos.write().await;
+
         for source in self.sources().iter_processed() {
             let Ok(processed_reader) = source.processed_reader() else {
                 continue;
@@ -1375,3 +1479,221 @@ pub enum InitializeError {
     #[error("Failed to validate asset log: {0}")]
     ValidateLogError(ValidateLogError),
 }
+
+/// Retrieves asset paths recursively. If `clean_empty_folders_writer` is Some, it will be used to clean up empty
+/// folders when they are discovered.
+async fn get_asset_paths<'a>(
+    reader: &'a dyn ErasedAssetReader,
+    clean_empty_folders_writer: Option<&'a dyn ErasedAssetWriter>,
+    path: PathBuf,
+    paths: &'a mut Vec<PathBuf>,
+) -> Result<bool, AssetReaderError> {
+    if reader.is_directory(&path).await? {
+        let mut path_stream = reader.read_directory(&path).await?;
+        let mut contains_files = false;
+
+        while let Some(child_path) = path_stream.next().await {
+            contains_files |= Box::pin(get_asset_paths(
+                reader,
+                clean_empty_folders_writer,
+                child_path,
+                paths,
+            ))
+            .await?;
+        }
+        if !contains_files && path.parent().is_some() {
+            if let Some(writer) = clean_empty_folders_writer {
+                // it is ok for this to fail as it is just a cleanup job.
+                let _ = writer.remove_empty_directory(&path).await;
+            }
+        }
+        Ok(contains_files)
+    } else {
+        paths.push(path);
+        Ok(true)
+    }
+}
+
+/// An error that occurs when writing a pack with [`AssetProcessor::write_pack`].
+#[derive(Error, Debug)]
+pub enum WritePackError {
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingProcessedAssetReader(#[from] MissingProcessedAssetReaderError),
+    #[error("Failed to read processed asset: {0}")]
+    AssetReaderError(#[from] AssetReaderError),
+    #[error("Failed to write pack: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// An error that occurs when listing unused assets with [`AssetProcessor::unused_assets`].
+#[derive(Error, Debug)]
+pub enum UnusedAssetsError {
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingProcessedAssetReader(#[from] MissingProcessedAssetReaderError),
+    #[error("Failed to read processed asset paths: {0}")]
+    AssetReaderError(#[from] AssetReaderError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory::{Dir, MemoryAssetReader};
+
+    /// Creates a processor for the already processed assets in `processed`, without processing assets.
+    fn processed_test_processor(processed: Dir) -> AssetProcessor {
+        let mut builders = AssetSourceBuilders::default();
+        builders.insert(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(|| {
+                    Box::new(MemoryAssetReader {
+                        root: Dir::default(),
+                    })
+                })
+                .with_processed_reader(move || {
+                    Box::new(MemoryAssetReader {
+                        root: processed.clone(),
+                    })
+                }),
+        );
+        let processor = AssetProcessor::new(&mut builders);
+        bevy_tasks::block_on(async {
+            let reader = processor
+                .get_source(AssetSourceId::Default)
+                .unwrap()
+                .processed_reader()
+                .unwrap();
+            let mut paths = Vec::new();
+            get_asset_paths(reader, None, PathBuf::from(""), &mut paths)
+                .await
+                .unwrap();
+            let mut infos = processor.data.asset_infos.write().await;
+            for path in paths {
+                infos
+                    .get_or_insert(AssetPath::from(path))
+                    .update_status(ProcessStatus::Processed)
+                    .await;
+            }
+            drop(infos);
+            processor.set_state(ProcessorState::Finished).await;
+        });
+        processor
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn write_pack() {
+        use crate::io::pack::{PackAssetReader, PackCompression};
+
+        let processed = Dir::default();
+        processed.insert_asset_text(Path::new("a.txt"), "a");
+        processed.insert_meta_text(Path::new("a.txt"), "(meta)");
+        processed.insert_asset_text(Path::new("folder/b.txt"), "b");
+        let processor = processed_test_processor(processed);
+
+        let dir =
+            std::env::temp_dir().join(format!("bevy_asset_write_pack_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pack_path = dir.join("assets.pack");
+        bevy_tasks::block_on(processor.write_pack(
+            AssetSourceId::Default,
+            &pack_path,
+            PackCompression::None,
+        ))
+        .unwrap();
+
+        let pack = PackAssetReader::open(&pack_path).unwrap();
+        bevy_tasks::block_on(async {
+            let read = |path: &'static str| {
+                let pack = &pack;
+                async move {
+                    let mut bytes = Vec::new();
+                    AssetReader::read(pack, Path::new(path))
+                        .await
+                        .unwrap()
+                        .read_to_end(&mut bytes)
+                        .await
+                        .unwrap();
+                    bytes
+                }
+            };
+            assert_eq!(read("a.txt").await, b"a");
+            assert_eq!(read("folder/b.txt").await, b"b");
+            assert_eq!(
+                AssetReader::read_meta_bytes(&pack, Path::new("a.txt"))
+                    .await
+                    .unwrap(),
+                b"(meta)"
+            );
+            assert!(matches!(
+                AssetReader::read_meta_bytes(&pack, Path::new("folder/b.txt")).await,
+                Err(AssetReaderError::NotFound(_))
+            ));
+        });
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn unused_assets() {
+        use crate::{
+            tests::{CoolText, CoolTextLoader, SubText},
+            Assets,
+        };
+
+        let cool_text = |dependencies: &[&str], embedded: &[&str], sub_texts: &[&str]| {
+            let list = |paths: &[&str]| {
+                paths
+                    .iter()
+                    .map(|path| format!("\"{path}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            format!(
+                r#"(
+    text: "",
+    dependencies: [{}],
+    embedded_dependencies: [{}],
+    sub_texts: [{}],
+)"#,
+                list(dependencies),
+                list(embedded),
+                list(sub_texts)
+            )
+        };
+        let processed = Dir::default();
+        processed.insert_asset_text(
+            Path::new("a.cool.ron"),
+            &cool_text(&["b.cool.ron", "c.cool.ron#sub"], &["d.cool.ron"], &[]),
+        );
+        processed.insert_asset_text(Path::new("b.cool.ron"), &cool_text(&[], &[], &[]));
+        processed.insert_asset_text(Path::new("c.cool.ron"), &cool_text(&[], &[], &["sub"]));
+        processed.insert_asset_text(Path::new("d.cool.ron"), &cool_text(&[], &[], &[]));
+        // Unused assets are reported even if they reference used ones.
+        processed.insert_asset_text(
+            Path::new("folder/unused.cool.ron"),
+            &cool_text(&["b.cool.ron"], &[], &[]),
+        );
+        processed.insert_asset_text(Path::new("unused.txt"), "unused");
+        let processor = processed_test_processor(processed);
+        let server = processor.server();
+        server.register_loader(CoolTextLoader);
+        server.register_asset(&Assets::<CoolText>::default());
+        server.register_asset(&Assets::<SubText>::default());
+
+        let unused =
+            bevy_tasks::block_on(processor.unused_assets(AssetSourceId::Default, ["a.cool.ron"]))
+                .unwrap();
+        assert_eq!(
+            unused,
+            [
+                AssetPath::from("folder/unused.cool.ron"),
+                AssetPath::from("unused.txt")
+            ]
+        );
+    }
+}
@@ -11,6 +11,7 @@ use crate::{
     AssetLoadError, AssetLoader, AssetPath, DeserializeMetaError, ErasedLoadedAsset,
     MissingAssetLoaderForExtensionError, MissingAssetLoaderForTypeNameError,
 };
+use bevy_tasks::TaskPriority;
 use bevy_utils::{BoxedFuture, ConditionalSendFuture};
 use serde::{Deserialize, Serialize};
 use std::marker::PhantomData;
@@ -355,6 +356,7 @@ impl<'a> ProcessContext<'a> {
                 &mut reader,
                 false,
                 true,
+                TaskPriority::default(),
             )
             .await?;
         for (path, full_hash) in &loaded_asset.loader_dependencies {
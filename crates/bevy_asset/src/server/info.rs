@@ -1,8 +1,10 @@
+#[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+use super::LoadTask;
 use crate::{
     meta::{AssetHash, MetaTransform},
-    Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
-    Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
-    UntypedAssetId, UntypedHandle,
+    Asset, AssetDependencyGraph, AssetGraphNode, AssetHandleProvider, AssetLoadError, AssetPath,
+    DependencyLoadState, ErasedLoadedAsset, Handle, InternalAssetEvent, LoadState,
+    RecursiveDependencyLoadState, StrongHandle, UntypedAssetId, UntypedHandle,
 };
 use bevy_ecs::world::World;
 use bevy_utils::tracing::warn;
@@ -27,6 +29,10 @@ pub(crate) struct AssetInfo {
     failed_rec_dependencies: HashSet<UntypedAssetId>,
     dependants_waiting_on_load: HashSet<UntypedAssetId>,
     dependants_waiting_on_recursive_dep_load: HashSet<UntypedAssetId>,
+    /// The direct dependencies of this asset, set when it is loaded.
+    pub(crate) dependencies: HashSet<UntypedAssetId>,
+    /// The loaded assets that directly depend on this asset.
+    pub(crate) dependants: HashSet<UntypedAssetId>,
     /// The asset paths required to load this asset. Hashes will only be set for processed assets.
     /// This is set using the value from [`LoadedAsset`].
     /// This will only be populated if [`AssetInfos::watching_for_changes`] is set to `true` to
@@ -37,6 +43,9 @@ pub(crate) struct AssetInfo {
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
+    /// The task loading this asset, which is cancelled when this info is dropped.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+    pub(crate) load_task: Option<LoadTask>,
 }
 
 impl AssetInfo {
@@ -54,7 +63,11 @@ impl AssetInfo {
             loader_dependencies: HashMap::default(),
             dependants_waiting_on_load: HashSet::default(),
             dependants_waiting_on_recursive_dep_load: HashSet::default(),
+            dependencies: HashSet::default(),
+            dependants: HashSet::default(),
             handle_drops_to_skip: 0,
+            #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+            load_task: None,
         }
     }
 }
@@ -383,6 +396,7 @@ impl AssetInfos {
         }
 
         loaded_asset.value.insert(loaded_asset_id, world);
+        self.set_dependencies(loaded_asset_id, loaded_asset.dependencies.clone());
         let mut loading_deps = loaded_asset.dependencies;
         let mut failed_deps = HashSet::new();
         let mut loading_rec_deps = loading_deps.clone();
@@ -529,6 +543,60 @@ impl AssetInfos {
         }
     }
 
+    /// Replaces the dependencies of the asset `id` in the dependency graph.
+    fn set_dependencies(&mut self, id: UntypedAssetId, dependencies: HashSet<UntypedAssetId>) {
+        let Some(info) = self.get_mut(id) else {
+            return;
+        };
+        let old_dependencies = std::mem::replace(&mut info.dependencies, dependencies.clone());
+        for dependency in old_dependencies.difference(&dependencies) {
+            if let Some(info) = self.get_mut(*dependency) {
+                info.dependants.remove(&id);
+            }
+        }
+        for dependency in &dependencies {
+            if let Some(info) = self.get_mut(*dependency) {
+                info.dependants.insert(id);
+            }
+        }
+    }
+
+    /// Returns the graph of the given assets and their recursive dependencies.
+    pub(crate) fn dependency_graph(
+        &self,
+        roots: impl IntoIterator<Item = UntypedAssetId>,
+    ) -> AssetDependencyGraph {
+        let mut graph = AssetDependencyGraph::default();
+        let mut indices = HashMap::new();
+        let mut queue: Vec<_> = roots.into_iter().collect();
+        let mut edges = Vec::new();
+        while let Some(id) = queue.pop() {
+            let Entry::Vacant(entry) = indices.entry(id) else {
+                continue;
+            };
+            entry.insert(graph.nodes.len());
+            let info = self.infos.get(&id);
+            graph.nodes.push(AssetGraphNode {
+                id,
+                path: info.and_then(|info| info.path.clone()),
+            });
+            for dependency in info.into_iter().flat_map(|info| &info.dependencies) {
+                edges.push((id, *dependency));
+                queue.push(*dependency);
+            }
+        }
+        graph.edges = edges
+            .into_iter()
+            .map(|(dependant, dependency)| (indices[&dependant], indices[&dependency]))
+            .collect();
+        graph
+    }
+
+    /// Returns the ids of all the assets managed by the server.
+    pub(crate) fn ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.infos.keys().copied()
+    }
+
     /// Recursively propagates loaded state up the dependency tree.
     fn propagate_loaded_state(
         infos: &mut AssetInfos,
@@ -588,9 +656,11 @@ impl AssetInfos {
 
     pub(crate) fn process_asset_fail(&mut self, failed_id: UntypedAssetId, error: AssetLoadError) {
         let (dependants_waiting_on_load, dependants_waiting_on_rec_load) = {
-            let info = self
-                .get_mut(failed_id)
-                .expect("Asset info should always exist at this point");
+            // Check whether the handle has been dropped since the load failed, which also
+            // cancels the load.
+            let Some(info) = self.get_mut(failed_id) else {
+                return;
+            };
             info.load_state = LoadState::Failed(Box::new(error));
             info.dep_load_state = DependencyLoadState::Failed;
             info.rec_dep_load_state = RecursiveDependencyLoadState::Failed;
@@ -664,6 +734,17 @@ impl AssetInfos {
         let type_id = entry.key().type_id();
 
         let info = entry.remove();
+        for dependency in &info.dependencies {
+            if let Some(dependency_info) = infos.get_mut(dependency) {
+                dependency_info.dependants.remove(&id);
+            }
+        }
+        for dependant in &info.dependants {
+            if let Some(dependant_info) = infos.get_mut(dependant) {
+                dependant_info.dependencies.remove(&id);
+            }
+        }
+
         let Some(path) = &info.path else {
             return true;
         };
@@ -0,0 +1,322 @@
+use crate::io::Reader;
+use bevy_tasks::TaskPriority;
+use futures_lite::{future::poll_fn, AsyncSeekExt};
+use parking_lot::Mutex;
+use std::{
+    io::SeekFrom,
+    sync::Arc,
+    task::{Poll, Waker},
+};
+
+/// Limits on the loads an [`AssetServer`](crate::AssetServer) runs at the same time, set with
+/// [`AssetPlugin::load_budget`](crate::AssetPlugin::load_budget) or
+/// [`AssetServer::set_load_budget`](crate::AssetServer::set_load_budget).
+///
+/// Loads over the budget wait until enough of the running loads have finished, and are then let
+/// through by [`TaskPriority`]. Assets loaded directly by [`AssetLoader`](crate::AssetLoader)s as
+/// part of another load do not count towards the budget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AssetLoadBudget {
+    /// The maximum number of assets loaded at the same time. No limit if `None`.
+    ///
+    /// Loads wait for this limit before their asset file is opened, so it also limits the memory
+    /// used by [`AssetReader`](crate::io::AssetReader)s that read whole files when opening them,
+    /// such as the ones for HTTP and asset packs.
+    pub max_concurrent_loads: Option<usize>,
+    /// The maximum total size in bytes of the asset files loaded at the same time. No limit if `None`.
+    ///
+    /// The size of a file is only known once it is opened, so the loads of
+    /// [`AssetReader`](crate::io::AssetReader)s that read whole files when opening them wait for
+    /// this limit with the file in memory. An asset larger than this limit is loaded once no other
+    /// asset is in flight. Assets read from an [`AssetReader`](crate::io::AssetReader) that can't
+    /// seek to the end of their file are not counted.
+    pub max_bytes_in_flight: Option<usize>,
+}
+
+/// Tracks the loads in flight of an [`AssetServer`](crate::AssetServer) against its [`AssetLoadBudget`].
+#[derive(Clone, Default)]
+pub(crate) struct LoadLimiter {
+    state: Arc<Mutex<LoadLimiterState>>,
+}
+
+#[derive(Default)]
+struct LoadLimiterState {
+    budget: AssetLoadBudget,
+    loads: usize,
+    bytes: usize,
+    next_waiter_id: u64,
+    /// Loads waiting to start.
+    load_waiters: WaitQueue,
+    /// Started loads waiting for their file to fit in the bytes in flight.
+    byte_waiters: WaitQueue,
+}
+
+impl LoadLimiterState {
+    fn has_load_slot(&self) -> bool {
+        // A budget of zero loads would never load anything, so at least one load is allowed.
+        self.budget
+            .max_concurrent_loads
+            .map_or(true, |max| self.loads < max.max(1))
+    }
+
+    fn fits_bytes(&self, bytes: usize) -> bool {
+        self.budget.max_bytes_in_flight.map_or(true, |max| {
+            self.bytes == 0 || self.bytes.saturating_add(bytes) <= max
+        })
+    }
+
+    fn wake_waiters(&self) {
+        self.load_waiters.wake_first();
+        self.byte_waiters.wake_first();
+    }
+}
+
+/// Waiting loads, ordered by [`TaskPriority`] and then by arrival. Only the first one may go
+/// through, so that loads of a lower priority don't take the budget freed for a higher one.
+#[derive(Default)]
+struct WaitQueue(Vec<Waiter>);
+
+struct Waiter {
+    priority: TaskPriority,
+    id: u64,
+    waker: Waker,
+}
+
+impl WaitQueue {
+    /// Adds the waiter `id`, or updates its waker, and returns whether it is the first one.
+    fn wait(&mut self, priority: TaskPriority, id: u64, waker: &Waker) -> bool {
+        match self.0.iter_mut().find(|waiter| waiter.id == id) {
+            Some(waiter) => waiter.waker.clone_from(waker),
+            None => {
+                let index = self
+                    .0
+                    .partition_point(|waiter| (waiter.priority, waiter.id) < (priority, id));
+                self.0.insert(
+                    index,
+                    Waiter {
+                        priority,
+                        id,
+                        waker: waker.clone(),
+                    },
+                );
+            }
+        }
+        self.0[0].id == id
+    }
+
+    fn remove(&mut self, id: u64) -> bool {
+        let Some(index) = self.0.iter().position(|waiter| waiter.id == id) else {
+            return false;
+        };
+        self.0.remove(index);
+        true
+    }
+
+    fn wake_first(&self) {
+        if let Some(waiter) = self.0.first() {
+            waiter.waker.wake_by_ref();
+        }
+    }
+}
+
+/// Removes a waiter from its queue if the load stops waiting, for example because it was cancelled.
+struct WaiterGuard<'a> {
+    limiter: &'a LoadLimiter,
+    id: u64,
+    byte_waiter: bool,
+}
+
+impl Drop for WaiterGuard<'_> {
+    fn drop(&mut self) {
+        let mut state = self.limiter.state.lock();
+        let queue = if self.byte_waiter {
+            &mut state.byte_waiters
+        } else {
+            &mut state.load_waiters
+        };
+        if queue.remove(self.id) {
+            state.wake_waiters();
+        }
+    }
+}
+
+impl LoadLimiter {
+    pub(crate) fn budget(&self) -> AssetLoadBudget {
+        self.state.lock().budget
+    }
+
+    pub(crate) fn set_budget(&self, budget: AssetLoadBudget) {
+        let mut state = self.state.lock();
+        state.budget = budget;
+        state.wake_waiters();
+    }
+
+    pub(crate) fn loads_in_flight(&self) -> usize {
+        self.state.lock().loads
+    }
+
+    pub(crate) fn bytes_in_flight(&self) -> usize {
+        self.state.lock().bytes
+    }
+
+    fn waiter(&self, byte_waiter: bool) -> WaiterGuard<'_> {
+        let mut state = self.state.lock();
+        let id = state.next_waiter_id;
+        state.next_waiter_id += 1;
+        WaiterGuard {
+            limiter: self,
+            id,
+            byte_waiter,
+        }
+    }
+
+    /// Waits until a load of the given `priority` fits in the budget. The load is in flight until
+    /// the returned permit is dropped.
+    pub(crate) async fn acquire(&self, priority: TaskPriority) -> LoadPermit {
+        let waiter = self.waiter(false);
+        poll_fn(|cx| {
+            let mut state = self.state.lock();
+            if state.has_load_slot() && state.load_waiters.0.is_empty() {
+                state.loads += 1;
+                return Poll::Ready(());
+            }
+            if state.load_waiters.wait(priority, waiter.id, cx.waker()) && state.has_load_slot() {
+                state.load_waiters.remove(waiter.id);
+                state.loads += 1;
+                // The next load may fit as well.
+                state.load_waiters.wake_first();
+                return Poll::Ready(());
+            }
+            Poll::Pending
+        })
+        .await;
+        LoadPermit {
+            limiter: self.clone(),
+            priority,
+            bytes: 0,
+        }
+    }
+}
+
+/// A load in flight, which frees its share of the [`AssetLoadBudget`] when dropped.
+pub(crate) struct LoadPermit {
+    limiter: LoadLimiter,
+    priority: TaskPriority,
+    bytes: usize,
+}
+
+impl LoadPermit {
+    /// Waits until the file read by `reader` fits in the bytes in flight, if the budget limits them.
+    pub(crate) async fn reserve_file_bytes(
+        &mut self,
+        reader: &mut Reader<'_>,
+    ) -> std::io::Result<()> {
+        if self.limiter.budget().max_bytes_in_flight.is_none() {
+            return Ok(());
+        }
+        let Ok(len) = reader.seek(SeekFrom::End(0)).await else {
+            return Ok(());
+        };
+        reader.seek(SeekFrom::Start(0)).await?;
+        let bytes = usize::try_from(len).unwrap_or(usize::MAX);
+
+        let limiter = &self.limiter;
+        let waiter = limiter.waiter(true);
+        poll_fn(|cx| {
+            let mut state = limiter.state.lock();
+            if state.fits_bytes(bytes) && state.byte_waiters.0.is_empty() {
+                state.bytes = state.bytes.saturating_add(bytes);
+                return Poll::Ready(());
+            }
+            if state
+                .byte_waiters
+                .wait(self.priority, waiter.id, cx.waker())
+                && state.fits_bytes(bytes)
+            {
+                state.byte_waiters.remove(waiter.id);
+                state.bytes = state.bytes.saturating_add(bytes);
+                state.byte_waiters.wake_first();
+                return Poll::Ready(());
+            }
+            Poll::Pending
+        })
+        .await;
+        self.bytes = bytes;
+        Ok(())
+    }
+}
+
+impl Drop for LoadPermit {
+    fn drop(&mut self) {
+        let mut state = self.limiter.state.lock();
+        state.loads -= 1;
+        state.bytes -= self.bytes;
+        state.wake_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::VecReader;
+    use futures_lite::future::{block_on, poll_once};
+
+    #[test]
+    fn loads_wait_for_budget() {
+        let limiter = LoadLimiter::default();
+        limiter.set_budget(AssetLoadBudget {
+            max_concurrent_loads: Some(2),
+            max_bytes_in_flight: Some(10),
+        });
+
+        let first = block_on(limiter.acquire(TaskPriority::Normal));
+        let mut second = block_on(limiter.acquire(TaskPriority::Normal));
+        assert!(block_on(poll_once(limiter.acquire(TaskPriority::Normal))).is_none());
+        drop(first);
+        let mut third = block_on(limiter.acquire(TaskPriority::Normal));
+        assert_eq!(limiter.loads_in_flight(), 2);
+
+        let mut reader = VecReader::new(vec![0; 8]);
+        block_on(second.reserve_file_bytes(&mut reader)).unwrap();
+        assert_eq!(limiter.bytes_in_flight(), 8);
+        let mut reader = VecReader::new(vec![0; 4]);
+        assert!(block_on(poll_once(third.reserve_file_bytes(&mut reader))).is_none());
+        drop(second);
+        block_on(third.reserve_file_bytes(&mut reader)).unwrap();
+        assert_eq!(limiter.bytes_in_flight(), 4);
+        drop(third);
+        assert_eq!(limiter.loads_in_flight(), 0);
+        assert_eq!(limiter.bytes_in_flight(), 0);
+    }
+
+    #[test]
+    fn waiting_loads_start_by_priority() {
+        let limiter = LoadLimiter::default();
+        limiter.set_budget(AssetLoadBudget {
+            max_concurrent_loads: Some(1),
+            max_bytes_in_flight: None,
+        });
+
+        let running = block_on(limiter.acquire(TaskPriority::Normal));
+        let mut background = Box::pin(limiter.acquire(TaskPriority::Background));
+        let mut high = Box::pin(limiter.acquire(TaskPriority::High));
+        assert!(block_on(poll_once(background.as_mut())).is_none());
+        assert!(block_on(poll_once(high.as_mut())).is_none());
+
+        drop(running);
+        // The background load waited longer, but the high priority one goes first.
+        assert!(block_on(poll_once(background.as_mut())).is_none());
+        let high = block_on(high);
+        assert!(block_on(poll_once(background.as_mut())).is_none());
+        drop(high);
+        block_on(background);
+
+        // Cancelled loads leave the queue.
+        let running = block_on(limiter.acquire(TaskPriority::Normal));
+        let mut cancelled = Box::pin(limiter.acquire(TaskPriority::High));
+        assert!(block_on(poll_once(cancelled.as_mut())).is_none());
+        drop(cancelled);
+        drop(running);
+        block_on(limiter.acquire(TaskPriority::Background));
+    }
+}
@@ -0,0 +1,120 @@
+use super::load_budget::{LoadLimiter, LoadPermit};
+use bevy_tasks::{IoTaskPool, Task, TaskPriority};
+use bevy_utils::BoxedFuture;
+use parking_lot::Mutex;
+use std::sync::{
+    atomic::{AtomicU64, AtomicUsize, Ordering},
+    Arc,
+};
+
+/// The generation of a [`LoadRequest`] once one of its tasks has started loading.
+const STARTED: usize = usize::MAX;
+
+/// Builds the load future for the priority of the task that starts it, once the load fits in the
+/// [`AssetLoadBudget`](super::AssetLoadBudget).
+pub(crate) type LoadFn =
+    Box<dyn FnOnce(TaskPriority, LoadPermit) -> BoxedFuture<'static, ()> + Send>;
+
+/// A load spawned on the [`IoTaskPool`], which is cancelled when dropped.
+///
+/// Until the load starts, it can be moved to another priority by spawning a new task, which
+/// only runs the load if it is the latest task of the request. Loads waiting for the
+/// [`AssetLoadBudget`](super::AssetLoadBudget) haven't started yet.
+pub(crate) struct LoadTask {
+    id: u64,
+    task: Task<()>,
+    priority: TaskPriority,
+    request: Arc<LoadRequest>,
+}
+
+struct LoadRequest {
+    limiter: LoadLimiter,
+    /// Incremented whenever a new task is spawned for this request, or [`STARTED`].
+    generation: AtomicUsize,
+    load: Mutex<Option<LoadFn>>,
+}
+
+impl LoadTask {
+    /// Returns a new id for a [`LoadTask`], so a load can tell whether it is still the one tracked by its asset.
+    pub(crate) fn next_id() -> u64 {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        NEXT_ID.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub(crate) fn spawn(
+        id: u64,
+        priority: TaskPriority,
+        limiter: LoadLimiter,
+        load: LoadFn,
+    ) -> Self {
+        let request = Arc::new(LoadRequest {
+            limiter,
+            generation: AtomicUsize::new(0),
+            load: Mutex::new(Some(load)),
+        });
+        Self {
+            id,
+            task: spawn_task(request.clone(), 0, priority),
+            priority,
+            request,
+        }
+    }
+
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Moves the load to the given priority, if it hasn't started yet.
+    pub(crate) fn set_priority(&mut self, priority: TaskPriority) {
+        if priority == self.priority {
+            return;
+        }
+        let generation = self.request.generation.load(Ordering::Acquire);
+        if generation == STARTED
+            || self
+                .request
+                .generation
+                .compare_exchange(
+                    generation,
+                    generation + 1,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                )
+                .is_err()
+        {
+            return;
+        }
+        // The previous task can no longer start the load, so it can be cancelled.
+        self.task = spawn_task(self.request.clone(), generation + 1, priority);
+        self.priority = priority;
+    }
+}
+
+impl std::fmt::Debug for LoadTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LoadTask")
+            .field("priority", &self.priority)
+            .finish_non_exhaustive()
+    }
+}
+
+fn spawn_task(request: Arc<LoadRequest>, generation: usize, priority: TaskPriority) -> Task<()> {
+    IoTaskPool::get().spawn_with_priority(priority, async move {
+        if request.generation.load(Ordering::Acquire) != generation {
+            return;
+        }
+        // The task is cancelled while it waits if the load is moved to another priority.
+        let permit = request.limiter.acquire(priority).await;
+        if request
+            .generation
+            .compare_exchange(generation, STARTED, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return;
+        }
+        let load = request.load.lock().take();
+        if let Some(load) = load {
+            load(priority, permit).await;
+        }
+    })
+}
@@ -1,4 +1,7 @@
 mod info;
+mod load_budget;
+#[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+mod load_task;
 mod loaders;
 
 use crate::{
@@ -13,17 +16,21 @@ use crate::{
         MetaTransform, Settings,
     },
     path::AssetPath,
-    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent, AssetMetaCheck, Assets,
-    DeserializeMetaError, ErasedLoadedAsset, Handle, LoadedUntypedAsset, UntypedAssetId,
-    UntypedAssetLoadFailedEvent, UntypedHandle,
+    Asset, AssetDependencyGraph, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent,
+    AssetMetaCheck, Assets, DeserializeMetaError, ErasedLoadedAsset, Handle, LoadedUntypedAsset,
+    UntypedAssetId, UntypedAssetLoadFailedEvent, UntypedHandle,
 };
 use bevy_ecs::prelude::*;
-use bevy_tasks::IoTaskPool;
+use bevy_tasks::{IoTaskPool, TaskPriority};
 use bevy_utils::tracing::{error, info};
-use bevy_utils::{CowArc, HashSet};
+use bevy_utils::{ConditionalSendFuture, CowArc, HashSet};
 use crossbeam_channel::{Receiver, Sender};
 use futures_lite::StreamExt;
 use info::*;
+pub use load_budget::AssetLoadBudget;
+use load_budget::*;
+#[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+use load_task::*;
 use loaders::*;
 use parking_lot::RwLock;
 use std::future::Future;
@@ -63,6 +70,7 @@ pub(crate) struct AssetServerData {
     sources: AssetSources,
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
+    load_limiter: LoadLimiter,
 }
 
 /// The "asset mode" the server is currently in.
@@ -123,6 +131,7 @@ impl AssetServer {
                 asset_event_receiver,
                 loaders,
                 infos: RwLock::new(infos),
+                load_limiter: LoadLimiter::default(),
             }),
         }
     }
@@ -140,6 +149,28 @@ impl AssetServer {
         self.data.infos.read().watching_for_changes
     }
 
+    /// Returns the [`AssetLoadBudget`] limiting the loads in flight.
+    pub fn load_budget(&self) -> AssetLoadBudget {
+        self.data.load_limiter.budget()
+    }
+
+    /// Sets the [`AssetLoadBudget`] limiting the loads in flight. Loads that are already in flight are not
+    /// cancelled if they exceed the new budget.
+    pub fn set_load_budget(&self, budget: AssetLoadBudget) {
+        self.data.load_limiter.set_budget(budget);
+    }
+
+    /// Returns the number of assets currently being loaded within the [`AssetLoadBudget`].
+    pub fn loads_in_flight(&self) -> usize {
+        self.data.load_limiter.loads_in_flight()
+    }
+
+    /// Returns the total size in bytes of the asset files currently being loaded within the [`AssetLoadBudget`].
+    /// Only counted if the budget limits [`AssetLoadBudget::max_bytes_in_flight`].
+    pub fn bytes_in_flight(&self) -> usize {
+        self.data.load_limiter.bytes_in_flight()
+    }
+
     /// Registers a new [`AssetLoader`]. [`AssetLoader`]s must be registered before they can be used.
     pub fn register_loader<L: AssetLoader>(&self, loader: L) {
         self.data.loaders.write().push(loader);
@@ -300,10 +331,26 @@ impl AssetServer {
     /// You can check the asset's load state by reading [`AssetEvent`] events, calling [`AssetServer::load_state`], or checking
     /// the [`Assets`] storage to see if the [`Asset`] exists yet.
     ///
+    /// If all the strong handles to the asset are dropped before it is loaded, the load is cancelled (except on
+    /// platforms without multi-threading, where it runs to completion and the asset is discarded).
+    ///
     /// The asset load will fail and an error will be printed to the logs if the asset stored at `path` is not of type `A`.
     #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
     pub fn load<'a, A: Asset>(&self, path: impl Into<AssetPath<'a>>) -> Handle<A> {
-        self.load_with_meta_transform(path, None, ())
+        self.load_with_meta_transform(path, None, TaskPriority::default(), ())
+    }
+
+    /// Begins loading an [`Asset`] of type `A` stored at `path` with the given `priority`. Loads of a higher priority
+    /// are started first when the [`IoTaskPool`] is busy. See [`AssetServer::load`] for more details.
+    ///
+    /// The priority of a load that hasn't started yet can be changed with [`AssetServer::set_load_priority`].
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_priority<'a, A: Asset>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        priority: TaskPriority,
+    ) -> Handle<A> {
+        self.load_with_meta_transform(path, None, priority, ())
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path` while holding a guard item.
@@ -327,7 +374,7 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         guard: G,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, None, guard)
+        self.load_with_meta_transform(path, None, TaskPriority::default(), guard)
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path`. The given `settings` function will override the asset's
@@ -339,7 +386,33 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
         settings: impl Fn(&mut S) + Send + Sync + 'static,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, Some(loader_settings_meta_transform(settings)), ())
+        self.load_with_meta_transform(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            TaskPriority::default(),
+            (),
+        )
+    }
+
+    /// Begins loading an [`Asset`] of type `A` stored at `path` with the given `priority`. The given `settings`
+    /// function will override the asset's [`AssetLoader`] settings, like in [`AssetServer::load_with_settings`].
+    ///
+    /// Loads of a higher priority are started first when the [`IoTaskPool`] is busy, so assets needed right away,
+    /// such as the ones visible by the camera, can use [`TaskPriority::High`] and prefetched ones
+    /// [`TaskPriority::Background`].
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings_and_priority<'a, A: Asset, S: Settings>(
+        &self,
+        path: impl Into<AssetPath<'a>>,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+        priority: TaskPriority,
+    ) -> Handle<A> {
+        self.load_with_meta_transform(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            priority,
+            (),
+        )
     }
 
     /// Begins loading an [`Asset`] of type `A` stored at `path` while holding a guard item.
@@ -358,13 +431,91 @@ impl AssetServer {
         settings: impl Fn(&mut S) + Send + Sync + 'static,
         guard: G,
     ) -> Handle<A> {
-        self.load_with_meta_transform(path, Some(loader_settings_meta_transform(settings)), guard)
+        self.load_with_meta_transform(
+            path,
+            Some(loader_settings_meta_transform(settings)),
+            TaskPriority::default(),
+            guard,
+        )
+    }
+
+    /// Changes the priority of the load of the asset `id` if it hasn't started yet. Loads that already started keep
+    /// running at their current priority.
+    ///
+    /// This can be used to reprioritize the loads of a streamed world as the camera moves. Priorities are ignored on
+    /// platforms without multi-threading.
+    pub fn set_load_priority(&self, id: impl Into<UntypedAssetId>, priority: TaskPriority) {
+        #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+        if let Some(task) = self
+            .data
+            .infos
+            .write()
+            .get_mut(id.into())
+            .and_then(|info| info.load_task.as_mut())
+        {
+            task.set_priority(priority);
+        }
+        #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
+        let _ = (id, priority);
+    }
+
+    /// Spawns the `load` of the asset `id` on the [`IoTaskPool`]. On multi-threaded platforms, the load is tracked
+    /// by the asset, so it is cancelled when the asset is dropped and its priority can be changed.
+    ///
+    /// `load` is called once the load fits in the [`AssetLoadBudget`], with the priority the load runs at,
+    /// so that its dependencies can be loaded at the same priority.
+    fn spawn_load_task<F>(
+        &self,
+        id: UntypedAssetId,
+        priority: TaskPriority,
+        load: impl FnOnce(TaskPriority, LoadPermit) -> F + Send + 'static,
+    ) where
+        F: ConditionalSendFuture<Output = ()> + 'static,
+    {
+        #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+        {
+            let server = self.clone();
+            let task_id = LoadTask::next_id();
+            let load: LoadFn = Box::new(move |priority, permit| {
+                Box::pin(async move {
+                    load(priority, permit).await;
+                    // A newer load of the asset may have been spawned since, which must keep running.
+                    if let Some(info) = server.data.infos.write().get_mut(id) {
+                        if info
+                            .load_task
+                            .as_ref()
+                            .is_some_and(|task| task.id() == task_id)
+                        {
+                            info.load_task = None;
+                        }
+                    }
+                })
+            });
+            // Hold the lock while spawning, so the load can't finish before its task is stored.
+            let mut infos = self.data.infos.write();
+            let task = LoadTask::spawn(task_id, priority, self.data.load_limiter.clone(), load);
+            if let Some(info) = infos.get_mut(id) {
+                info.load_task = Some(task);
+            }
+        }
+        #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
+        {
+            let _ = id;
+            let limiter = self.data.load_limiter.clone();
+            IoTaskPool::get()
+                .spawn_with_priority(priority, async move {
+                    let permit = limiter.acquire(priority).await;
+                    load(priority, permit).await;
+                })
+                .detach();
+        }
     }
 
     pub(crate) fn load_with_meta_transform<'a, A: Asset, G: Send + Sync + 'static>(
         &self,
         path: impl Into<AssetPath<'a>>,
         meta_transform: Option<MetaTransform>,
+        priority: TaskPriority,
         guard: G,
     ) -> Handle<A> {
         let path = path.into().into_owned();
@@ -375,16 +526,22 @@ impl AssetServer {
         );
 
         if should_load {
-            let owned_handle = Some(handle.clone().untyped());
+            // The load only holds a weak handle, so that dropping the asset cancels it.
+            let owned_handle = Some(handle.clone_weak().untyped());
             let server = self.clone();
-            IoTaskPool::get()
-                .spawn(async move {
-                    if let Err(err) = server.load_internal(owned_handle, path, false, None).await {
+            self.spawn_load_task(
+                handle.id().untyped(),
+                priority,
+                move |priority, permit| async move {
+                    if let Err(err) = server
+                        .load_internal(owned_handle, path, false, None, priority, Some(permit))
+                        .await
+                    {
                         error!("{}", err);
                     }
                     drop(guard);
-                })
-                .detach();
+                },
+            );
         }
 
         handle
@@ -399,13 +556,15 @@ impl AssetServer {
         path: impl Into<AssetPath<'a>>,
     ) -> Result<UntypedHandle, AssetLoadError> {
         let path: AssetPath = path.into();
-        self.load_internal(None, path, false, None).await
+        self.load_internal(None, path, false, None, TaskPriority::default(), None)
+            .await
     }
 
     pub(crate) fn load_untyped_with_meta_transform<'a>(
         &self,
         path: impl Into<AssetPath<'a>>,
         meta_transform: Option<MetaTransform>,
+        priority: TaskPriority,
     ) -> Handle<LoadedUntypedAsset> {
         let path = path.into().into_owned();
         let untyped_source = AssetSourceId::Name(match path.source() {
@@ -429,29 +588,30 @@ impl AssetServer {
         let id = handle.id().untyped();
 
         let server = self.clone();
-        IoTaskPool::get()
-            .spawn(async move {
-                let path_clone = path.clone();
-                match server.load_untyped_async(path).await {
-                    Ok(handle) => server.send_asset_event(InternalAssetEvent::Loaded {
+        self.spawn_load_task(id, priority, move |priority, permit| async move {
+            let path_clone = path.clone();
+            match server
+                .load_internal(None, path, false, None, priority, Some(permit))
+                .await
+            {
+                Ok(handle) => server.send_asset_event(InternalAssetEvent::Loaded {
+                    id,
+                    loaded_asset: LoadedAsset::new_with_dependencies(
+                        LoadedUntypedAsset { handle },
+                        None,
+                    )
+                    .into(),
+                }),
+                Err(err) => {
+                    error!("{err}");
+                    server.send_asset_event(InternalAssetEvent::Failed {
                         id,
-                        loaded_asset: LoadedAsset::new_with_dependencies(
-                            LoadedUntypedAsset { handle },
-                            None,
-                        )
-                        .into(),
-                    }),
-                    Err(err) => {
-                        error!("{err}");
-                        server.send_asset_event(InternalAssetEvent::Failed {
-                            id,
-                            path: path_clone,
-                            error: err,
-                        });
-                    }
+                        path: path_clone,
+                        error: err,
+                    });
                 }
-            })
-            .detach();
+            }
+        });
         handle
     }
 
@@ -479,21 +639,29 @@ impl AssetServer {
     /// required to figure out the asset type before a handle can be created.
     #[must_use = "not using the returned strong handle may result in the unexpected release of the assets"]
     pub fn load_untyped<'a>(&self, path: impl Into<AssetPath<'a>>) -> Handle<LoadedUntypedAsset> {
-        self.load_untyped_with_meta_transform(path, None)
+        self.load_untyped_with_meta_transform(path, None, TaskPriority::default())
     }
 
     /// Performs an async asset load.
     ///
     /// `input_handle` must only be [`Some`] if `should_load` was true when retrieving `input_handle`. This is an optimization to
     /// avoid looking up `should_load` twice, but it means you _must_ be sure a load is necessary when calling this function with [`Some`].
+    ///
+    /// If no `permit` is given, the load waits for one before opening the asset, and holds it until the load finishes.
     async fn load_internal<'a>(
         &self,
         input_handle: Option<UntypedHandle>,
         path: AssetPath<'a>,
         force: bool,
         meta_transform: Option<MetaTransform>,
+        priority: TaskPriority,
+        permit: Option<LoadPermit>,
     ) -> Result<UntypedHandle, AssetLoadError> {
         let asset_type_id = input_handle.as_ref().map(|handle| handle.type_id());
+        let mut permit = match permit {
+            Some(permit) => permit,
+            None => self.data.load_limiter.acquire(priority).await,
+        };
 
         let path = path.into_owned();
         let path_clone = path.clone();
@@ -581,14 +749,40 @@ impl AssetServer {
             (handle.clone().unwrap(), path.clone())
         };
 
-        if let Some(meta_transform) = base_handle.meta_transform() {
-            (*meta_transform)(&mut *meta);
+        {
+            // Loads spawned by the server only hold a weak handle, so the transform is read from a
+            // temporary strong handle, which must not be held during the load.
+            let strong_base_handle = match &base_handle {
+                UntypedHandle::Weak(id) => self.data.infos.read().get_id_handle(*id),
+                UntypedHandle::Strong(_) => None,
+            };
+            if let Some(meta_transform) = strong_base_handle
+                .as_ref()
+                .unwrap_or(&base_handle)
+                .meta_transform()
+            {
+                (*meta_transform)(&mut *meta);
+            }
         }
 
-        match self
-            .load_with_meta_loader_and_reader(&base_path, meta, &*loader, &mut *reader, true, false)
-            .await
-        {
+        let result = match permit.reserve_file_bytes(&mut *reader).await {
+            Ok(()) => {
+                self.load_with_meta_loader_and_reader(
+                    &base_path,
+                    meta,
+                    &*loader,
+                    &mut *reader,
+                    true,
+                    false,
+                    priority,
+                )
+                .await
+            }
+            Err(err) => Err(AssetReaderError::from(err).into()),
+        };
+        drop(permit);
+
+        match result {
             Ok(loaded_asset) => {
                 let final_handle = if let Some(label) = path.label_cow() {
                     match loaded_asset.labeled_assets.get(&label) {
@@ -649,7 +843,16 @@ impl AssetServer {
                     .infos
                     .read()
                     .get_path_handles(&path)
-                    .map(|handle| server.load_internal(Some(handle), path.clone(), true, None))
+                    .map(|handle| {
+                        server.load_internal(
+                            Some(handle),
+                            path.clone(),
+                            true,
+                            None,
+                            TaskPriority::default(),
+                            None,
+                        )
+                    })
                     .collect::<Vec<_>>();
 
                 for result in requests {
@@ -660,7 +863,10 @@ impl AssetServer {
                 }
 
                 if !reloaded && server.data.infos.read().should_reload(&path) {
-                    if let Err(err) = server.load_internal(None, path, true, None).await {
+                    if let Err(err) = server
+                        .load_internal(None, path, true, None, TaskPriority::default(), None)
+                        .await
+                    {
                         error!("{}", err);
                     }
                 }
@@ -913,6 +1119,41 @@ impl AssetServer {
             .map(|i| i.rec_dep_load_state)
     }
 
+    /// Returns the direct dependencies of the asset `id`, recorded when it was loaded, or `None` if the asset isn't
+    /// managed by the [`AssetServer`].
+    pub fn get_dependencies(&self, id: impl Into<UntypedAssetId>) -> Option<Vec<UntypedAssetId>> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .map(|i| i.dependencies.iter().copied().collect())
+    }
+
+    /// Returns the loaded assets that directly depend on the asset `id`, or `None` if the asset isn't managed by
+    /// the [`AssetServer`].
+    pub fn get_dependants(&self, id: impl Into<UntypedAssetId>) -> Option<Vec<UntypedAssetId>> {
+        self.data
+            .infos
+            .read()
+            .get(id.into())
+            .map(|i| i.dependants.iter().copied().collect())
+    }
+
+    /// Returns the graph of the `roots` assets and their recursive dependencies, which can be exported to inspect
+    /// why assets are loaded.
+    pub fn dependency_graph(
+        &self,
+        roots: impl IntoIterator<Item = UntypedAssetId>,
+    ) -> AssetDependencyGraph {
+        self.data.infos.read().dependency_graph(roots)
+    }
+
+    /// Returns the graph of all the assets managed by the [`AssetServer`].
+    pub fn full_dependency_graph(&self) -> AssetDependencyGraph {
+        let infos = self.data.infos.read();
+        infos.dependency_graph(infos.ids())
+    }
+
     /// Retrieves the main [`LoadState`] of a given asset `id`.
     pub fn load_state(&self, id: impl Into<UntypedAssetId>) -> LoadState {
         self.get_load_state(id).unwrap_or(LoadState::NotLoaded)
@@ -1171,11 +1412,17 @@ impl AssetServer {
         reader: &mut Reader<'_>,
         load_dependencies: bool,
         populate_hashes: bool,
+        priority: TaskPriority,
     ) -> Result<ErasedLoadedAsset, AssetLoadError> {
         // TODO: experiment with this
         let asset_path = asset_path.clone_owned();
-        let load_context =
-            LoadContext::new(self, asset_path.clone(), load_dependencies, populate_hashes);
+        let load_context = LoadContext::new(
+            self,
+            asset_path.clone(),
+            load_dependencies,
+            populate_hashes,
+            priority,
+        );
         loader.load(reader, meta, load_context).await.map_err(|e| {
             AssetLoadError::AssetLoaderError(AssetLoaderError {
                 path: asset_path.clone_owned(),
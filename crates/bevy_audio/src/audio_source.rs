@@ -1,12 +1,12 @@
 use bevy_asset::{
     io::{AsyncReadExt, Reader},
-    Asset, AssetLoader, LoadContext,
+    Asset, AssetLoader, LoadContext, VisitAssetDependencies,
 };
 use bevy_reflect::TypePath;
 use std::{io::Cursor, sync::Arc};
 
 /// A source of audio data
-#[derive(Asset, Debug, Clone, TypePath)]
+#[derive(VisitAssetDependencies, Debug, Clone, TypePath)]
 pub struct AudioSource {
     /// Raw data of the audio source.
     ///
@@ -22,6 +22,12 @@ pub struct AudioSource {
     pub bytes: Arc<[u8]>,
 }
 
+impl Asset for AudioSource {
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.bytes.len()
+    }
+}
+
 impl AsRef<[u8]> for AudioSource {
     fn as_ref(&self) -> &[u8] {
         &self.bytes
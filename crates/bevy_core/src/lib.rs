@@ -25,7 +25,7 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, system::apply_command_tasks};
 use std::marker::PhantomData;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -43,6 +43,11 @@ impl Plugin for TypeRegistrationPlugin {
 
 /// Setup of default task pools: [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool),
 /// [`ComputeTaskPool`](bevy_tasks::ComputeTaskPool), [`IoTaskPool`](bevy_tasks::IoTaskPool).
+///
+/// This also applies the commands returned by tasks spawned with
+/// [`Commands::spawn_task`] in [`PreUpdate`], using [`apply_command_tasks`].
+///
+/// [`apply_command_tasks`]: bevy_ecs::system::apply_command_tasks
 #[derive(Default)]
 pub struct TaskPoolPlugin {
     /// Options for the [`TaskPool`](bevy_tasks::TaskPool) created at application start.
@@ -50,12 +55,14 @@ pub struct TaskPoolPlugin {
 }
 
 impl Plugin for TaskPoolPlugin {
-    fn build(&self, _app: &mut App) {
+    fn build(&self, app: &mut App) {
         // Setup the default bevy task pools
         self.task_pool_options.create_default_pools();
 
+        app.add_systems(PreUpdate, apply_command_tasks);
+
         #[cfg(not(target_arch = "wasm32"))]
-        _app.add_systems(Last, tick_global_task_pools);
+        app.add_systems(Last, tick_global_task_pools);
     }
 }
 /// A dummy type that is [`!Send`](Send), to force systems to run on the main thread.
@@ -139,6 +146,26 @@ mod tests {
         io_rx.try_recv().unwrap();
     }
 
+    #[test]
+    fn applies_command_tasks() {
+        #[derive(Resource)]
+        struct Loaded;
+
+        let mut app = App::new();
+        app.add_plugins(TaskPoolPlugin::default()).add_systems(
+            Startup,
+            |mut commands: Commands| {
+                commands.spawn_task(async { |world: &mut World| world.insert_resource(Loaded) });
+            },
+        );
+
+        let start = std::time::Instant::now();
+        while !app.world().contains_resource::<Loaded>() {
+            assert!(start.elapsed() < std::time::Duration::from_secs(10));
+            app.update();
+        }
+    }
+
     #[test]
     fn frame_counter_update() {
         let mut app = App::new();
@@ -1,4 +1,12 @@
+use std::path::PathBuf;
+
 use bevy_ecs::prelude::*;
+use bevy_input::{
+    gamepad::{GamepadAxisType, GamepadButtonType},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+};
+use bevy_math::Vec2;
 use serde::Deserialize;
 
 /// A configuration struct for automated CI testing.
@@ -25,6 +33,38 @@ pub struct CiTestingSetup {
     ///
     /// [`TimeUpdateStrategy::ManualDuration`]: bevy_time::TimeUpdateStrategy::ManualDuration
     pub fixed_frame_time: Option<f32>,
+    /// The directory containing the reference images of [`CiTestingEvent::CompareScreenshot`],
+    /// `golden_images` by default.
+    ///
+    /// When the `CI_TESTING_UPDATE_GOLDEN_IMAGES` environment variable is set, the screenshots are
+    /// saved as the new reference images instead of being compared.
+    pub golden_images_dir: Option<PathBuf>,
+    /// The default tolerance of [`CiTestingEvent::CompareScreenshot`].
+    #[serde(default)]
+    pub golden_image_tolerance: ImageTolerance,
+}
+
+/// How different a screenshot can be from its reference image.
+#[derive(Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(default)]
+pub struct ImageTolerance {
+    /// The largest difference between two pixels that is still considered the same color,
+    /// as a distance in the Oklab color space.
+    ///
+    /// A distance of `0.02` is barely noticeable, and black and white are `1.0` apart.
+    pub pixel_threshold: f32,
+    /// The fraction of the pixels that can differ by more than
+    /// [`pixel_threshold`](Self::pixel_threshold).
+    pub max_differing_pixels: f32,
+}
+
+impl Default for ImageTolerance {
+    fn default() -> Self {
+        Self {
+            pixel_threshold: 0.02,
+            max_differing_pixels: 0.001,
+        }
+    }
 }
 
 /// An event to send at a given frame, used for CI testing.
@@ -41,8 +81,59 @@ pub enum CiTestingEvent {
     ///
     /// [`AppExit::Success`]: bevy_app::AppExit::Success
     AppExit,
+    /// Takes a screenshot of the entire screen and compares it to the reference image
+    /// `{name}.png` in [`CiTestingSetup::golden_images_dir`].
+    ///
+    /// If the images differ by more than the tolerance, or if there is no reference image, the
+    /// screenshot is saved next to the reference image as `{name}.actual.png` and the app exits
+    /// with an error at the next [`CiTestingEvent::AppExit`]. The app also exits with an error if
+    /// the screenshot can't be taken, or isn't compared within 100 frames of
+    /// [`CiTestingEvent::AppExit`].
+    CompareScreenshot {
+        /// The name of the reference image, without extension.
+        name: String,
+        /// The tolerance of the comparison, [`CiTestingSetup::golden_image_tolerance`] if `None`.
+        #[serde(default)]
+        tolerance: Option<ImageTolerance>,
+    },
     /// Sends a [`CiTestingCustomEvent`] using the given [`String`].
     Custom(String),
+    /// Presses a key of the keyboard in the primary window.
+    KeyPress(KeyCode),
+    /// Releases a key of the keyboard in the primary window.
+    KeyRelease(KeyCode),
+    /// Presses a mouse button in the primary window.
+    MouseButtonPress(MouseButton),
+    /// Releases a mouse button in the primary window.
+    MouseButtonRelease(MouseButton),
+    /// Moves the cursor to the given position of the primary window, in logical pixels.
+    CursorMove(Vec2),
+    /// Moves the mouse by the given delta, as a raw mouse motion.
+    MouseMotion(Vec2),
+    /// Scrolls the mouse wheel in the primary window by the given number of lines.
+    MouseWheel(Vec2),
+    /// Changes the value of a button of the gamepad with the given id, between `0.0` and `1.0`.
+    ///
+    /// The gamepad is connected the first time it is used.
+    GamepadButton {
+        /// The id of the gamepad.
+        gamepad: usize,
+        /// The button to change.
+        button: GamepadButtonType,
+        /// The new value of the button.
+        value: f32,
+    },
+    /// Changes the value of an axis of the gamepad with the given id, between `-1.0` and `1.0`.
+    ///
+    /// The gamepad is connected the first time it is used.
+    GamepadAxis {
+        /// The id of the gamepad.
+        gamepad: usize,
+        /// The axis to change.
+        axis: GamepadAxisType,
+        /// The new value of the axis.
+        value: f32,
+    },
 }
 
 /// A custom event that can be configured from a configuration file for CI testing.
@@ -59,10 +150,17 @@ mod tests {
 (
     setup: (
         fixed_frame_time: Some(0.03),
+        golden_images_dir: Some("tests/golden"),
+        golden_image_tolerance: (pixel_threshold: 0.05),
     ),
     events: [
         (100, Custom("Hello, world!")),
+        (150, KeyPress(Space)),
+        (151, KeyRelease(Space)),
+        (160, CursorMove((10.0, 20.0))),
+        (161, GamepadButton(gamepad: 0, button: South, value: 1.0)),
         (200, Screenshot),
+        (250, CompareScreenshot(name: "menu")),
         (300, AppExit),
     ],
 )"#;
@@ -70,10 +168,33 @@ mod tests {
         let expected = CiTestingConfig {
             setup: CiTestingSetup {
                 fixed_frame_time: Some(0.03),
+                golden_images_dir: Some("tests/golden".into()),
+                golden_image_tolerance: ImageTolerance {
+                    pixel_threshold: 0.05,
+                    ..Default::default()
+                },
             },
             events: vec![
                 CiTestingEventOnFrame(100, CiTestingEvent::Custom("Hello, world!".into())),
+                CiTestingEventOnFrame(150, CiTestingEvent::KeyPress(KeyCode::Space)),
+                CiTestingEventOnFrame(151, CiTestingEvent::KeyRelease(KeyCode::Space)),
+                CiTestingEventOnFrame(160, CiTestingEvent::CursorMove(Vec2::new(10.0, 20.0))),
+                CiTestingEventOnFrame(
+                    161,
+                    CiTestingEvent::GamepadButton {
+                        gamepad: 0,
+                        button: GamepadButtonType::South,
+                        value: 1.0,
+                    },
+                ),
                 CiTestingEventOnFrame(200, CiTestingEvent::Screenshot),
+                CiTestingEventOnFrame(
+                    250,
+                    CiTestingEvent::CompareScreenshot {
+                        name: "menu".into(),
+                        tolerance: None,
+                    },
+                ),
                 CiTestingEventOnFrame(300, CiTestingEvent::AppExit),
             ],
         };
@@ -0,0 +1,215 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use bevy_color::{LinearRgba, Oklaba, Srgba};
+use bevy_ecs::prelude::*;
+use bevy_render::texture::Image;
+use bevy_utils::tracing::{error, info};
+use image::RgbImage;
+
+use super::config::ImageTolerance;
+
+/// The environment variable that makes [`CiTestingEvent::CompareScreenshot`](super::CiTestingEvent::CompareScreenshot)
+/// save the screenshots as the new reference images.
+const UPDATE_GOLDEN_IMAGES: &str = "CI_TESTING_UPDATE_GOLDEN_IMAGES";
+
+/// The outcome of the screenshot comparisons, which finish on another thread.
+#[derive(Resource, Clone, Default)]
+pub(crate) struct GoldenImageResults(Arc<Mutex<GoldenImageState>>);
+
+#[derive(Default)]
+struct GoldenImageState {
+    pending: usize,
+    failures: Vec<String>,
+}
+
+impl GoldenImageResults {
+    fn lock(&self) -> std::sync::MutexGuard<'_, GoldenImageState> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns `true` if some screenshots haven't been compared yet.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.lock().pending > 0
+    }
+
+    /// Returns the errors of the failed comparisons.
+    pub(crate) fn failures(&self) -> Vec<String> {
+        self.lock().failures.clone()
+    }
+
+    /// Returns a screenshot callback comparing it to the reference image `{name}.png` in `dir`.
+    ///
+    /// The comparison fails if the callback is dropped without being called.
+    pub(crate) fn compare(
+        &self,
+        dir: PathBuf,
+        name: String,
+        tolerance: ImageTolerance,
+    ) -> impl FnOnce(Image) + Send + Sync + 'static {
+        self.lock().pending += 1;
+        let comparison = PendingComparison {
+            results: self.clone(),
+            name,
+            result: None,
+        };
+        move |image| {
+            let mut comparison = comparison;
+            comparison.result = Some(check_screenshot(image, &dir, &comparison.name, tolerance));
+        }
+    }
+}
+
+/// A comparison whose outcome is recorded when dropped.
+struct PendingComparison {
+    results: GoldenImageResults,
+    name: String,
+    result: Option<Result<(), String>>,
+}
+
+impl Drop for PendingComparison {
+    fn drop(&mut self) {
+        let name = &self.name;
+        let result = self
+            .result
+            .take()
+            .unwrap_or_else(|| Err("the screenshot was not taken".to_string()));
+        let mut state = self.results.lock();
+        state.pending -= 1;
+        match result {
+            Ok(()) => info!("Screenshot matches golden image `{name}`."),
+            Err(err) => {
+                error!("Comparison with golden image `{name}` failed: {err}");
+                state.failures.push(format!("`{name}`: {err}"));
+            }
+        }
+    }
+}
+
+fn check_screenshot(
+    image: Image,
+    dir: &Path,
+    name: &str,
+    tolerance: ImageTolerance,
+) -> Result<(), String> {
+    let actual = image
+        .try_into_dynamic()
+        .map_err(|err| format!("screen format cannot be understood: {err}"))?
+        // Discard the alpha channel, like screenshots saved to disk.
+        .to_rgb8();
+    let reference_path = dir.join(format!("{name}.png"));
+
+    if std::env::var_os(UPDATE_GOLDEN_IMAGES).is_some() {
+        std::fs::create_dir_all(dir)
+            .and_then(|()| actual.save(&reference_path).map_err(std::io::Error::other))
+            .map_err(|err| format!("cannot save {}: {err}", reference_path.display()))?;
+        info!("Golden image saved to {}", reference_path.display());
+        return Ok(());
+    }
+
+    let result = match image::open(&reference_path) {
+        Ok(expected) => compare_images(&actual, &expected.to_rgb8(), tolerance),
+        Err(err) => Err(format!("cannot open {}: {err}", reference_path.display())),
+    };
+    if result.is_err() {
+        let actual_path = dir.join(format!("{name}.actual.png"));
+        match actual.save(&actual_path) {
+            Ok(()) => info!("Screenshot saved to {}", actual_path.display()),
+            Err(err) => error!("Cannot save screenshot, IO error: {err}"),
+        }
+    }
+    result
+}
+
+/// Compares two images by the perceptual distance between their pixels.
+pub(crate) fn compare_images(
+    actual: &RgbImage,
+    expected: &RgbImage,
+    tolerance: ImageTolerance,
+) -> Result<(), String> {
+    if actual.dimensions() != expected.dimensions() {
+        return Err(format!(
+            "the screenshot is {:?} but the golden image is {:?}",
+            actual.dimensions(),
+            expected.dimensions()
+        ));
+    }
+
+    let mut differing_pixels = 0;
+    let mut max_distance = 0.0f32;
+    for (a, b) in actual.pixels().zip(expected.pixels()) {
+        if a == b {
+            continue;
+        }
+        let distance = oklab_distance(a.0, b.0);
+        max_distance = max_distance.max(distance);
+        if distance > tolerance.pixel_threshold {
+            differing_pixels += 1;
+        }
+    }
+
+    let pixels = actual.pixels().len().max(1);
+    let differing = differing_pixels as f32 / pixels as f32;
+    if differing > tolerance.max_differing_pixels {
+        return Err(format!(
+            "{differing_pixels} pixels ({:.3}%) differ by more than {}, up to {max_distance:.3}",
+            differing * 100.0,
+            tolerance.pixel_threshold
+        ));
+    }
+    Ok(())
+}
+
+fn oklab_distance(a: [u8; 3], b: [u8; 3]) -> f32 {
+    let to_oklab = |[r, g, b]: [u8; 3]| Oklaba::from(LinearRgba::from(Srgba::rgb_u8(r, g, b)));
+    let (a, b) = (to_oklab(a), to_oklab(b));
+    let (dl, da, db) = (a.lightness - b.lightness, a.a - b.a, a.b - b.b);
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+
+    #[test]
+    fn compare() {
+        let expected = RgbImage::from_pixel(10, 10, Rgb([100, 150, 200]));
+        let tolerance = ImageTolerance {
+            pixel_threshold: 0.02,
+            max_differing_pixels: 0.05,
+        };
+        assert!(compare_images(&expected, &expected, tolerance).is_ok());
+
+        // Slightly different colors are within the threshold.
+        let mut actual = RgbImage::from_pixel(10, 10, Rgb([101, 150, 199]));
+        assert!(compare_images(&actual, &expected, tolerance).is_ok());
+
+        // A few very different pixels are tolerated, but not too many.
+        for x in 0..5 {
+            actual.put_pixel(x, 0, Rgb([255, 0, 0]));
+        }
+        assert!(compare_images(&actual, &expected, tolerance).is_ok());
+        actual.put_pixel(5, 0, Rgb([255, 0, 0]));
+        assert!(compare_images(&actual, &expected, tolerance).is_err());
+
+        let smaller = RgbImage::from_pixel(5, 10, Rgb([100, 150, 200]));
+        assert!(compare_images(&smaller, &expected, tolerance).is_err());
+    }
+
+    #[test]
+    fn screenshot_not_taken() {
+        let results = GoldenImageResults::default();
+        let compare = results.compare(
+            PathBuf::new(),
+            "name".to_string(),
+            ImageTolerance::default(),
+        );
+        assert!(results.is_pending());
+        drop(compare);
+        assert!(!results.is_pending());
+        assert_eq!(results.failures(), ["`name`: the screenshot was not taken"]);
+    }
+}
@@ -1,6 +1,7 @@
 //! Utilities for testing in CI environments.
 
 mod config;
+mod golden;
 mod systems;
 
 pub use self::config::*;
@@ -15,6 +16,10 @@ use std::time::Duration;
 /// (`ci_testing_config.ron` by default) and executes its specified actions. For a reference of the
 /// allowed configuration, see [`CiTestingConfig`].
 ///
+/// Besides taking screenshots, the configuration can compare them to reference images with
+/// [`CiTestingEvent::CompareScreenshot`], making the app exit with an error if they differ, and
+/// inject keyboard, mouse and gamepad input to script the app.
+///
 /// This plugin is included within `DefaultPlugins` and `MinimalPlugins` when the `bevy_ci_testing`
 /// feature is enabled. It is recommended to only used this plugin during testing (manual or
 /// automatic), and disable it during regular development and for production builds.
@@ -48,6 +53,7 @@ impl Plugin for CiTestingPlugin {
 
         app.add_event::<CiTestingCustomEvent>()
             .insert_resource(config)
+            .init_resource::<golden::GoldenImageResults>()
             .add_systems(Update, systems::send_events);
     }
 }
@@ -1,11 +1,32 @@
-use super::config::*;
+use super::{config::*, golden::GoldenImageResults};
 use bevy_app::AppExit;
 use bevy_ecs::prelude::*;
+use bevy_input::{
+    gamepad::{
+        Gamepad, GamepadAxisChangedEvent, GamepadButtonChangedEvent, GamepadConnection,
+        GamepadConnectionEvent, GamepadEvent, GamepadInfo,
+    },
+    keyboard::{Key, KeyboardInput, NativeKey},
+    mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    ButtonState,
+};
 use bevy_render::view::screenshot::ScreenshotManager;
-use bevy_utils::tracing::{debug, info, warn};
-use bevy_window::PrimaryWindow;
+use bevy_utils::{
+    tracing::{debug, error, info, warn},
+    HashSet,
+};
+use bevy_window::{CursorMoved, PrimaryWindow, Window};
 
-pub(crate) fn send_events(world: &mut World, mut current_frame: Local<u32>) {
+/// How many frames [`CiTestingEvent::AppExit`] waits for the screenshots to be compared before
+/// giving up.
+const GOLDEN_IMAGE_TIMEOUT_FRAMES: u32 = 100;
+
+pub(crate) fn send_events(
+    world: &mut World,
+    mut current_frame: Local<u32>,
+    mut connected_gamepads: Local<HashSet<usize>>,
+    mut exit_frame: Local<Option<u32>>,
+) {
     let mut config = world.resource_mut::<CiTestingConfig>();
 
     // Take all events for the current frame, leaving all the remaining alone.
@@ -19,13 +40,35 @@ pub(crate) fn send_events(world: &mut World, mut current_frame: Local<u32>) {
         debug!("Handling event: {:?}", event);
         match event {
             CiTestingEvent::AppExit => {
-                world.send_event(AppExit::Success);
-                info!("Exiting after {} frames. Test successful!", *current_frame);
+                let results = world.resource::<GoldenImageResults>();
+                let waited_frames = *current_frame - *exit_frame.get_or_insert(*current_frame);
+                let pending = results.is_pending();
+                if pending && waited_frames < GOLDEN_IMAGE_TIMEOUT_FRAMES {
+                    // Wait for the screenshots to be compared.
+                    let mut config = world.resource_mut::<CiTestingConfig>();
+                    let event = CiTestingEventOnFrame(*current_frame + 1, CiTestingEvent::AppExit);
+                    config.events.push(event);
+                    continue;
+                }
+                let failures = results.failures();
+                if failures.is_empty() && !pending {
+                    world.send_event(AppExit::Success);
+                    info!("Exiting after {} frames. Test successful!", *current_frame);
+                } else {
+                    world.send_event(AppExit::error());
+                    if pending {
+                        error!(
+                            "Screenshots still not compared after waiting {waited_frames} frames"
+                        );
+                    }
+                    for failure in failures {
+                        error!("Golden image comparison failed: {failure}");
+                    }
+                    error!("Exiting after {} frames. Test failed!", *current_frame);
+                }
             }
             CiTestingEvent::Screenshot => {
-                let mut primary_window_query =
-                    world.query_filtered::<Entity, With<PrimaryWindow>>();
-                let Ok(main_window) = primary_window_query.get_single(world) else {
+                let Some(main_window) = primary_window(world) else {
                     warn!("Requesting screenshot, but PrimaryWindow is not available");
                     continue;
                 };
@@ -35,17 +78,154 @@ pub(crate) fn send_events(world: &mut World, mut current_frame: Local<u32>) {
                     continue;
                 };
                 let path = format!("./screenshot-{}.png", *current_frame);
-                screenshot_manager
-                    .save_screenshot_to_disk(main_window, path)
-                    .unwrap();
+                if let Err(err) = screenshot_manager.save_screenshot_to_disk(main_window, path) {
+                    error!(
+                        "Cannot take a screenshot at frame {}: {err}",
+                        *current_frame
+                    );
+                    continue;
+                }
+                info!("Took a screenshot at frame {}.", *current_frame);
+            }
+            CiTestingEvent::CompareScreenshot { name, tolerance } => {
+                let Some(main_window) = primary_window(world) else {
+                    warn!("Requesting screenshot, but PrimaryWindow is not available");
+                    continue;
+                };
+                let config = world.resource::<CiTestingConfig>();
+                let dir = config
+                    .setup
+                    .golden_images_dir
+                    .clone()
+                    .unwrap_or_else(|| "golden_images".into());
+                let tolerance = tolerance.unwrap_or(config.setup.golden_image_tolerance);
+                let results = world.resource::<GoldenImageResults>().clone();
+                let Some(mut screenshot_manager) = world.get_resource_mut::<ScreenshotManager>()
+                else {
+                    warn!("Requesting screenshot, but ScreenshotManager is not available");
+                    continue;
+                };
+                // The comparison fails if the screenshot can't be taken.
+                let compare = results.compare(dir, name, tolerance);
+                if let Err(err) = screenshot_manager.take_screenshot(main_window, compare) {
+                    error!(
+                        "Cannot take a screenshot at frame {}: {err}",
+                        *current_frame
+                    );
+                    continue;
+                }
                 info!("Took a screenshot at frame {}.", *current_frame);
             }
             // Custom events are forwarded to the world.
             CiTestingEvent::Custom(event_string) => {
                 world.send_event(CiTestingCustomEvent(event_string));
             }
+            CiTestingEvent::KeyPress(key_code) | CiTestingEvent::KeyRelease(key_code) => {
+                let Some(window) = primary_window(world) else {
+                    warn!("Sending keyboard input, but PrimaryWindow is not available");
+                    continue;
+                };
+                let state = match event {
+                    CiTestingEvent::KeyPress(_) => ButtonState::Pressed,
+                    _ => ButtonState::Released,
+                };
+                world.send_event(KeyboardInput {
+                    key_code,
+                    logical_key: Key::Unidentified(NativeKey::Unidentified),
+                    state,
+                    window,
+                });
+            }
+            CiTestingEvent::MouseButtonPress(button)
+            | CiTestingEvent::MouseButtonRelease(button) => {
+                let Some(window) = primary_window(world) else {
+                    warn!("Sending mouse input, but PrimaryWindow is not available");
+                    continue;
+                };
+                let state = match event {
+                    CiTestingEvent::MouseButtonPress(_) => ButtonState::Pressed,
+                    _ => ButtonState::Released,
+                };
+                world.send_event(MouseButtonInput {
+                    button,
+                    state,
+                    window,
+                });
+            }
+            CiTestingEvent::CursorMove(position) => {
+                let Some(window) = primary_window(world) else {
+                    warn!("Moving the cursor, but PrimaryWindow is not available");
+                    continue;
+                };
+                let mut window_component = world.get_mut::<Window>(window).unwrap();
+                let delta = window_component
+                    .cursor_position()
+                    .map(|previous| position - previous);
+                window_component.set_cursor_position(Some(position));
+                world.send_event(CursorMoved {
+                    window,
+                    position,
+                    delta,
+                });
+            }
+            CiTestingEvent::MouseMotion(delta) => {
+                world.send_event(MouseMotion { delta });
+            }
+            CiTestingEvent::MouseWheel(delta) => {
+                let Some(window) = primary_window(world) else {
+                    warn!("Sending mouse input, but PrimaryWindow is not available");
+                    continue;
+                };
+                world.send_event(MouseWheel {
+                    unit: MouseScrollUnit::Line,
+                    x: delta.x,
+                    y: delta.y,
+                    window,
+                });
+            }
+            CiTestingEvent::GamepadButton {
+                gamepad,
+                button,
+                value,
+            } => {
+                connect_gamepad(world, &mut connected_gamepads, gamepad);
+                world.send_event(GamepadEvent::from(GamepadButtonChangedEvent::new(
+                    Gamepad::new(gamepad),
+                    button,
+                    value,
+                )));
+            }
+            CiTestingEvent::GamepadAxis {
+                gamepad,
+                axis,
+                value,
+            } => {
+                connect_gamepad(world, &mut connected_gamepads, gamepad);
+                world.send_event(GamepadEvent::from(GamepadAxisChangedEvent::new(
+                    Gamepad::new(gamepad),
+                    axis,
+                    value,
+                )));
+            }
         }
     }
 
     *current_frame += 1;
 }
+
+fn primary_window(world: &mut World) -> Option<Entity> {
+    let mut primary_window_query = world.query_filtered::<Entity, With<PrimaryWindow>>();
+    primary_window_query.get_single(world).ok()
+}
+
+/// Sends a connection event for the gamepad the first time it is used.
+fn connect_gamepad(world: &mut World, connected_gamepads: &mut HashSet<usize>, gamepad: usize) {
+    if connected_gamepads.insert(gamepad) {
+        world.send_event(GamepadEvent::from(GamepadConnectionEvent::new(
+            Gamepad::new(gamepad),
+            GamepadConnection::Connected(GamepadInfo {
+                name: "CI testing gamepad".to_string(),
+            }),
+        )));
+    }
+}
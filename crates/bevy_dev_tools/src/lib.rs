@@ -20,6 +20,8 @@ pub mod ui_debug_overlay;
 
 pub mod states;
 
+pub mod stepping;
+
 /// Enables developer tools in an [`App`]. This plugin is added automatically with `bevy_dev_tools`
 /// feature.
 ///
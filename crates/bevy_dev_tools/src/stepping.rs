@@ -0,0 +1,289 @@
+//! Module containing logic for the stepping overlay.
+//!
+//! The overlay lists the systems of the stepped schedules, marks the system under the
+//! [`Stepping`] cursor and the systems with a breakpoint. Clicking on a system toggles its
+//! breakpoint.
+//!
+//! Stepping only has an effect when Bevy is compiled with the `bevy_debug_stepping` feature.
+
+use bevy_app::{App, Last, MainScheduleOrder, Plugin, Update};
+use bevy_color::Color;
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, NodeId, ScheduleLabel, Stepping},
+};
+use bevy_hierarchy::{BuildChildren, ChildBuilder};
+use bevy_input::{keyboard::KeyCode, ButtonInput};
+use bevy_render::view::Visibility;
+use bevy_text::{Text, TextSection, TextStyle};
+use bevy_ui::{
+    node_bundles::{NodeBundle, TextBundle},
+    BackgroundColor, FlexDirection, Interaction, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_utils::default;
+
+/// Global [`ZIndex`] used to render the stepping overlay.
+///
+/// We use a number slightly under the [`FPS_OVERLAY_ZINDEX`](crate::fps_overlay::FPS_OVERLAY_ZINDEX)
+/// so the FPS overlay stays on top.
+pub const STEPPING_OVERLAY_ZINDEX: i32 = i32::MAX - 64;
+
+/// Schedule running the overlay systems.
+///
+/// The overlay needs its own schedule to be able to list the systems of the stepped schedules:
+/// the schedule that is currently running is removed from the [`Schedules`] resource.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct SteppingOverlaySchedule;
+
+/// A plugin that adds an overlay to control [`Stepping`] through the systems of the app.
+///
+/// Systems whose name starts with `bevy` are always run, so that the app keeps rendering and
+/// handling input while stepping.
+pub struct SteppingOverlayPlugin {
+    /// The schedules to step through.
+    pub schedules: Vec<InternedScheduleLabel>,
+    /// Starting configuration of overlay, this can be later be changed through [`SteppingOverlayConfig`] resource.
+    pub config: SteppingOverlayConfig,
+}
+
+impl Default for SteppingOverlayPlugin {
+    fn default() -> Self {
+        Self {
+            schedules: vec![Update.intern()],
+            config: SteppingOverlayConfig::default(),
+        }
+    }
+}
+
+impl Plugin for SteppingOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_schedule(SteppingOverlaySchedule);
+        app.world_mut()
+            .resource_mut::<MainScheduleOrder>()
+            .insert_after(Last, SteppingOverlaySchedule);
+
+        let mut stepping = app
+            .world_mut()
+            .remove_resource::<Stepping>()
+            .unwrap_or_default();
+        for label in &self.schedules {
+            stepping.add_schedule(*label);
+        }
+
+        app.insert_resource(stepping)
+            .insert_resource(self.config.clone())
+            .add_systems(
+                SteppingOverlaySchedule,
+                (
+                    build_overlay.run_if(not(any_with_component::<SteppingOverlay>)),
+                    handle_input,
+                    toggle_breakpoints,
+                    update_overlay,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// Configuration options for the stepping overlay.
+#[derive(Resource, Clone)]
+pub struct SteppingOverlayConfig {
+    /// Configuration of text in the overlay, applied when the overlay is built.
+    pub text_config: TextStyle,
+    /// Color of the overlay's background, applied when the overlay is built.
+    pub background_color: Color,
+    /// Key enabling and disabling stepping. The overlay is only visible while stepping is enabled.
+    pub toggle_key: KeyCode,
+    /// Key running the system under the cursor.
+    pub step_key: KeyCode,
+    /// Key running the systems until the end of the frame, or until the next breakpoint.
+    pub continue_key: KeyCode,
+}
+
+impl Default for SteppingOverlayConfig {
+    fn default() -> Self {
+        SteppingOverlayConfig {
+            text_config: TextStyle {
+                font_size: 16.0,
+                ..default()
+            },
+            background_color: Color::srgba(0.0, 0.0, 0.0, 0.75),
+            toggle_key: KeyCode::Backquote,
+            step_key: KeyCode::KeyS,
+            continue_key: KeyCode::Space,
+        }
+    }
+}
+
+#[derive(Component)]
+struct SteppingOverlay;
+
+/// A system listed in the overlay.
+#[derive(Component)]
+struct SteppingSystem {
+    schedule: InternedScheduleLabel,
+    node: NodeId,
+}
+
+/// Builds the overlay from the [`Schedules`] resource.
+///
+/// This may run multiple times before building the overlay, as the order of the schedules is only
+/// known once they have all run.
+fn build_overlay(
+    mut commands: Commands,
+    schedules: Res<Schedules>,
+    mut stepping: ResMut<Stepping>,
+    config: Res<SteppingOverlayConfig>,
+) {
+    let Ok(labels) = stepping.schedules() else {
+        return;
+    };
+    let labels = labels.clone();
+
+    let mut always_run = Vec::new();
+    let mut rows = Vec::new();
+    for label in labels {
+        let Some(schedule) = schedules.get(label) else {
+            continue;
+        };
+        let Ok(systems) = schedule.systems() else {
+            return;
+        };
+
+        rows.push((None, format!("{label:?}")));
+        for (node, system) in systems {
+            if system.name().starts_with("bevy") {
+                always_run.push((label, node));
+            } else {
+                rows.push((Some((label, node)), system.name().into_owned()));
+            }
+        }
+    }
+
+    for (label, node) in always_run {
+        stepping.always_run_node(label, node);
+    }
+
+    commands
+        .spawn((
+            SteppingOverlay,
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    right: Val::Px(0.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(config.background_color),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(STEPPING_OVERLAY_ZINDEX),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            for (system, name) in rows {
+                spawn_row(parent, system, name, &config.text_config);
+            }
+        });
+}
+
+fn spawn_row(
+    parent: &mut ChildBuilder,
+    system: Option<(InternedScheduleLabel, NodeId)>,
+    name: String,
+    style: &TextStyle,
+) {
+    let Some((schedule, node)) = system else {
+        parent.spawn(TextBundle::from_section(name, style.clone()));
+        return;
+    };
+    parent.spawn((
+        SteppingSystem { schedule, node },
+        Interaction::default(),
+        TextBundle::from_sections([
+            // cursor
+            TextSection::new("   ", style.clone()),
+            // breakpoint
+            TextSection::new("  ", style.clone()),
+            TextSection::new(name, style.clone()),
+        ]),
+    ));
+}
+
+fn handle_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    config: Res<SteppingOverlayConfig>,
+    mut stepping: ResMut<Stepping>,
+) {
+    if keyboard_input.just_pressed(config.toggle_key) {
+        if stepping.is_enabled() {
+            stepping.disable();
+        } else {
+            stepping.enable();
+        }
+    }
+
+    if !stepping.is_enabled() {
+        return;
+    }
+
+    if keyboard_input.just_pressed(config.step_key) {
+        stepping.step_frame();
+    } else if keyboard_input.just_pressed(config.continue_key) {
+        stepping.continue_frame();
+    }
+}
+
+fn toggle_breakpoints(
+    mut stepping: ResMut<Stepping>,
+    systems: Query<(&Interaction, &SteppingSystem), Changed<Interaction>>,
+) {
+    for (interaction, system) in &systems {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if stepping.is_breakpoint(system.schedule, system.node) {
+            stepping.clear_breakpoint_node(system.schedule, system.node);
+        } else {
+            stepping.set_breakpoint_node(system.schedule, system.node);
+        }
+    }
+}
+
+fn update_overlay(
+    stepping: Res<Stepping>,
+    mut overlay: Query<&mut Visibility, With<SteppingOverlay>>,
+    mut systems: Query<(&SteppingSystem, &mut Text)>,
+) {
+    let visibility = if stepping.is_enabled() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut overlay_visibility in &mut overlay {
+        overlay_visibility.set_if_neq(visibility);
+    }
+
+    if !stepping.is_enabled() {
+        return;
+    }
+
+    let cursor = stepping.cursor();
+    for (system, mut text) in &mut systems {
+        let cursor_mark = if cursor == Some((system.schedule, system.node)) {
+            "-> "
+        } else {
+            "   "
+        };
+        let breakpoint_mark = if stepping.is_breakpoint(system.schedule, system.node) {
+            "* "
+        } else {
+            "  "
+        };
+        if text.sections[0].value != cursor_mark || text.sections[1].value != breakpoint_mark {
+            text.sections[0].value = cursor_mark.to_string();
+            text.sections[1].value = breakpoint_mark.to_string();
+        }
+    }
+}
@@ -15,16 +15,23 @@ mod diagnostic;
 mod entity_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod sink;
 #[cfg(feature = "sysinfo_plugin")]
 mod system_information_diagnostics_plugin;
+mod system_time_diagnostics_plugin;
 
 pub use diagnostic::*;
 
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use sink::{
+    ChromeTraceDiagnosticsSink, CsvDiagnosticsSink, DiagnosticsRecord, DiagnosticsSink,
+    DiagnosticsSinkPlugin, DiagnosticsSinks, JsonLinesDiagnosticsSink,
+};
 #[cfg(feature = "sysinfo_plugin")]
 pub use system_information_diagnostics_plugin::{SystemInfo, SystemInformationDiagnosticsPlugin};
+pub use system_time_diagnostics_plugin::SystemTimeDiagnosticsPlugin;
 
 use bevy_app::prelude::*;
 
@@ -0,0 +1,91 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use super::{write_json_number, write_json_string, DiagnosticsRecord, DiagnosticsSink};
+
+/// A [`DiagnosticsSink`] writing a [Chrome trace](https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU)
+/// with one counter event per diagnostic and record, which can be opened in
+/// [Perfetto](https://ui.perfetto.dev/) or `chrome://tracing`.
+///
+/// Diagnostics without a value are skipped. The trace uses the JSON array format, whose closing
+/// bracket is optional, so it stays readable if the app doesn't exit cleanly.
+pub struct ChromeTraceDiagnosticsSink<W: Write + Send + Sync + 'static = BufWriter<File>> {
+    writer: W,
+    started: bool,
+}
+
+impl ChromeTraceDiagnosticsSink {
+    /// Creates a sink writing to the file at `path`, truncating it.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Send + Sync + 'static> ChromeTraceDiagnosticsSink<W> {
+    /// Creates a sink writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            started: false,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write + Send + Sync + 'static> DiagnosticsSink for ChromeTraceDiagnosticsSink<W> {
+    fn record(&mut self, record: &DiagnosticsRecord) -> io::Result<()> {
+        let writer = &mut self.writer;
+        let timestamp = record.elapsed.as_micros();
+        for diagnostic in record.diagnostics {
+            let Some(value) = diagnostic.value() else {
+                continue;
+            };
+            if self.started {
+                writeln!(writer, ",")?;
+            } else {
+                writeln!(writer, "[")?;
+                self.started = true;
+            }
+            write!(writer, "{{\"name\":")?;
+            write_json_string(writer, diagnostic.path().as_str())?;
+            write!(
+                writer,
+                ",\"ph\":\"C\",\"ts\":{timestamp},\"pid\":0,\"tid\":0,\"args\":{{\"value\":"
+            )?;
+            write_json_number(writer, Some(value))?;
+            write!(writer, "}}}}")?;
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::tests::{diagnostics, record};
+
+    #[test]
+    fn chrome_trace() {
+        let [a, b] = diagnostics();
+        let mut sink = ChromeTraceDiagnosticsSink::new(Vec::new());
+        record(&mut sink, 1, &[&a, &b]);
+        record(&mut sink, 2, &[&a]);
+        assert_eq!(
+            String::from_utf8(sink.into_inner()).unwrap(),
+            "[\n\
+             {\"name\":\"a\",\"ph\":\"C\",\"ts\":250000,\"pid\":0,\"tid\":0,\"args\":{\"value\":1.5}},\n\
+             {\"name\":\"a\",\"ph\":\"C\",\"ts\":500000,\"pid\":0,\"tid\":0,\"args\":{\"value\":1.5}}"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use super::{DiagnosticsRecord, DiagnosticsSink};
+use crate::DiagnosticPath;
+
+/// A [`DiagnosticsSink`] writing one CSV row per record.
+///
+/// The columns are `frame`, `time` in seconds, and the value of each diagnostic recorded the first
+/// time, in order. Diagnostics without a value are left empty, and diagnostics added afterwards
+/// are ignored.
+pub struct CsvDiagnosticsSink<W: Write + Send + Sync + 'static = BufWriter<File>> {
+    writer: W,
+    columns: Option<Vec<DiagnosticPath>>,
+}
+
+impl CsvDiagnosticsSink {
+    /// Creates a sink writing to the file at `path`, truncating it.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Send + Sync + 'static> CsvDiagnosticsSink<W> {
+    /// Creates a sink writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            columns: None,
+        }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write + Send + Sync + 'static> DiagnosticsSink for CsvDiagnosticsSink<W> {
+    fn record(&mut self, record: &DiagnosticsRecord) -> io::Result<()> {
+        let writer = &mut self.writer;
+        let columns = match &self.columns {
+            Some(columns) => columns,
+            None => {
+                let columns = record
+                    .diagnostics
+                    .iter()
+                    .map(|diagnostic| diagnostic.path().clone())
+                    .collect::<Vec<_>>();
+                write!(writer, "frame,time")?;
+                for path in &columns {
+                    write!(writer, ",")?;
+                    write_csv_field(writer, path.as_str())?;
+                }
+                writeln!(writer)?;
+                &*self.columns.insert(columns)
+            }
+        };
+
+        write!(writer, "{},{}", record.frame, record.elapsed.as_secs_f64())?;
+        for path in columns {
+            write!(writer, ",")?;
+            let value = record
+                .diagnostics
+                .iter()
+                .find(|diagnostic| diagnostic.path() == path)
+                .and_then(|diagnostic| diagnostic.value());
+            if let Some(value) = value {
+                write!(writer, "{value}")?;
+            }
+        }
+        writeln!(writer)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Writes `field`, quoting it if needed.
+fn write_csv_field(writer: &mut impl Write, field: &str) -> io::Result<()> {
+    if field.contains([',', '"', '\n', '\r']) {
+        write!(writer, "\"{}\"", field.replace('"', "\"\""))
+    } else {
+        write!(writer, "{field}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::tests::{diagnostics, record};
+
+    #[test]
+    fn csv() {
+        let [a, b] = diagnostics();
+        let mut sink = CsvDiagnosticsSink::new(Vec::new());
+        record(&mut sink, 1, &[&a, &b]);
+        // Columns are fixed by the first record.
+        record(&mut sink, 2, &[&b]);
+        assert_eq!(
+            String::from_utf8(sink.into_inner()).unwrap(),
+            "frame,time,a,b/c\n1,0.25,1.5,\n2,0.5,,\n"
+        );
+    }
+}
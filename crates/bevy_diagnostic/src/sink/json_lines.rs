@@ -0,0 +1,78 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use super::{write_json_number, write_json_string, DiagnosticsRecord, DiagnosticsSink};
+
+/// A [`DiagnosticsSink`] writing one [JSON Lines](https://jsonlines.org/) object per record.
+///
+/// Each line looks like `{"frame":12,"time":0.2,"diagnostics":{"fps":60.1,"frame_time":16.6}}`,
+/// with `time` in seconds. Diagnostics without a value are `null`.
+pub struct JsonLinesDiagnosticsSink<W: Write + Send + Sync + 'static = BufWriter<File>> {
+    writer: W,
+}
+
+impl JsonLinesDiagnosticsSink {
+    /// Creates a sink writing to the file at `path`, truncating it.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write + Send + Sync + 'static> JsonLinesDiagnosticsSink<W> {
+    /// Creates a sink writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write + Send + Sync + 'static> DiagnosticsSink for JsonLinesDiagnosticsSink<W> {
+    fn record(&mut self, record: &DiagnosticsRecord) -> io::Result<()> {
+        let writer = &mut self.writer;
+        write!(
+            writer,
+            "{{\"frame\":{},\"time\":{},\"diagnostics\":{{",
+            record.frame,
+            record.elapsed.as_secs_f64()
+        )?;
+        for (i, diagnostic) in record.diagnostics.iter().enumerate() {
+            if i > 0 {
+                write!(writer, ",")?;
+            }
+            write_json_string(writer, diagnostic.path().as_str())?;
+            write!(writer, ":")?;
+            write_json_number(writer, diagnostic.value())?;
+        }
+        writeln!(writer, "}}}}")
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::tests::{diagnostics, record};
+
+    #[test]
+    fn json_lines() {
+        let [a, b] = diagnostics();
+        let mut sink = JsonLinesDiagnosticsSink::new(Vec::new());
+        record(&mut sink, 1, &[&a, &b]);
+        record(&mut sink, 2, &[]);
+        assert_eq!(
+            String::from_utf8(sink.into_inner()).unwrap(),
+            "{\"frame\":1,\"time\":0.25,\"diagnostics\":{\"a\":1.5,\"b/c\":null}}\n\
+             {\"frame\":2,\"time\":0.5,\"diagnostics\":{}}\n"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+mod chrome_trace;
+mod csv;
+mod json_lines;
+
+pub use chrome_trace::ChromeTraceDiagnosticsSink;
+pub use csv::CsvDiagnosticsSink;
+pub use json_lines::JsonLinesDiagnosticsSink;
+
+use std::io::{self, Write};
+
+use bevy_app::prelude::*;
+use bevy_core::FrameCount;
+use bevy_ecs::prelude::*;
+use bevy_time::{Real, Time, Timer, TimerMode};
+use bevy_utils::tracing::error;
+use bevy_utils::Duration;
+
+use super::{Diagnostic, DiagnosticPath, DiagnosticsStore};
+
+/// A destination for the values of the diagnostics in the [`DiagnosticsStore`], such as a file.
+///
+/// Sinks are added to the [`DiagnosticsSinks`] resource of the [`DiagnosticsSinkPlugin`].
+/// A sink that returns an error is logged and removed.
+pub trait DiagnosticsSink: Send + Sync + 'static {
+    /// Records the current values of the diagnostics.
+    fn record(&mut self, record: &DiagnosticsRecord) -> io::Result<()>;
+
+    /// Writes out any buffered record. Called when the app exits.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The diagnostics recorded by a [`DiagnosticsSink`] during a frame.
+#[derive(Debug)]
+pub struct DiagnosticsRecord<'a> {
+    /// The current [`FrameCount`], or `0` if there is none.
+    pub frame: u32,
+    /// The [`Time<Real>`] elapsed since the startup of the app.
+    pub elapsed: Duration,
+    /// The enabled diagnostics, sorted by path unless filtered.
+    pub diagnostics: &'a [&'a Diagnostic],
+}
+
+/// An App Plugin that records diagnostics in the [`DiagnosticsSink`]s of the [`DiagnosticsSinks`]
+/// resource, in [`Last`].
+///
+/// Diagnostics are collected by plugins such as
+/// [`FrameTimeDiagnosticsPlugin`](crate::FrameTimeDiagnosticsPlugin)
+/// or can be provided by the user.
+///
+/// ```no_run
+/// use bevy_app::App;
+/// use bevy_diagnostic::{CsvDiagnosticsSink, DiagnosticsSinkPlugin, DiagnosticsSinks};
+///
+/// let mut app = App::new();
+/// app.add_plugins(DiagnosticsSinkPlugin::default());
+/// app.world_mut()
+///     .resource_mut::<DiagnosticsSinks>()
+///     .add(CsvDiagnosticsSink::create("diagnostics.csv").unwrap());
+/// ```
+pub struct DiagnosticsSinkPlugin {
+    /// How often diagnostics are recorded, every frame if zero.
+    pub wait_duration: Duration,
+    /// The diagnostics to record, all of them if `None`.
+    pub filter: Option<Vec<DiagnosticPath>>,
+}
+
+impl Default for DiagnosticsSinkPlugin {
+    fn default() -> Self {
+        DiagnosticsSinkPlugin {
+            wait_duration: Duration::ZERO,
+            filter: None,
+        }
+    }
+}
+
+impl DiagnosticsSinkPlugin {
+    pub fn filtered(filter: Vec<DiagnosticPath>) -> Self {
+        DiagnosticsSinkPlugin {
+            filter: Some(filter),
+            ..Default::default()
+        }
+    }
+}
+
+impl Plugin for DiagnosticsSinkPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DiagnosticsSinks {
+            sinks: Vec::new(),
+            timer: Timer::new(self.wait_duration, TimerMode::Repeating),
+            filter: self.filter.clone(),
+        })
+        .add_systems(Last, DiagnosticsSinks::record_diagnostics_system);
+    }
+}
+
+/// The [`DiagnosticsSink`]s used by the [`DiagnosticsSinkPlugin`].
+#[derive(Resource)]
+pub struct DiagnosticsSinks {
+    sinks: Vec<Box<dyn DiagnosticsSink>>,
+    timer: Timer,
+    filter: Option<Vec<DiagnosticPath>>,
+}
+
+impl DiagnosticsSinks {
+    /// Adds a sink, which records diagnostics from the next frame on.
+    pub fn add(&mut self, sink: impl DiagnosticsSink) -> &mut Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// Returns the number of sinks.
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Returns `true` if there are no sinks.
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Calls `f` on each sink, removing the ones that fail.
+    fn retain(&mut self, mut f: impl FnMut(&mut dyn DiagnosticsSink) -> io::Result<()>) {
+        self.sinks.retain_mut(|sink| match f(sink.as_mut()) {
+            Ok(()) => true,
+            Err(err) => {
+                error!("Failed to record diagnostics, removing the sink: {err}");
+                false
+            }
+        });
+    }
+
+    fn record_diagnostics_system(
+        mut sinks: ResMut<DiagnosticsSinks>,
+        time: Res<Time<Real>>,
+        frame_count: Option<Res<FrameCount>>,
+        diagnostics: Res<DiagnosticsStore>,
+        mut exit: EventReader<AppExit>,
+    ) {
+        if sinks.is_empty() {
+            return;
+        }
+
+        let wait_duration = sinks.timer.duration();
+        if wait_duration.is_zero() || sinks.timer.tick(time.delta()).finished() {
+            let mut recorded: Vec<&Diagnostic> = match &sinks.filter {
+                Some(filter) => filter
+                    .iter()
+                    .filter_map(|path| diagnostics.get(path))
+                    .collect(),
+                None => {
+                    let mut recorded: Vec<_> = diagnostics.iter().collect();
+                    recorded.sort_by(|a, b| a.path().as_str().cmp(b.path().as_str()));
+                    recorded
+                }
+            };
+            recorded.retain(|diagnostic| diagnostic.is_enabled);
+
+            let record = DiagnosticsRecord {
+                frame: frame_count.map_or(0, |frame_count| frame_count.0),
+                elapsed: time.elapsed(),
+                diagnostics: &recorded,
+            };
+            sinks.retain(|sink| sink.record(&record));
+        }
+
+        if exit.read().next().is_some() {
+            sinks.retain(DiagnosticsSink::flush);
+        }
+    }
+}
+
+/// Writes `value` as a JSON number, or `null` if it isn't finite.
+fn write_json_number(writer: &mut impl Write, value: Option<f64>) -> io::Result<()> {
+    match value {
+        Some(value) if value.is_finite() => write!(writer, "{value}"),
+        _ => write!(writer, "null"),
+    }
+}
+
+/// Writes `value` as a JSON string.
+fn write_json_string(writer: &mut impl Write, value: &str) -> io::Result<()> {
+    write!(writer, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(writer, "\\\"")?,
+            '\\' => write!(writer, "\\\\")?,
+            '\n' => write!(writer, "\\n")?,
+            '\r' => write!(writer, "\\r")?,
+            '\t' => write!(writer, "\\t")?,
+            c if c.is_control() => write!(writer, "\\u{:04x}", c as u32)?,
+            c => write!(writer, "{c}")?,
+        }
+    }
+    write!(writer, "\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns diagnostics `a` with a value of `1.5` and `b/c` with no value.
+    pub(super) fn diagnostics() -> [Diagnostic; 2] {
+        let mut a = Diagnostic::new(DiagnosticPath::const_new("a")).with_suffix("ms");
+        a.add_measurement(crate::DiagnosticMeasurement {
+            time: bevy_utils::Instant::now(),
+            value: 1.5,
+        });
+        [a, Diagnostic::new(DiagnosticPath::const_new("b/c"))]
+    }
+
+    pub(super) fn record(sink: &mut impl DiagnosticsSink, frame: u32, diagnostics: &[&Diagnostic]) {
+        sink.record(&DiagnosticsRecord {
+            frame,
+            elapsed: Duration::from_millis(u64::from(frame) * 250),
+            diagnostics,
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn json_string() {
+        let mut buf = Vec::new();
+        write_json_string(&mut buf, "a\"b\\c\n\u{1}").unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), r#""a\"b\\c\n\u0001""#);
+    }
+}
@@ -0,0 +1,136 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{prelude::*, schedule::SystemTimings};
+use bevy_utils::Instant;
+
+use crate::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+
+/// Adds a diagnostic for the run time of each system, system set and schedule to an App.
+///
+/// The diagnostics are named `system_time/<schedule>/<system>`, `system_set_time/<schedule>/<set>`
+/// and `schedule_time/<schedule>`, using the [`Debug`](std::fmt::Debug) names of the schedules and
+/// sets, and are measured in milliseconds per frame. Systems and sets of schedules that run several
+/// times in a frame, such as `FixedUpdate`, add up their run times.
+///
+/// Measuring systems has a small overhead, so this plugin should only be used when profiling.
+/// Note that the systems running other schedules, like the one of the `Main` schedule, include
+/// the time taken by those schedules.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct SystemTimeDiagnosticsPlugin;
+
+impl Plugin for SystemTimeDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DiagnosticsStore>()
+            .init_resource::<SystemTimings>()
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl SystemTimeDiagnosticsPlugin {
+    pub const SYSTEM_TIME: &'static str = "system_time";
+    pub const SYSTEM_SET_TIME: &'static str = "system_set_time";
+    pub const SCHEDULE_TIME: &'static str = "schedule_time";
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<DiagnosticsStore>,
+        mut timings: ResMut<SystemTimings>,
+    ) {
+        let time = Instant::now();
+        let mut add_measurement = |components: &[&str], value: f64| {
+            // Debug names may contain `/`, which separates the components of a path.
+            let components = components.iter().map(|c| c.replace('/', "\\"));
+            let path = DiagnosticPath::new(components.collect::<Vec<_>>().join("/"));
+            if diagnostics.get(&path).is_none() {
+                diagnostics.add(Diagnostic::new(path.clone()).with_suffix("ms"));
+            }
+            let diagnostic = diagnostics.get_mut(&path).unwrap();
+            if diagnostic.is_enabled {
+                diagnostic.add_measurement(DiagnosticMeasurement { time, value });
+            }
+        };
+
+        for (label, schedule) in timings.iter() {
+            let label = format!("{label:?}");
+            add_measurement(
+                &[Self::SCHEDULE_TIME, &label],
+                schedule.total().as_secs_f64() * 1000.0,
+            );
+            for (name, duration) in schedule.systems() {
+                add_measurement(
+                    &[Self::SYSTEM_TIME, &label, name],
+                    duration.as_secs_f64() * 1000.0,
+                );
+            }
+            for (name, duration) in schedule.sets() {
+                add_measurement(
+                    &[Self::SYSTEM_SET_TIME, &label, name],
+                    duration.as_secs_f64() * 1000.0,
+                );
+            }
+        }
+
+        timings.clear();
+    }
+
+    /// Returns the `count` system diagnostics with the highest smoothed run time, slowest first.
+    pub fn slowest_systems(diagnostics: &DiagnosticsStore, count: usize) -> Vec<&Diagnostic> {
+        let mut systems: Vec<_> = diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_enabled)
+            .filter(|diagnostic| diagnostic.path().components().next() == Some(Self::SYSTEM_TIME))
+            .filter_map(|diagnostic| Some((diagnostic.smoothed()?, diagnostic)))
+            .collect();
+        systems.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        systems
+            .into_iter()
+            .take(count)
+            .map(|(_, diagnostic)| diagnostic)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread::sleep, time::Duration};
+
+    use bevy_ecs::system::IntoSystem;
+
+    use super::*;
+
+    fn slow() {
+        sleep(Duration::from_millis(2));
+    }
+
+    fn fast() {}
+
+    #[test]
+    fn system_time_diagnostics() {
+        let mut app = App::new();
+        app.add_plugins(SystemTimeDiagnosticsPlugin)
+            .add_systems(Update, (slow, fast));
+        app.update();
+        app.update();
+
+        let diagnostics = app.world().resource::<DiagnosticsStore>();
+        let slow_name = IntoSystem::into_system(slow).name();
+        let path = DiagnosticPath::from_components([
+            SystemTimeDiagnosticsPlugin::SYSTEM_TIME,
+            "Update",
+            &slow_name,
+        ]);
+        let slow_time = diagnostics.get(&path).unwrap();
+        assert_eq!(slow_time.history_len(), 2);
+        assert!(slow_time.value().unwrap() >= 2.0);
+
+        let update =
+            DiagnosticPath::from_components([SystemTimeDiagnosticsPlugin::SCHEDULE_TIME, "Update"]);
+        assert!(diagnostics.get(&update).unwrap().value().unwrap() >= 2.0);
+
+        let slowest = SystemTimeDiagnosticsPlugin::slowest_systems(diagnostics, 2);
+        assert_eq!(slowest.len(), 2);
+        assert!(slowest.iter().any(|diagnostic| diagnostic.path() == &path));
+    }
+}
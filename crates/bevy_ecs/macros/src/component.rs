@@ -1,12 +1,17 @@
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
-use syn::{parse_macro_input, parse_quote, DeriveInput, Ident, LitStr, Path, Result};
+use syn::{parse_macro_input, parse_quote, DeriveInput, Ident, LitStr, Path, Result, Type};
 
 pub fn derive_event(input: TokenStream) -> TokenStream {
     let mut ast = parse_macro_input!(input as DeriveInput);
     let bevy_ecs_path: Path = crate::bevy_ecs_path();
 
+    let attrs = match parse_event_attr(&ast) {
+        Ok(attrs) => attrs,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
     ast.generics
         .make_where_clause()
         .predicates
@@ -15,8 +20,15 @@ pub fn derive_event(input: TokenStream) -> TokenStream {
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
 
+    let traversal = attrs
+        .traversal
+        .unwrap_or_else(|| parse_quote! { #bevy_ecs_path::traversal::TraverseNone });
+    let auto_propagate = attrs.auto_propagate;
+
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::event::Event for #struct_name #type_generics #where_clause {
+            type Traversal = #traversal;
+            const AUTO_PROPAGATE: bool = #auto_propagate;
         }
 
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
@@ -113,6 +125,38 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     Ok(attrs)
 }
 
+pub const EVENT: &str = "event";
+pub const TRAVERSAL: &str = "traversal";
+pub const AUTO_PROPAGATE: &str = "auto_propagate";
+
+struct EventAttrs {
+    traversal: Option<Type>,
+    auto_propagate: bool,
+}
+
+fn parse_event_attr(ast: &DeriveInput) -> Result<EventAttrs> {
+    let mut attrs = EventAttrs {
+        traversal: None,
+        auto_propagate: false,
+    };
+
+    for meta in ast.attrs.iter().filter(|a| a.path().is_ident(EVENT)) {
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident(TRAVERSAL) {
+                attrs.traversal = Some(nested.value()?.parse::<Type>()?);
+                Ok(())
+            } else if nested.path.is_ident(AUTO_PROPAGATE) {
+                attrs.auto_propagate = true;
+                Ok(())
+            } else {
+                Err(nested.error("Unsupported attribute"))
+            }
+        })?;
+    }
+
+    Ok(attrs)
+}
+
 fn storage_path(bevy_ecs_path: &Path, ty: StorageTy) -> TokenStream2 {
     let storage_type = match ty {
         StorageTy::Table => Ident::new("Table", Span::call_site()),
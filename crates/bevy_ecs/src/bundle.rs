@@ -17,7 +17,7 @@ use crate::{
     prelude::World,
     query::DebugCheckedUnwrap,
     storage::{SparseSetIndex, SparseSets, Storages, Table, TableRow},
-    world::{unsafe_world_cell::UnsafeWorldCell, ON_ADD, ON_INSERT},
+    world::{unsafe_world_cell::UnsafeWorldCell, ON_ADD, ON_INSERT, ON_REPLACE},
 };
 
 use bevy_ptr::{ConstNonNull, OwningPtr};
@@ -456,11 +456,13 @@ impl BundleInfo {
         let mut new_sparse_set_components = Vec::new();
         let mut bundle_status = Vec::with_capacity(self.component_ids.len());
         let mut added = Vec::new();
+        let mut existing = Vec::new();
 
         let current_archetype = &mut archetypes[archetype_id];
         for component_id in self.component_ids.iter().cloned() {
             if current_archetype.contains(component_id) {
                 bundle_status.push(ComponentStatus::Mutated);
+                existing.push(component_id);
             } else {
                 bundle_status.push(ComponentStatus::Added);
                 added.push(component_id);
@@ -476,7 +478,7 @@ impl BundleInfo {
         if new_table_components.is_empty() && new_sparse_set_components.is_empty() {
             let edges = current_archetype.edges_mut();
             // the archetype does not change when we add this bundle
-            edges.insert_add_bundle(self.id, archetype_id, bundle_status, added);
+            edges.insert_add_bundle(self.id, archetype_id, bundle_status, added, existing);
             archetype_id
         } else {
             let table_id;
@@ -526,6 +528,7 @@ impl BundleInfo {
                 new_archetype_id,
                 bundle_status,
                 added,
+                existing,
             );
             new_archetype_id
         }
@@ -664,6 +667,31 @@ impl<'w> BundleInserter<'w> {
     ) -> EntityLocation {
         let bundle_info = self.bundle_info.as_ref();
         let add_bundle = self.add_bundle.as_ref();
+
+        if !add_bundle.existing.is_empty() {
+            // SAFETY: `DeferredWorld` cannot provide mutable access to `Archetypes`.
+            let archetype = self.archetype.as_ref();
+            // SAFETY: We have no outstanding mutable references to world, and the table and archetype
+            // references below are only created once the hooks and observers have run.
+            let mut deferred_world = unsafe { self.world.into_deferred() };
+            // SAFETY: All components in the bundle are guaranteed to exist in the World
+            // as they must be initialized before creating the BundleInfo.
+            unsafe {
+                deferred_world.trigger_on_replace(
+                    archetype,
+                    entity,
+                    add_bundle.existing.iter().cloned(),
+                );
+                if archetype.has_replace_observer() {
+                    deferred_world.trigger_observers(
+                        ON_REPLACE,
+                        entity,
+                        add_bundle.existing.iter().cloned(),
+                    );
+                }
+            }
+        }
+
         let table = self.table.as_mut();
         let archetype = self.archetype.as_mut();
 
@@ -1138,9 +1166,15 @@ mod tests {
     #[derive(Component)]
     struct D;
 
+    #[derive(Component)]
+    struct V(&'static str);
+
     #[derive(Resource, Default)]
     struct R(usize);
 
+    #[derive(Resource, Default)]
+    struct Log(Vec<String>);
+
     impl R {
         #[track_caller]
         fn assert_order(&mut self, count: usize) {
@@ -1159,11 +1193,12 @@ mod tests {
                 world.resource_mut::<R>().assert_order(0);
             })
             .on_insert(|mut world, _, _| world.resource_mut::<R>().assert_order(1))
-            .on_remove(|mut world, _, _| world.resource_mut::<R>().assert_order(2));
+            .on_replace(|mut world, _, _| world.resource_mut::<R>().assert_order(2))
+            .on_remove(|mut world, _, _| world.resource_mut::<R>().assert_order(3));
 
         let entity = world.spawn(A).id();
         world.despawn(entity);
-        assert_eq!(3, world.resource::<R>().0);
+        assert_eq!(4, world.resource::<R>().0);
     }
 
     #[test]
@@ -1178,15 +1213,43 @@ mod tests {
             .on_insert(|mut world, _, _| {
                 world.resource_mut::<R>().assert_order(1);
             })
-            .on_remove(|mut world, _, _| {
+            .on_replace(|mut world, _, _| {
                 world.resource_mut::<R>().assert_order(2);
+            })
+            .on_remove(|mut world, _, _| {
+                world.resource_mut::<R>().assert_order(3);
             });
 
         let mut entity = world.spawn_empty();
         entity.insert(A);
         entity.remove::<A>();
         entity.flush();
-        assert_eq!(3, world.resource::<R>().0);
+        assert_eq!(4, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn component_hook_order_replace() {
+        let mut world = World::new();
+        world
+            .register_component_hooks::<V>()
+            .on_replace(|mut world, entity, _| {
+                let old = world.get::<V>(entity).unwrap().0;
+                world.resource_mut::<Log>().0.push(format!("replace {old}"));
+            })
+            .on_insert(|mut world, entity, _| {
+                let new = world.get::<V>(entity).unwrap().0;
+                world.resource_mut::<Log>().0.push(format!("insert {new}"));
+            });
+        world.init_resource::<Log>();
+
+        let mut entity = world.spawn(V("a"));
+        entity.insert(V("b"));
+        entity.remove::<V>();
+        entity.flush();
+        assert_eq!(
+            world.resource::<Log>().0,
+            vec!["insert a", "replace a", "insert b", "replace b"]
+        );
     }
 
     #[test]
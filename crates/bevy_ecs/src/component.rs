@@ -240,6 +240,7 @@ pub type ComponentHook = for<'w> fn(DeferredWorld<'w>, Entity, ComponentId);
 pub struct ComponentHooks {
     pub(crate) on_add: Option<ComponentHook>,
     pub(crate) on_insert: Option<ComponentHook>,
+    pub(crate) on_replace: Option<ComponentHook>,
     pub(crate) on_remove: Option<ComponentHook>,
 }
 
@@ -274,6 +275,28 @@ impl ComponentHooks {
             .expect("Component id: {:?}, already has an on_insert hook")
     }
 
+    /// Register a [`ComponentHook`] that will be run when this component is about to be dropped,
+    /// such as being replaced (with `.insert`) or removed.
+    ///
+    /// If this component is inserted onto an entity that already has it, this hook will run before the value is replaced,
+    /// allowing access to the previous data just before it is dropped.
+    /// This hook does *not* run if the entity did not already have this component.
+    ///
+    /// An `on_replace` hook always runs before any `on_remove` hooks (if the component is being removed from the entity).
+    ///
+    /// # Warning
+    ///
+    /// The hook won't run if the component is already present and is only mutated, such as in a system via a query.
+    /// As a result, this is *not* an appropriate mechanism for reliably updating indexes and other caches.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_replace` hook
+    pub fn on_replace(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_replace(hook)
+            .expect("Component id: {:?}, already has an on_replace hook")
+    }
+
     /// Register a [`ComponentHook`] that will be run when this component is removed from an entity.
     /// Despawning an entity counts as removing all of its components.
     ///
@@ -311,6 +334,19 @@ impl ComponentHooks {
         Some(self)
     }
 
+    /// Attempt to register a [`ComponentHook`] that will be run when this component is replaced (with `.insert`) or removed
+    ///
+    /// This is a fallible version of [`Self::on_replace`].
+    ///
+    /// Returns `None` if the component already has an `on_replace` hook.
+    pub fn try_on_replace(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_replace.is_some() {
+            return None;
+        }
+        self.on_replace = Some(hook);
+        Some(self)
+    }
+
     /// Attempt to register a [`ComponentHook`] that will be run when this component is removed from an entity.
     ///
     /// This is a fallible version of [`Self::on_remove`].
@@ -402,6 +438,9 @@ impl ComponentInfo {
         if self.hooks().on_insert.is_some() {
             flags.insert(ArchetypeFlags::ON_INSERT_HOOK);
         }
+        if self.hooks().on_replace.is_some() {
+            flags.insert(ArchetypeFlags::ON_REPLACE_HOOK);
+        }
         if self.hooks().on_remove.is_some() {
             flags.insert(ArchetypeFlags::ON_REMOVE_HOOK);
         }
@@ -487,9 +526,12 @@ pub struct ComponentDescriptor {
     // this descriptor describes.
     // None if the underlying type doesn't need to be dropped
     drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
+    // The hooks of the component type, registered when the component is initialized from the
+    // info of another world. None if this doesn't describe a Rust component type.
+    register_hooks: Option<fn(&mut ComponentHooks)>,
 }
 
-// We need to ignore the `drop` field in our `Debug` impl
+// We need to ignore the `drop` and `register_hooks` fields in our `Debug` impl
 impl std::fmt::Debug for ComponentDescriptor {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ComponentDescriptor")
@@ -522,6 +564,7 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            register_hooks: Some(T::register_component_hooks),
         }
     }
 
@@ -543,6 +586,7 @@ impl ComponentDescriptor {
             type_id: None,
             layout,
             drop,
+            register_hooks: None,
         }
     }
 
@@ -559,6 +603,7 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            register_hooks: None,
         }
     }
 
@@ -570,6 +615,7 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            register_hooks: None,
         }
     }
 
@@ -649,6 +695,37 @@ impl Components {
         Components::init_component_inner(&mut self.components, storages, descriptor)
     }
 
+    /// Initializes a component with the same descriptor as `info`, which may come from another
+    /// world, and returns its [`ComponentId`] in this instance.
+    ///
+    /// Only the hooks of the component type are registered, as the hooks registered at runtime
+    /// in the other world may rely on its resources.
+    /// If a component of the same type has already been initialized, this will return
+    /// the ID of the pre-existing component.
+    ///
+    /// Returns `None` if `info` does not correspond to a Rust type.
+    pub(crate) fn init_component_from_info(
+        &mut self,
+        storages: &mut Storages,
+        info: &ComponentInfo,
+    ) -> Option<ComponentId> {
+        let type_id = info.type_id()?;
+
+        let Components {
+            indices,
+            components,
+            ..
+        } = self;
+        Some(*indices.entry(type_id).or_insert_with(|| {
+            let index =
+                Components::init_component_inner(components, storages, info.descriptor.clone());
+            if let Some(register_hooks) = info.descriptor.register_hooks {
+                register_hooks(&mut components[index.index()].hooks);
+            }
+            index
+        }))
+    }
+
     #[inline]
     fn init_component_inner(
         components: &mut Vec<ComponentInfo>,
@@ -885,6 +885,43 @@ impl Entities {
         self.len = count as u32;
     }
 
+    /// Captures the allocation state of the entities: the generation of every index, the
+    /// freelist and the number of allocated entities.
+    ///
+    /// Must not be called while reserved entities are awaiting `flush()`.
+    pub(crate) fn snapshot(&mut self) -> EntitiesSnapshot {
+        self.verify_flushed();
+        EntitiesSnapshot {
+            generations: self.meta.iter().map(|meta| meta.generation).collect(),
+            pending: self.pending.clone(),
+            len: self.len,
+        }
+    }
+
+    /// Restores the allocation state captured by [`Entities::snapshot`], so that the next
+    /// allocations return the same entities as they did after the snapshot was taken.
+    ///
+    /// The set of allocated entities must be the same as when the snapshot was taken.
+    /// Must not be called while reserved entities are awaiting `flush()`.
+    pub(crate) fn restore(&mut self, snapshot: &EntitiesSnapshot) {
+        self.verify_flushed();
+        assert_eq!(
+            self.len, snapshot.len,
+            "the allocated entities must be the ones of the snapshot"
+        );
+        debug_assert!(self.meta[snapshot.generations.len().min(self.meta.len())..]
+            .iter()
+            .all(|meta| meta.location.archetype_id == ArchetypeId::INVALID));
+
+        self.meta
+            .resize(snapshot.generations.len(), EntityMeta::EMPTY);
+        for (meta, generation) in self.meta.iter_mut().zip(&snapshot.generations) {
+            meta.generation = *generation;
+        }
+        self.pending.clone_from(&snapshot.pending);
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+    }
+
     /// The count of all entities in the [`World`] that have ever been allocated
     /// including the entities that are currently freed.
     ///
@@ -910,6 +947,14 @@ impl Entities {
     }
 }
 
+/// The allocation state of [`Entities`], captured by [`Entities::snapshot`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct EntitiesSnapshot {
+    generations: Vec<NonZeroU32>,
+    pending: Vec<u32>,
+    len: u32,
+}
+
 // This type is repr(C) to ensure that the layout and values within it can be safe to fully fill
 // with u8::MAX, as required by [`Entities::flush_and_reserve_invalid_assuming_no_entities`].
 // Safety:
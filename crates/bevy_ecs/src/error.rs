@@ -0,0 +1,246 @@
+//! Error handling for fallible systems.
+//!
+//! Systems that return a [`Result<(), E>`](Result) can be added to schedules just like
+//! systems that return `()`, as long as `E` can be converted into an [`Error`].
+//! This means that fallible operations inside a system can be propagated with `?`:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::error::Result;
+//! #[derive(Component)]
+//! struct Player;
+//!
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! fn heal_player(mut player: Query<&mut Health, With<Player>>) -> Result {
+//!     player.get_single_mut()?.0 += 1;
+//!     Ok(())
+//! }
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(heal_player);
+//! ```
+//!
+//! When a system returns an error, it is immediately handed to the [`ErrorHandler`], along with a
+//! [`SystemErrorContext`] describing the failing system. The handler is read from the
+//! [`SystemErrorHandler`] resource, which can be overridden for a single schedule with
+//! [`Schedule::set_error_handler`](crate::schedule::Schedule::set_error_handler).
+//! If no handler is configured, errors cause a panic.
+
+use std::borrow::Cow;
+
+use bevy_utils::tracing;
+
+use crate as bevy_ecs;
+use crate::{component::Tick, system::Resource};
+
+/// A type-erased error, which any [`std::error::Error`] can be converted into.
+pub type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// A [`Result`](std::result::Result) whose error type defaults to [`Error`].
+pub type Result<T = (), E = Error> = std::result::Result<T, E>;
+
+/// Information about the system that returned an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemErrorContext {
+    /// The name of the system that failed.
+    pub name: Cow<'static, str>,
+    /// The tick at which the system last ran.
+    pub last_run: Tick,
+}
+
+/// A function that handles the errors returned by fallible systems.
+///
+/// See the [`error_handler`] module for the built-in handlers.
+pub type ErrorHandler = fn(Error, SystemErrorContext);
+
+/// The [`ErrorHandler`] used for errors returned by systems in this world.
+///
+/// Defaults to [`error_handler::panic`].
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct SystemErrorHandler(pub ErrorHandler);
+
+impl Default for SystemErrorHandler {
+    fn default() -> Self {
+        Self(error_handler::panic)
+    }
+}
+
+/// Built-in [`ErrorHandler`]s.
+pub mod error_handler {
+    use super::*;
+
+    /// Panics with the error and the name of the system that returned it.
+    pub fn panic(error: Error, context: SystemErrorContext) {
+        panic!("Encountered an error in system `{}`: {error}", context.name);
+    }
+
+    /// Logs the error at the `error` level.
+    pub fn error(error: Error, context: SystemErrorContext) {
+        tracing::error!("Encountered an error in system `{}`: {error}", context.name);
+    }
+
+    /// Logs the error at the `warn` level.
+    pub fn warn(error: Error, context: SystemErrorContext) {
+        tracing::warn!("Encountered an error in system `{}`: {error}", context.name);
+    }
+
+    /// Logs the error at the `info` level.
+    pub fn info(error: Error, context: SystemErrorContext) {
+        tracing::info!("Encountered an error in system `{}`: {error}", context.name);
+    }
+
+    /// Logs the error at the `debug` level.
+    pub fn debug(error: Error, context: SystemErrorContext) {
+        tracing::debug!("Encountered an error in system `{}`: {error}", context.name);
+    }
+
+    /// Discards the error.
+    pub fn ignore(_: Error, _: SystemErrorContext) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{
+        prelude::*,
+        schedule::{ExecutorKind, Schedule},
+    };
+
+    #[derive(Component)]
+    struct Player;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    static FAILURES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    fn record(error: Error, context: SystemErrorContext) {
+        FAILURES
+            .lock()
+            .unwrap()
+            .push(format!("{}: {error}", context.name));
+    }
+
+    fn count_player(player: Query<&Player>, mut counter: ResMut<Counter>) -> Result {
+        player.get_single()?;
+        counter.0 += 1;
+        Ok(())
+    }
+
+    fn run_schedule(kind: ExecutorKind, schedule: &mut Schedule, world: &mut World) {
+        schedule.set_executor_kind(kind);
+        schedule.run(world);
+    }
+
+    #[test]
+    fn fallible_systems() {
+        for kind in [
+            ExecutorKind::Simple,
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::new();
+            world.init_resource::<Counter>();
+            world.insert_resource(SystemErrorHandler(error_handler::ignore));
+            let mut schedule = Schedule::default();
+            schedule.add_systems(count_player);
+
+            run_schedule(kind, &mut schedule, &mut world);
+            assert_eq!(world.resource::<Counter>().0, 0);
+
+            world.spawn(Player);
+            run_schedule(kind, &mut schedule, &mut world);
+            assert_eq!(world.resource::<Counter>().0, 1);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "count_player")]
+    fn errors_panic_by_default() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        schedule.add_systems(count_player);
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    fn error_handlers() {
+        fn fail() -> Result<(), std::fmt::Error> {
+            Err(std::fmt::Error)
+        }
+
+        let mut world = World::new();
+
+        // The schedule's handler is only used while it runs.
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        schedule.set_error_handler(record);
+        schedule.add_systems(fail);
+        schedule.run(&mut world);
+
+        let failures = std::mem::take(&mut *FAILURES.lock().unwrap());
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].contains("fail"));
+        assert!(std::ptr::fn_addr_eq(
+            world.resource::<SystemErrorHandler>().0,
+            error_handler::panic as ErrorHandler
+        ));
+
+        // The world's handler is restored when a system panics too.
+        fn panic() {
+            panic!("system panicked");
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        schedule.set_error_handler(record);
+        schedule.add_systems(panic);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            schedule.run(&mut world);
+        }));
+        assert!(result.is_err());
+        assert!(std::ptr::fn_addr_eq(
+            world.resource::<SystemErrorHandler>().0,
+            error_handler::panic as ErrorHandler
+        ));
+    }
+
+    #[test]
+    fn errors_are_handled_when_returned() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        static HANDLED: AtomicBool = AtomicBool::new(false);
+
+        #[derive(Resource, Default)]
+        struct HandledBeforeNextSystem(bool);
+
+        fn fail() -> Result<(), std::fmt::Error> {
+            Err(std::fmt::Error)
+        }
+
+        fn check(mut handled: ResMut<HandledBeforeNextSystem>) {
+            handled.0 = HANDLED.load(Ordering::Acquire);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<HandledBeforeNextSystem>();
+        world.insert_resource(SystemErrorHandler(|_, _| {
+            HANDLED.store(true, Ordering::Release);
+        }));
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        schedule.set_apply_final_deferred(false);
+        schedule.add_systems((fail, check).chain());
+        schedule.run(&mut world);
+
+        assert!(world.resource::<HandledBeforeNextSystem>().0);
+        // No sync point is needed for the error to be handled.
+        assert_eq!(schedule.systems_len(), 2);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::component::Component;
+use crate::{component::Component, traversal::Traversal};
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::Reflect;
 use std::{
@@ -23,6 +23,27 @@ use std::{
 ///
 /// Events must be thread-safe.
 ///
+/// When an event is triggered for an entity, it can optionally propagate to other entities, following
+/// the path described by [`Event::Traversal`]. This can be configured with the `#[event(...)]` attribute
+/// when deriving:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::traversal::Traversal;
+/// #[derive(Component)]
+/// struct Owner(Entity);
+///
+/// impl Traversal for Owner {
+///     fn traverse(&self) -> Option<Entity> {
+///         Some(self.0)
+///     }
+/// }
+///
+/// #[derive(Event)]
+/// #[event(traversal = Owner, auto_propagate)]
+/// struct Damage(u32);
+/// ```
+///
 /// [`World`]: crate::world::World
 /// [`ComponentId`]: crate::component::ComponentId
 /// [`Observer`]: crate::observer::Observer
@@ -31,7 +52,19 @@ use std::{
     label = "invalid `Event`",
     note = "consider annotating `{Self}` with `#[derive(Event)]`"
 )]
-pub trait Event: Component {}
+pub trait Event: Component {
+    /// The component that describes which Entity to propagate this event to next, when [propagation] is enabled.
+    ///
+    /// [propagation]: crate::observer::Trigger::propagate
+    type Traversal: Traversal;
+
+    /// When true, this event will always attempt to propagate when [triggered], without requiring a call
+    /// to [`Trigger::propagate`].
+    ///
+    /// [triggered]: crate::world::World::trigger_targets
+    /// [`Trigger::propagate`]: crate::observer::Trigger::propagate
+    const AUTO_PROPAGATE: bool = false;
+}
 
 /// An `EventId` uniquely identifies an event stored in a specific [`World`].
 ///
@@ -0,0 +1,319 @@
+//! Secondary indices that map component values to the entities that hold them.
+//!
+//! Looking up "the entity whose `Name` is X" with a [`Query`] requires scanning every
+//! matching entity. A [`ComponentIndex`] keeps a map from each value of an
+//! [`Eq`] + [`Hash`] component to the set of entities that currently hold it, so that
+//! lookups are `O(1)`.
+//!
+//! Indices are opt-in: call [`World::init_component_index`] before the component is
+//! first used. The index is kept up to date by the component's `on_insert` and
+//! `on_replace` [hooks](crate::component::ComponentHooks), so inserting, replacing,
+//! removing and despawning are reflected immediately.
+//!
+//! Mutating a component in place (through `&mut C` or [`Mut<C>`](crate::change_detection::Mut))
+//! does not run any hooks. Those changes are picked up by [`sync_component_index`],
+//! which must run in a schedule; `App::add_component_index` in `bevy_app` schedules it for you.
+//! Until it runs, lookups reflect the values the entities had before they were mutated.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::index::Index;
+//! #[derive(Component, Clone, PartialEq, Eq, Hash)]
+//! struct TeamId(u32);
+//!
+//! let mut world = World::new();
+//! world.init_component_index::<TeamId>();
+//!
+//! world.spawn(TeamId(3));
+//! world.spawn(TeamId(3));
+//! world.spawn(TeamId(4));
+//!
+//! fn count_team(index: Index<TeamId>) -> usize {
+//!     index.get(&TeamId(3)).count()
+//! }
+//! # use bevy_ecs::system::RunSystemOnce;
+//! assert_eq!(world.run_system_once(count_team), 2);
+//! ```
+//!
+//! [`Query`]: crate::system::Query
+
+use std::hash::Hash;
+
+use bevy_utils::HashMap;
+
+use crate as bevy_ecs;
+use crate::{
+    component::{Component, ComponentId},
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    query::Changed,
+    system::{Query, Res, ResMut, Resource, SystemParam},
+    world::{DeferredWorld, World},
+};
+
+/// A map from the values of the component `C` to the entities that hold them.
+///
+/// Created by [`World::init_component_index`], and usually accessed through the
+/// [`Index`] system parameter.
+#[derive(Resource)]
+pub struct ComponentIndex<C: Component + Eq + Hash + Clone> {
+    entities: HashMap<C, EntityHashSet>,
+    values: EntityHashMap<C>,
+}
+
+impl<C: Component + Eq + Hash + Clone> Default for ComponentIndex<C> {
+    fn default() -> Self {
+        Self {
+            entities: HashMap::default(),
+            values: EntityHashMap::default(),
+        }
+    }
+}
+
+impl<C: Component + Eq + Hash + Clone> ComponentIndex<C> {
+    /// Returns an iterator over the entities whose `C` is equal to `value`.
+    pub fn get(&self, value: &C) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.get(value).into_iter().flatten().copied()
+    }
+
+    /// Returns the entity whose `C` is equal to `value`, if there is exactly one.
+    pub fn get_single(&self, value: &C) -> Option<Entity> {
+        let entities = self.entities.get(value)?;
+        if entities.len() == 1 {
+            entities.iter().next().copied()
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if any entity's `C` is equal to `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns the indexed value of `C` for `entity`, if it has one.
+    pub fn value_of(&self, entity: Entity) -> Option<&C> {
+        self.values.get(&entity)
+    }
+
+    /// Returns an iterator over all the distinct indexed values.
+    pub fn values(&self) -> impl Iterator<Item = &C> {
+        self.entities.keys()
+    }
+
+    /// Returns the number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no entities are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Indexes `entity` under `value`, removing it from the value it was previously indexed under.
+    fn insert(&mut self, entity: Entity, value: C) {
+        self.remove(entity);
+        self.entities
+            .entry(value.clone())
+            .or_default()
+            .insert(entity);
+        self.values.insert(entity, value);
+    }
+
+    /// Removes `entity` from the index.
+    fn remove(&mut self, entity: Entity) {
+        let Some(old) = self.values.remove(&entity) else {
+            return;
+        };
+        if let Some(entities) = self.entities.get_mut(&old) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                self.entities.remove(&old);
+            }
+        }
+    }
+}
+
+/// A [`SystemParam`] that provides `O(1)` lookups of entities by the value of their `C` component.
+///
+/// The [`ComponentIndex<C>`] must have been created with [`World::init_component_index`],
+/// otherwise systems using this parameter will panic.
+#[derive(SystemParam)]
+pub struct Index<'w, C: Component + Eq + Hash + Clone> {
+    index: Res<'w, ComponentIndex<C>>,
+}
+
+impl<'w, C: Component + Eq + Hash + Clone> std::ops::Deref for Index<'w, C> {
+    type Target = ComponentIndex<C>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.index
+    }
+}
+
+/// Re-indexes entities whose `C` was mutated in place since the last time this system ran.
+///
+/// Insertions, replacements and removals are tracked by hooks and don't need this system.
+pub fn sync_component_index<C: Component + Eq + Hash + Clone>(
+    changed: Query<(Entity, &C), Changed<C>>,
+    mut index: ResMut<ComponentIndex<C>>,
+) {
+    for (entity, value) in &changed {
+        if index.value_of(entity) != Some(value) {
+            index.insert(entity, value.clone());
+        }
+    }
+}
+
+fn index_on_insert<C: Component + Eq + Hash + Clone>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _: ComponentId,
+) {
+    let value = world.get::<C>(entity).unwrap().clone();
+    world
+        .resource_mut::<ComponentIndex<C>>()
+        .insert(entity, value);
+}
+
+fn index_on_replace<C: Component + Eq + Hash + Clone>(
+    mut world: DeferredWorld,
+    entity: Entity,
+    _: ComponentId,
+) {
+    world.resource_mut::<ComponentIndex<C>>().remove(entity);
+}
+
+impl World {
+    /// Creates a [`ComponentIndex`] for the component `C`, which can then be used through the
+    /// [`Index`] system parameter. Does nothing if the index already exists.
+    ///
+    /// See the [`index`](crate::index) module for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `C` is already used by an entity, or if it already has an `on_insert` or
+    /// `on_replace` hook.
+    pub fn init_component_index<C: Component + Eq + Hash + Clone>(&mut self) -> &mut Self {
+        if self.contains_resource::<ComponentIndex<C>>() {
+            return self;
+        }
+        let hooks = self.register_component_hooks::<C>();
+        // Both hooks are checked first, so that nothing is registered if the index can't be created.
+        assert!(
+            hooks.on_insert.is_none() && hooks.on_replace.is_none(),
+            "{} cannot be indexed because it already has an on_insert or on_replace hook",
+            std::any::type_name::<C>()
+        );
+        hooks
+            .on_insert(index_on_insert::<C>)
+            .on_replace(index_on_replace::<C>);
+        self.init_resource::<ComponentIndex<C>>();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::RunSystemOnce;
+
+    #[derive(Component, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TeamId(u32);
+
+    fn sorted(mut entities: Vec<Entity>) -> Vec<Entity> {
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn index_tracks_structural_changes() {
+        let mut world = World::new();
+        world.init_component_index::<TeamId>();
+
+        let a = world.spawn(TeamId(1)).id();
+        let b = world.spawn(TeamId(1)).id();
+        let c = world.spawn(TeamId(2)).id();
+
+        let index = world.resource::<ComponentIndex<TeamId>>();
+        assert_eq!(sorted(index.get(&TeamId(1)).collect()), sorted(vec![a, b]));
+        assert_eq!(index.get_single(&TeamId(1)), None);
+        assert_eq!(index.get_single(&TeamId(2)), Some(c));
+        assert_eq!(index.len(), 3);
+
+        // Replacing moves the entity to its new value.
+        world.entity_mut(b).insert(TeamId(2));
+        let index = world.resource::<ComponentIndex<TeamId>>();
+        assert_eq!(index.get_single(&TeamId(1)), Some(a));
+        assert_eq!(sorted(index.get(&TeamId(2)).collect()), sorted(vec![b, c]));
+        assert_eq!(index.value_of(b), Some(&TeamId(2)));
+
+        // Removing and despawning drop the entity from the index.
+        world.entity_mut(a).remove::<TeamId>();
+        world.despawn(c);
+        let index = world.resource::<ComponentIndex<TeamId>>();
+        assert!(!index.contains(&TeamId(1)));
+        assert_eq!(index.get_single(&TeamId(2)), Some(b));
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn index_syncs_in_place_mutations() {
+        let mut world = World::new();
+        world.init_component_index::<TeamId>();
+
+        let a = world.spawn(TeamId(1)).id();
+        world.run_system_once(sync_component_index::<TeamId>);
+
+        world.get_mut::<TeamId>(a).unwrap().0 = 5;
+        assert_eq!(
+            world
+                .resource::<ComponentIndex<TeamId>>()
+                .get_single(&TeamId(1)),
+            Some(a)
+        );
+
+        world.run_system_once(sync_component_index::<TeamId>);
+        let index = world.resource::<ComponentIndex<TeamId>>();
+        assert!(!index.contains(&TeamId(1)));
+        assert_eq!(index.get_single(&TeamId(5)), Some(a));
+    }
+
+    #[test]
+    fn index_system_param() {
+        let mut world = World::new();
+        world.init_component_index::<TeamId>();
+        let a = world.spawn(TeamId(7)).id();
+
+        let found = world.run_system_once(|index: Index<TeamId>| index.get_single(&TeamId(7)));
+        assert_eq!(found, Some(a));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_requires_unused_component() {
+        let mut world = World::new();
+        world.spawn(TeamId(1));
+        world.init_component_index::<TeamId>();
+    }
+
+    #[test]
+    fn index_with_conflicting_hook_changes_nothing() {
+        #[derive(Component, Clone, PartialEq, Eq, Hash)]
+        struct Hooked;
+
+        let mut world = World::new();
+        world
+            .register_component_hooks::<Hooked>()
+            .on_replace(|_, _, _| {});
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            world.init_component_index::<Hooked>();
+        }));
+        assert!(result.is_err());
+        assert!(!world.contains_resource::<ComponentIndex<Hooked>>());
+        assert!(world
+            .register_component_hooks::<Hooked>()
+            .try_on_insert(|_, _, _| {})
+            .is_some());
+    }
+}
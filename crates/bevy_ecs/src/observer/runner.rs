@@ -20,7 +20,7 @@ pub struct ObserverState {
 impl Default for ObserverState {
     fn default() -> Self {
         Self {
-            runner: |_, _, _| {},
+            runner: |_, _, _, _| {},
             last_trigger_id: 0,
             despawned_watched_entities: 0,
             descriptor: Default::default(),
@@ -86,7 +86,7 @@ impl Component for ObserverState {
 /// Type for function that is run when an observer is triggered.
 /// Typically refers to the default runner that runs the system stored in the associated [`ObserverSystemComponent`],
 /// but can be overridden for custom behaviour.
-pub type ObserverRunner = fn(DeferredWorld, ObserverTrigger, PtrMut);
+pub type ObserverRunner = fn(DeferredWorld, ObserverTrigger, PtrMut, propagate: &mut bool);
 
 /// An [`Observer`] system. Add this [`Component`] to an [`Entity`] to turn it into an "observer".
 ///
@@ -355,6 +355,7 @@ fn observer_system_runner<E: Event, B: Bundle>(
     mut world: DeferredWorld,
     observer_trigger: ObserverTrigger,
     ptr: PtrMut,
+    propagate: &mut bool,
 ) {
     let world = world.as_unsafe_world_cell();
     // SAFETY: Observer was triggered so must still exist in world
@@ -379,7 +380,8 @@ fn observer_system_runner<E: Event, B: Bundle>(
     state.last_trigger_id = last_trigger;
 
     // SAFETY: Caller ensures `ptr` is castable to `&mut T`
-    let trigger: Trigger<E, B> = Trigger::new(unsafe { ptr.deref_mut() }, observer_trigger);
+    let trigger: Trigger<E, B> =
+        Trigger::new(unsafe { ptr.deref_mut() }, propagate, observer_trigger);
     // SAFETY: the static lifetime is encapsulated in Trigger / cannot leak out.
     // Additionally, IntoObserverSystem is only implemented for functions starting
     // with for<'a> Trigger<'a>, meaning users cannot specify Trigger<'static> manually,
@@ -48,7 +48,7 @@ impl<E: Event, Targets: TriggerTargets> Command for EmitDynamicTrigger<E, Target
 }
 
 #[inline]
-fn trigger_event<E, Targets: TriggerTargets>(
+fn trigger_event<E: Event, Targets: TriggerTargets>(
     world: &mut World,
     event_type: ComponentId,
     event_data: &mut E,
@@ -58,22 +58,24 @@ fn trigger_event<E, Targets: TriggerTargets>(
     if targets.entities().len() == 0 {
         // SAFETY: T is accessible as the type represented by self.trigger, ensured in `Self::new`
         unsafe {
-            world.trigger_observers_with_data(
+            world.trigger_observers_with_data::<_, E::Traversal>(
                 event_type,
                 Entity::PLACEHOLDER,
                 targets.components(),
                 event_data,
+                false,
             );
         };
     } else {
         for target in targets.entities() {
             // SAFETY: T is accessible as the type represented by self.trigger, ensured in `Self::new`
             unsafe {
-                world.trigger_observers_with_data(
+                world.trigger_observers_with_data::<_, E::Traversal>(
                     event_type,
                     target,
                     targets.components(),
                     event_data,
+                    E::AUTO_PROPAGATE,
                 );
             };
         }
@@ -88,78 +90,78 @@ fn trigger_event<E, Targets: TriggerTargets>(
 /// [`Observer`]: crate::observer::Observer
 pub trait TriggerTargets: Send + Sync + 'static {
     /// The components the trigger should target.
-    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId>;
+    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> + Clone;
 
     /// The entities the trigger should target.
-    fn entities(&self) -> impl ExactSizeIterator<Item = Entity>;
+    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + Clone;
 }
 
 impl TriggerTargets for () {
-    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> {
+    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> + Clone {
         [].into_iter()
     }
 
-    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> {
+    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + Clone {
         [].into_iter()
     }
 }
 
 impl TriggerTargets for Entity {
-    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> {
+    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> + Clone {
         [].into_iter()
     }
 
-    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> {
+    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + Clone {
         std::iter::once(*self)
     }
 }
 
 impl TriggerTargets for Vec<Entity> {
-    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> {
+    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> + Clone {
         [].into_iter()
     }
 
-    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> {
+    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + Clone {
         self.iter().copied()
     }
 }
 
 impl<const N: usize> TriggerTargets for [Entity; N] {
-    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> {
+    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> + Clone {
         [].into_iter()
     }
 
-    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> {
+    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + Clone {
         self.iter().copied()
     }
 }
 
 impl TriggerTargets for ComponentId {
-    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> {
+    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> + Clone {
         std::iter::once(*self)
     }
 
-    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> {
+    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + Clone {
         [].into_iter()
     }
 }
 
 impl TriggerTargets for Vec<ComponentId> {
-    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> {
+    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> + Clone {
         self.iter().copied()
     }
 
-    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> {
+    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + Clone {
         [].into_iter()
     }
 }
 
 impl<const N: usize> TriggerTargets for [ComponentId; N] {
-    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> {
+    fn components(&self) -> impl ExactSizeIterator<Item = ComponentId> + Clone {
         self.iter().copied()
     }
 
-    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> {
+    fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + Clone {
         [].into_iter()
     }
 }
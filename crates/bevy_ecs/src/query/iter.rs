@@ -730,6 +730,98 @@ impl<'w, 's, D: QueryData, F: QueryFilter> QueryIter<'w, 's, D, F> {
         }
     }
 
+    /// Sorts all query items into a new iterator with a key extraction function over the query lens.
+    ///
+    /// This sort is unstable (i.e., may reorder equal elements).
+    ///
+    /// This uses [`slice::sort_unstable_by_key`] internally.
+    ///
+    /// Defining the lens works like [`transmute_lens`](crate::system::Query::transmute_lens).
+    /// This includes the allowed parameter type changes listed under [allowed transmutes].
+    /// However, the lens uses the filter of the original query when present.
+    ///
+    /// The sort is not cached across system runs.
+    ///
+    /// [allowed transmutes]: crate::system::Query#allowed-transmutes
+    ///
+    /// # Panics
+    ///
+    /// This will panic if `next` has been called on `QueryIter` before, unless the underlying `Query` is empty.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// #[derive(Component)]
+    /// struct Initiative(u32);
+    ///
+    /// // Entities with the highest initiative take their turn first.
+    /// fn system_1(query: Query<(Entity, &Initiative)>) {
+    ///     let turn_order: Vec<Entity> = query
+    ///         .iter()
+    ///         .sort_unstable_by_key::<&Initiative, _>(|initiative| std::cmp::Reverse(initiative.0))
+    ///         .map(|(entity, _)| entity)
+    ///         .collect();
+    /// }
+    /// # let mut world = World::new();
+    /// # let mut schedule = Schedule::default();
+    /// # schedule.add_systems(system_1);
+    /// # schedule.run(&mut world);
+    /// ```
+    pub fn sort_unstable_by_key<L: ReadOnlyQueryData + 'w, K>(
+        self,
+        mut f: impl FnMut(&L::Item<'w>) -> K,
+    ) -> QuerySortedIter<
+        'w,
+        's,
+        D,
+        F,
+        impl ExactSizeIterator<Item = Entity> + DoubleEndedIterator + FusedIterator + 'w,
+    >
+    where
+        K: Ord,
+    {
+        // On the first successful iteration of `QueryIterationCursor`, `archetype_entities` or `table_entities`
+        // will be set to a non-zero value. The correctness of this method relies on this.
+        // I.e. this sort method will execute if and only if `next` on `QueryIterationCursor` of a
+        // non-empty `QueryIter` has not yet been called. When empty, this sort method will not panic.
+        if !self.cursor.archetype_entities.is_empty() || !self.cursor.table_entities.is_empty() {
+            panic!("it is not valid to call sort() after next()")
+        }
+
+        let world = self.world;
+
+        let query_lens_state = self
+            .query_state
+            .transmute_filtered::<(L, Entity), F>(world.components());
+
+        // SAFETY:
+        // `self.world` has permission to access the required components.
+        // The original query iter has not been iterated on, so no items are aliased from it.
+        let query_lens = unsafe {
+            query_lens_state.iter_unchecked_manual(
+                world,
+                world.last_change_tick(),
+                world.change_tick(),
+            )
+        };
+        let mut keyed_query: Vec<_> = query_lens.collect();
+        keyed_query.sort_unstable_by_key(|(lens, _)| f(lens));
+        let entity_iter = keyed_query.into_iter().map(|(.., entity)| entity);
+        // SAFETY:
+        // `self.world` has permission to access the required components.
+        // Each lens query item is dropped before the respective actual query item is accessed.
+        unsafe {
+            QuerySortedIter::new(
+                world,
+                self.query_state,
+                entity_iter,
+                world.last_change_tick(),
+                world.change_tick(),
+            )
+        }
+    }
+
     /// Sort all query items into a new iterator with a key extraction function over the query lens.
     ///
     /// This sort is stable (i.e., does not reorder equal elements).
@@ -874,7 +966,7 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Debug for QueryIter<'w, 's, D, F> {
 ///
 /// This struct is created by the [`QueryIter::sort`], [`QueryIter::sort_unstable`],
 /// [`QueryIter::sort_by`], [`QueryIter::sort_unstable_by`], [`QueryIter::sort_by_key`],
-/// and [`QueryIter::sort_by_cached_key`] methods.
+/// [`QueryIter::sort_unstable_by_key`], and [`QueryIter::sort_by_cached_key`] methods.
 pub struct QuerySortedIter<'w, 's, D: QueryData, F: QueryFilter, I>
 where
     I: Iterator<Item = Entity>,
@@ -1648,7 +1740,7 @@ mod tests {
 
     #[derive(Component, Debug, PartialEq, PartialOrd, Clone, Copy)]
     struct A(f32);
-    #[derive(Component, Debug, Eq, PartialEq, Clone, Copy)]
+    #[derive(Component, Debug, Eq, PartialEq, PartialOrd, Ord, Clone, Copy)]
     #[component(storage = "SparseSet")]
     struct Sparse(usize);
 
@@ -1681,6 +1773,11 @@ mod tests {
             .sort_by_key::<Entity, _>(|&e| e)
             .collect::<Vec<_>>();
 
+        let sort_unstable_by_key = query
+            .iter(&world)
+            .sort_unstable_by_key::<Entity, _>(|&e| e)
+            .collect::<Vec<_>>();
+
         let sort_by_cached_key = query
             .iter(&world)
             .sort_by_cached_key::<Entity, _>(|&e| e)
@@ -1701,6 +1798,9 @@ mod tests {
         let mut sort_by_key_v2 = query.iter(&world).collect::<Vec<_>>();
         sort_by_key_v2.sort_by_key(|&e| e);
 
+        let mut sort_unstable_by_key_v2 = query.iter(&world).collect::<Vec<_>>();
+        sort_unstable_by_key_v2.sort_unstable_by_key(|&e| e);
+
         let mut sort_by_cached_key_v2 = query.iter(&world).collect::<Vec<_>>();
         sort_by_cached_key_v2.sort_by_cached_key(|&e| e);
 
@@ -1709,9 +1809,52 @@ mod tests {
         assert_eq!(sort_by, sort_by_v2);
         assert_eq!(sort_unstable_by, sort_unstable_by_v2);
         assert_eq!(sort_by_key, sort_by_key_v2);
+        assert_eq!(sort_unstable_by_key, sort_unstable_by_key_v2);
         assert_eq!(sort_by_cached_key, sort_by_cached_key_v2);
     }
 
+    #[test]
+    fn query_sort_yields_full_items() {
+        let mut world = World::new();
+
+        world.spawn((A(3.), Sparse(3)));
+        world.spawn((A(1.), Sparse(1)));
+        world.spawn((A(2.), Sparse(2)));
+        world.spawn(A(0.));
+
+        let mut query = world.query::<(&A, &Sparse)>();
+
+        let sorted = query
+            .iter(&world)
+            .sort::<&Sparse>()
+            .map(|(a, sparse)| (a.0, sparse.0))
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![(1., 1), (2., 2), (3., 3)]);
+
+        let reversed = query
+            .iter(&world)
+            .sort_unstable_by_key::<&Sparse, _>(|sparse| std::cmp::Reverse(sparse.0))
+            .map(|(a, _)| a.0)
+            .collect::<Vec<_>>();
+        assert_eq!(reversed, vec![3., 2., 1.]);
+
+        let mut query_mut = world.query::<(&mut A, &Sparse)>();
+        for (i, (mut a, _)) in query_mut
+            .iter_mut(&mut world)
+            .sort_by::<&Sparse>(|s1, s2| s2.0.cmp(&s1.0))
+            .enumerate()
+        {
+            a.0 = i as f32;
+        }
+
+        let updated = query
+            .iter(&world)
+            .sort::<&Sparse>()
+            .map(|(a, _)| a.0)
+            .collect::<Vec<_>>();
+        assert_eq!(updated, vec![2., 1., 0.]);
+    }
+
     #[test]
     #[should_panic]
     fn query_sort_after_next() {
@@ -0,0 +1,937 @@
+use crate::entity::{Entity, EntityHashMap};
+use crate::reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities};
+use crate::system::{Commands, EntityCommands, Resource};
+use crate::world::{Command, World};
+use bevy_reflect::{Reflect, ReflectRef, TypeRegistry};
+use std::borrow::Cow;
+
+use crate as bevy_ecs;
+
+/// A [`World`] mutation that can be recorded in a [`CommandLog`], inspected and replayed.
+///
+/// Unlike the type-erased closures stored in a [`CommandQueue`](crate::world::CommandQueue),
+/// logged commands carry their component data as reflected values, so they can be serialized
+/// (with the `serialize` feature, see `CommandLogSerializer`) and applied to another [`World`].
+///
+/// Applying a `LoggedCommand` as a [`Command`] performs the mutation and, if the world has a
+/// [`CommandLog`] resource, appends the command to it.
+#[derive(Debug)]
+pub enum LoggedCommand {
+    /// An entity was spawned.
+    Spawn {
+        /// The spawned entity.
+        entity: Entity,
+    },
+    /// Components were inserted into an entity, replacing existing values.
+    Insert {
+        /// The entity the components were inserted into.
+        entity: Entity,
+        /// The reflected values of the inserted components.
+        components: Vec<Box<dyn Reflect>>,
+    },
+    /// Components were removed from an entity.
+    Remove {
+        /// The entity the components were removed from.
+        entity: Entity,
+        /// The type paths of the removed components.
+        components: Vec<Cow<'static, str>>,
+    },
+    /// An entity was despawned.
+    Despawn {
+        /// The despawned entity.
+        entity: Entity,
+    },
+}
+
+impl LoggedCommand {
+    /// Returns the entity this command applies to.
+    pub fn entity(&self) -> Entity {
+        match self {
+            LoggedCommand::Spawn { entity }
+            | LoggedCommand::Insert { entity, .. }
+            | LoggedCommand::Remove { entity, .. }
+            | LoggedCommand::Despawn { entity } => *entity,
+        }
+    }
+
+    /// Applies this command to `world`, translating the entities it refers to through
+    /// `entity_map`.
+    ///
+    /// Entities that are not in the map yet, including the one created by
+    /// [`LoggedCommand::Spawn`], are spawned in `world` and added to the map.
+    /// Entity references held by inserted components are mapped as well, if their type
+    /// registers [`ReflectMapEntities`]. The referenced entities that are not in the map yet
+    /// are spawned and added to it too, so that the log can refer to an entity before its
+    /// [`LoggedCommand::Spawn`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if an inserted or removed component is not registered in `type_registry` with
+    /// [`ReflectComponent`] data.
+    pub fn apply_mapped(
+        &self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) {
+        let entity = *entity_map
+            .entry(self.entity())
+            .or_insert_with(|| world.spawn_empty().id());
+
+        match self {
+            LoggedCommand::Spawn { .. } => {}
+            LoggedCommand::Insert { components, .. } => {
+                insert_reflected(world, entity, type_registry, components);
+                for component in components {
+                    let map_entities = component
+                        .get_represented_type_info()
+                        .and_then(|info| type_registry.get(info.type_id()))
+                        .and_then(|registration| registration.data::<ReflectMapEntities>());
+                    let Some(map_entities) = map_entities else {
+                        continue;
+                    };
+                    // Entities missing from the map would be mapped to dead placeholders.
+                    let mut referenced = Vec::new();
+                    collect_entities(&**component, &mut referenced);
+                    for referenced in referenced {
+                        if referenced != Entity::PLACEHOLDER {
+                            entity_map
+                                .entry(referenced)
+                                .or_insert_with(|| world.spawn_empty().id());
+                        }
+                    }
+                    map_entities.map_entities(world, entity_map, &[entity]);
+                }
+            }
+            LoggedCommand::Remove { components, .. } => {
+                remove_reflected(world, entity, type_registry, components);
+            }
+            LoggedCommand::Despawn { .. } => {
+                world.despawn(entity);
+            }
+        }
+    }
+
+    /// Applies this command to `world`, using the entities it refers to as they are.
+    fn apply_in_place(&self, world: &mut World, type_registry: &TypeRegistry) {
+        match self {
+            LoggedCommand::Spawn { entity } => {
+                world.get_or_spawn(*entity);
+            }
+            LoggedCommand::Insert { entity, components } => {
+                insert_reflected(world, *entity, type_registry, components);
+            }
+            LoggedCommand::Remove { entity, components } => {
+                remove_reflected(world, *entity, type_registry, components);
+            }
+            LoggedCommand::Despawn { entity } => {
+                world.despawn(*entity);
+            }
+        }
+    }
+}
+
+impl Command for LoggedCommand {
+    fn apply(self, world: &mut World) {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        self.apply_in_place(world, &registry.read());
+        if let Some(mut log) = world.get_resource_mut::<CommandLog>() {
+            log.commands.push(self);
+        }
+    }
+}
+
+fn insert_reflected(
+    world: &mut World,
+    entity: Entity,
+    type_registry: &TypeRegistry,
+    components: &[Box<dyn Reflect>],
+) {
+    let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+        panic!("error[B0003]: Could not insert reflected components for entity {entity:?} because it doesn't exist in this World. See: https://bevyengine.org/learn/errors/#b0003");
+    };
+    for component in components {
+        let type_path = component
+            .get_represented_type_info()
+            .map_or_else(|| component.reflect_type_path(), |info| info.type_path());
+        let Some(reflect_component) = type_registry
+            .get_with_type_path(type_path)
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            panic!("Could not get ReflectComponent data (for component type {type_path}) because it doesn't exist in the TypeRegistry.");
+        };
+        reflect_component.insert(&mut entity_mut, &**component, type_registry);
+    }
+}
+
+fn remove_reflected(
+    world: &mut World,
+    entity: Entity,
+    type_registry: &TypeRegistry,
+    components: &[Cow<'static, str>],
+) {
+    let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+        return;
+    };
+    for type_path in components {
+        let Some(reflect_component) = type_registry
+            .get_with_type_path(type_path)
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            panic!("Could not get ReflectComponent data (for component type {type_path}) because it doesn't exist in the TypeRegistry.");
+        };
+        reflect_component.remove(&mut entity_mut);
+    }
+}
+
+/// Adds the [`Entity`] values found in the fields of `value` to `entities`.
+fn collect_entities(value: &dyn Reflect, entities: &mut Vec<Entity>) {
+    if let Some(entity) = value.downcast_ref::<Entity>() {
+        entities.push(*entity);
+        return;
+    }
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::Tuple(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field, entities);
+            }
+        }
+        ReflectRef::List(value) => {
+            for item in value.iter() {
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Array(value) => {
+            for item in value.iter() {
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Map(value) => {
+            for (key, item) in value.iter() {
+                collect_entities(key, entities);
+                collect_entities(item, entities);
+            }
+        }
+        ReflectRef::Enum(value) => {
+            for field in value.iter_fields() {
+                collect_entities(field.value(), entities);
+            }
+        }
+        ReflectRef::Value(_) => {}
+    }
+}
+
+/// A [`Resource`] that records [`LoggedCommand`]s as they are applied.
+///
+/// Recording is opt-in: commands are only logged while this resource exists, and only
+/// if they are issued through [`LoggedCommand`]s, for example with the methods of
+/// [`LogCommandsExt`] and [`LogEntityCommandsExt`].
+///
+/// A log can be replayed into another [`World`] with [`CommandLog::replay`], or written out
+/// with `CommandLogSerializer` when the `serialize` feature is enabled.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::reflect::{CommandLog, LogCommandsExt, LogEntityCommandsExt};
+/// # use bevy_ecs::system::RunSystemOnce;
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// world.init_resource::<CommandLog>();
+///
+/// world.run_system_once(|mut commands: Commands| {
+///     commands
+///         .spawn_logged()
+///         .insert_logged(Box::new(Health(10)));
+/// });
+///
+/// let log = world.resource_mut::<CommandLog>().take();
+/// assert_eq!(log.len(), 2);
+///
+/// let mut replica = World::new();
+/// replica.insert_resource(world.resource::<AppTypeRegistry>().clone());
+/// let entity_map = log.replay(&mut replica);
+/// assert_eq!(entity_map.len(), 1);
+/// ```
+#[derive(Resource, Default, Debug)]
+pub struct CommandLog {
+    commands: Vec<LoggedCommand>,
+}
+
+impl CommandLog {
+    /// Creates a log from the given commands.
+    pub fn from_commands(commands: Vec<LoggedCommand>) -> Self {
+        Self { commands }
+    }
+
+    /// Appends a command to the log without applying it.
+    pub fn push(&mut self, command: LoggedCommand) {
+        self.commands.push(command);
+    }
+
+    /// Returns an iterator over the recorded commands, in the order in which they were applied.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &LoggedCommand> {
+        self.commands.iter()
+    }
+
+    /// Returns the number of recorded commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns `true` if no commands have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Removes all recorded commands.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// Takes the recorded commands, leaving this log empty.
+    ///
+    /// This is typically called once per frame to extract that frame's mutations.
+    #[must_use]
+    pub fn take(&mut self) -> CommandLog {
+        std::mem::take(self)
+    }
+
+    /// Applies all recorded commands to `world`, using its [`AppTypeRegistry`].
+    ///
+    /// Every entity the log refers to is spawned fresh in `world`; the returned map
+    /// translates the recorded entities to the new ones.
+    pub fn replay(&self, world: &mut World) -> EntityHashMap<Entity> {
+        let mut entity_map = EntityHashMap::default();
+        self.replay_with_map(world, &mut entity_map);
+        entity_map
+    }
+
+    /// Applies all recorded commands to `world`, using its [`AppTypeRegistry`] and
+    /// translating entities through `entity_map`.
+    ///
+    /// Reuse the same map across calls to keep replaying consecutive logs into the same
+    /// world, or pre-populate it to target existing entities.
+    /// See [`LoggedCommand::apply_mapped`] for details.
+    pub fn replay_with_map(&self, world: &mut World, entity_map: &mut EntityHashMap<Entity>) {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        for command in &self.commands {
+            command.apply_mapped(world, &registry, entity_map);
+        }
+    }
+}
+
+impl IntoIterator for CommandLog {
+    type Item = LoggedCommand;
+    type IntoIter = std::vec::IntoIter<LoggedCommand>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.commands.into_iter()
+    }
+}
+
+/// An extension trait for [`Commands`] to spawn entities through a [`CommandLog`].
+pub trait LogCommandsExt {
+    /// Spawns a new empty entity and records a [`LoggedCommand::Spawn`].
+    ///
+    /// Use [`LogEntityCommandsExt`] on the returned [`EntityCommands`] to record further
+    /// changes to the entity.
+    fn spawn_logged(&mut self) -> EntityCommands;
+}
+
+impl LogCommandsExt for Commands<'_, '_> {
+    fn spawn_logged(&mut self) -> EntityCommands {
+        let entity = self.spawn_empty().id();
+        self.add(LoggedCommand::Spawn { entity });
+        self.entity(entity)
+    }
+}
+
+/// An extension trait for [`EntityCommands`] to mutate entities through a [`CommandLog`].
+///
+/// These behave like their counterparts in [`ReflectCommandExt`](crate::reflect::ReflectCommandExt),
+/// and additionally record the change if the world has a [`CommandLog`].
+pub trait LogEntityCommandsExt {
+    /// Inserts the given reflected component, recording a [`LoggedCommand::Insert`].
+    ///
+    /// # Panics
+    ///
+    /// - If the entity doesn't exist.
+    /// - If [`AppTypeRegistry`] does not have the reflection data for the given component.
+    fn insert_logged(&mut self, component: Box<dyn Reflect>) -> &mut Self;
+
+    /// Removes the component with the given type path, recording a [`LoggedCommand::Remove`].
+    fn remove_logged(&mut self, component_type_path: impl Into<Cow<'static, str>>) -> &mut Self;
+
+    /// Despawns the entity, recording a [`LoggedCommand::Despawn`].
+    fn despawn_logged(&mut self);
+}
+
+impl LogEntityCommandsExt for EntityCommands<'_> {
+    fn insert_logged(&mut self, component: Box<dyn Reflect>) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(LoggedCommand::Insert {
+            entity,
+            components: vec![component],
+        });
+        self
+    }
+
+    fn remove_logged(&mut self, component_type_path: impl Into<Cow<'static, str>>) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(LoggedCommand::Remove {
+            entity,
+            components: vec![component_type_path.into()],
+        });
+        self
+    }
+
+    fn despawn_logged(&mut self) {
+        let entity = self.id();
+        self.commands().add(LoggedCommand::Despawn { entity });
+    }
+}
+
+#[cfg(feature = "serialize")]
+pub use serialize::{CommandLogDeserializer, CommandLogSerializer};
+
+#[cfg(feature = "serialize")]
+mod serialize {
+    use super::{CommandLog, LoggedCommand};
+    use crate::entity::Entity;
+    use bevy_reflect::serde::{ReflectDeserializer, ReflectSerializer};
+    use bevy_reflect::{Reflect, TypeRegistry};
+    use serde::de::{self, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess};
+    use serde::ser::{SerializeSeq, SerializeStructVariant};
+    use serde::{Deserializer, Serialize, Serializer};
+    use std::borrow::Cow;
+    use std::fmt;
+
+    const LOGGED_COMMAND: &str = "LoggedCommand";
+    const VARIANTS: &[&str] = &["Spawn", "Insert", "Remove", "Despawn"];
+    const ENTITY: &str = "entity";
+    const COMPONENTS: &str = "components";
+    const FIELDS: &[&str] = &[ENTITY, COMPONENTS];
+
+    /// Serializes a [`CommandLog`] as a list of commands, with component data serialized
+    /// through the [`TypeRegistry`].
+    pub struct CommandLogSerializer<'a> {
+        log: &'a CommandLog,
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'a> CommandLogSerializer<'a> {
+        /// Creates a serializer for `log`, using `registry` to serialize component data.
+        pub fn new(log: &'a CommandLog, registry: &'a TypeRegistry) -> Self {
+            Self { log, registry }
+        }
+    }
+
+    impl Serialize for CommandLogSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.log.len()))?;
+            for command in self.log.iter() {
+                seq.serialize_element(&LoggedCommandSerializer {
+                    command,
+                    registry: self.registry,
+                })?;
+            }
+            seq.end()
+        }
+    }
+
+    struct LoggedCommandSerializer<'a> {
+        command: &'a LoggedCommand,
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for LoggedCommandSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self.command {
+                LoggedCommand::Spawn { entity } => {
+                    serializer.serialize_newtype_variant(LOGGED_COMMAND, 0, VARIANTS[0], entity)
+                }
+                LoggedCommand::Insert { entity, components } => {
+                    let mut state =
+                        serializer.serialize_struct_variant(LOGGED_COMMAND, 1, VARIANTS[1], 2)?;
+                    state.serialize_field(ENTITY, entity)?;
+                    state.serialize_field(
+                        COMPONENTS,
+                        &ComponentsSerializer {
+                            components,
+                            registry: self.registry,
+                        },
+                    )?;
+                    state.end()
+                }
+                LoggedCommand::Remove { entity, components } => {
+                    let mut state =
+                        serializer.serialize_struct_variant(LOGGED_COMMAND, 2, VARIANTS[2], 2)?;
+                    state.serialize_field(ENTITY, entity)?;
+                    state.serialize_field(COMPONENTS, components)?;
+                    state.end()
+                }
+                LoggedCommand::Despawn { entity } => {
+                    serializer.serialize_newtype_variant(LOGGED_COMMAND, 3, VARIANTS[3], entity)
+                }
+            }
+        }
+    }
+
+    struct ComponentsSerializer<'a> {
+        components: &'a [Box<dyn Reflect>],
+        registry: &'a TypeRegistry,
+    }
+
+    impl Serialize for ComponentsSerializer<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut seq = serializer.serialize_seq(Some(self.components.len()))?;
+            for component in self.components {
+                seq.serialize_element(&ReflectSerializer::new(&**component, self.registry))?;
+            }
+            seq.end()
+        }
+    }
+
+    /// Deserializes a [`CommandLog`] written by [`CommandLogSerializer`], using the
+    /// [`TypeRegistry`] to deserialize component data.
+    pub struct CommandLogDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'a> CommandLogDeserializer<'a> {
+        /// Creates a deserializer that uses `registry` to deserialize component data.
+        pub fn new(registry: &'a TypeRegistry) -> Self {
+            Self { registry }
+        }
+    }
+
+    impl<'de> DeserializeSeed<'de> for CommandLogDeserializer<'_> {
+        type Value = CommandLog;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'de> de::Visitor<'de> for CommandLogDeserializer<'_> {
+        type Value = CommandLog;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a list of logged commands")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut commands = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+            while let Some(command) = seq.next_element_seed(LoggedCommandDeserializer {
+                registry: self.registry,
+            })? {
+                commands.push(command);
+            }
+            Ok(CommandLog::from_commands(commands))
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    struct LoggedCommandDeserializer<'a> {
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'de> DeserializeSeed<'de> for LoggedCommandDeserializer<'_> {
+        type Value = LoggedCommand;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_enum(LOGGED_COMMAND, VARIANTS, self)
+        }
+    }
+
+    impl<'de> de::Visitor<'de> for LoggedCommandDeserializer<'_> {
+        type Value = LoggedCommand;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a logged command")
+        }
+
+        fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+            let (variant, access) = data.variant::<Variant>()?;
+            match variant {
+                Variant::Spawn => Ok(LoggedCommand::Spawn {
+                    entity: access.newtype_variant()?,
+                }),
+                Variant::Despawn => Ok(LoggedCommand::Despawn {
+                    entity: access.newtype_variant()?,
+                }),
+                Variant::Insert | Variant::Remove => access.struct_variant(
+                    FIELDS,
+                    EntityAndComponentsVisitor {
+                        variant,
+                        registry: self.registry,
+                    },
+                ),
+            }
+        }
+    }
+
+    #[derive(Clone, Copy)]
+    enum Variant {
+        Spawn,
+        Insert,
+        Remove,
+        Despawn,
+    }
+
+    impl<'de> de::Deserialize<'de> for Variant {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer
+                .deserialize_identifier(IdentifierVisitor(VARIANTS))
+                .map(|index| match index {
+                    0 => Variant::Spawn,
+                    1 => Variant::Insert,
+                    2 => Variant::Remove,
+                    _ => Variant::Despawn,
+                })
+        }
+    }
+
+    /// Identifies a variant or field by its name or index in the given list.
+    struct IdentifierVisitor(&'static [&'static str]);
+
+    impl<'de> de::Visitor<'de> for IdentifierVisitor {
+        type Value = usize;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "one of {:?}", self.0)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+            match usize::try_from(value) {
+                Ok(index) if index < self.0.len() => Ok(index),
+                _ => Err(E::invalid_value(de::Unexpected::Unsigned(value), &self)),
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            self.0
+                .iter()
+                .position(|name| *name == value)
+                .ok_or_else(|| E::unknown_variant(value, self.0))
+        }
+    }
+
+    struct Field(usize);
+
+    impl<'de> de::Deserialize<'de> for Field {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer
+                .deserialize_identifier(IdentifierVisitor(FIELDS))
+                .map(Field)
+        }
+    }
+
+    struct EntityAndComponentsVisitor<'a> {
+        variant: Variant,
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'a> EntityAndComponentsVisitor<'a> {
+        fn build<E: de::Error>(
+            self,
+            entity: Option<Entity>,
+            components: Option<ComponentsValue>,
+        ) -> Result<LoggedCommand, E> {
+            let entity = entity.ok_or_else(|| E::missing_field(ENTITY))?;
+            let components = components.ok_or_else(|| E::missing_field(COMPONENTS))?;
+            Ok(match components {
+                ComponentsValue::Reflected(components) => {
+                    LoggedCommand::Insert { entity, components }
+                }
+                ComponentsValue::Paths(components) => LoggedCommand::Remove { entity, components },
+            })
+        }
+
+        fn components_seed(&self) -> ComponentsDeserializer<'a> {
+            ComponentsDeserializer {
+                variant: self.variant,
+                registry: self.registry,
+            }
+        }
+    }
+
+    impl<'de> de::Visitor<'de> for EntityAndComponentsVisitor<'_> {
+        type Value = LoggedCommand;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an entity and a list of components")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let entity = seq.next_element()?;
+            let components = seq.next_element_seed(self.components_seed())?;
+            self.build(entity, components)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut entity = None;
+            let mut components = None;
+            while let Some(Field(field)) = map.next_key()? {
+                if field == 0 {
+                    entity = Some(map.next_value()?);
+                } else {
+                    components = Some(map.next_value_seed(self.components_seed())?);
+                }
+            }
+            self.build(entity, components)
+        }
+    }
+
+    enum ComponentsValue {
+        Reflected(Vec<Box<dyn Reflect>>),
+        Paths(Vec<Cow<'static, str>>),
+    }
+
+    struct ComponentsDeserializer<'a> {
+        variant: Variant,
+        registry: &'a TypeRegistry,
+    }
+
+    impl<'de> DeserializeSeed<'de> for ComponentsDeserializer<'_> {
+        type Value = ComponentsValue;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_seq(self)
+        }
+    }
+
+    impl<'de> de::Visitor<'de> for ComponentsDeserializer<'_> {
+        type Value = ComponentsValue;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a list of components")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            if let Variant::Remove = self.variant {
+                let mut paths = Vec::new();
+                while let Some(path) = seq.next_element::<String>()? {
+                    paths.push(Cow::Owned(path));
+                }
+                return Ok(ComponentsValue::Paths(paths));
+            }
+
+            let mut components = Vec::new();
+            while let Some(component) =
+                seq.next_element_seed(ReflectDeserializer::new(self.registry))?
+            {
+                components.push(component);
+            }
+            Ok(ComponentsValue::Reflected(components))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+    use crate::entity::{EntityMapper, MapEntities};
+    use crate::system::RunSystemOnce;
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component, MapEntities)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            self.0 = entity_mapper.map_entity(self.0);
+        }
+    }
+
+    impl Default for Target {
+        fn default() -> Self {
+            Self(Entity::PLACEHOLDER)
+        }
+    }
+
+    fn world_with_registry() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let registry = world.resource::<AppTypeRegistry>();
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Target>();
+        }
+        world
+    }
+
+    fn record(world: &mut World) -> (Entity, Entity) {
+        world.init_resource::<CommandLog>();
+        world.run_system_once(|mut commands: Commands| {
+            let a = commands
+                .spawn_logged()
+                .insert_logged(Box::new(Health(3)))
+                .id();
+            let b = commands
+                .spawn_logged()
+                .insert_logged(Box::new(Target(a)))
+                .id();
+            commands
+                .entity(a)
+                .insert_logged(Box::new(Health(5)))
+                .remove_logged(<Target as bevy_reflect::TypePath>::type_path());
+            (a, b)
+        })
+    }
+
+    #[test]
+    fn logged_commands_are_applied_and_recorded() {
+        let mut world = world_with_registry();
+        let (a, b) = record(&mut world);
+
+        assert_eq!(world.get::<Health>(a), Some(&Health(5)));
+        assert_eq!(world.get::<Target>(b), Some(&Target(a)));
+
+        let log = world.resource::<CommandLog>();
+        assert_eq!(log.len(), 6);
+        assert!(matches!(log.iter().next(), Some(LoggedCommand::Spawn { entity }) if *entity == a));
+
+        world.run_system_once(move |mut commands: Commands| {
+            commands.entity(b).despawn_logged();
+        });
+        assert!(world.get_entity(b).is_none());
+        assert!(matches!(
+            world.resource::<CommandLog>().iter().last(),
+            Some(LoggedCommand::Despawn { entity }) if *entity == b
+        ));
+    }
+
+    #[test]
+    fn replay_into_another_world() {
+        let mut world = world_with_registry();
+        let (a, b) = record(&mut world);
+        let log = world.resource_mut::<CommandLog>().take();
+        assert!(world.resource::<CommandLog>().is_empty());
+
+        let mut replica = world_with_registry();
+        // Offset entity allocation so that ids differ between the worlds.
+        replica.spawn_empty();
+        let entity_map = log.replay(&mut replica);
+
+        let replica_a = entity_map[&a];
+        let replica_b = entity_map[&b];
+        assert_ne!(replica_a, a);
+        assert_eq!(replica.get::<Health>(replica_a), Some(&Health(5)));
+        assert_eq!(replica.get::<Target>(replica_b), Some(&Target(replica_a)));
+    }
+
+    #[test]
+    fn replay_reference_before_spawn() {
+        let mut world = world_with_registry();
+        let target = world.spawn_empty().id();
+        let source = world.spawn_empty().id();
+        let log = CommandLog::from_commands(vec![
+            LoggedCommand::Spawn { entity: source },
+            LoggedCommand::Insert {
+                entity: source,
+                components: vec![Box::new(Target(target))],
+            },
+            LoggedCommand::Spawn { entity: target },
+            LoggedCommand::Insert {
+                entity: target,
+                components: vec![Box::new(Health(1))],
+            },
+        ]);
+
+        let mut replica = world_with_registry();
+        let entity_map = log.replay(&mut replica);
+        let replica_target = entity_map[&target];
+        assert_eq!(replica.get::<Health>(replica_target), Some(&Health(1)));
+        assert_eq!(
+            replica.get::<Target>(entity_map[&source]),
+            Some(&Target(replica_target))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn replay_unregistered_removal() {
+        let mut world = world_with_registry();
+        let entity = world.spawn_empty().id();
+        CommandLog::from_commands(vec![LoggedCommand::Remove {
+            entity,
+            components: vec!["not::a::Component".into()],
+        }])
+        .replay(&mut world);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn serialize_round_trip() {
+        use serde::de::DeserializeSeed;
+
+        let mut world = world_with_registry();
+        let (a, _) = record(&mut world);
+        let log = world.resource_mut::<CommandLog>().take();
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let serialized = ron::to_string(&CommandLogSerializer::new(&log, &registry)).unwrap();
+
+        let mut deserializer = ron::Deserializer::from_str(&serialized).unwrap();
+        let deserialized = CommandLogDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(deserialized.len(), log.len());
+
+        // The round-tripped log serializes identically, so logs can be diffed as text.
+        let reserialized =
+            ron::to_string(&CommandLogSerializer::new(&deserialized, &registry)).unwrap();
+        assert_eq!(serialized, reserialized);
+
+        let mut replica = world_with_registry();
+        let entity_map = deserialized.replay(&mut replica);
+        assert_eq!(replica.get::<Health>(entity_map[&a]), Some(&Health(5)));
+    }
+
+    #[test]
+    fn unlogged_commands_are_not_recorded() {
+        let mut world = world_with_registry();
+        let entity = world.spawn_empty().id();
+        LoggedCommand::Insert {
+            entity,
+            components: vec![Box::new(Health(1))],
+        }
+        .apply(&mut world);
+        assert_eq!(world.get::<Health>(entity), Some(&Health(1)));
+        assert!(!world.contains_resource::<CommandLog>());
+    }
+}
@@ -9,6 +9,7 @@ use bevy_reflect::std_traits::ReflectDefault;
 use bevy_reflect::{Reflect, ReflectFromReflect, TypeRegistry, TypeRegistryArc};
 
 mod bundle;
+mod command_log;
 mod component;
 mod entity_commands;
 mod from_world;
@@ -16,6 +17,9 @@ mod map_entities;
 mod resource;
 
 pub use bundle::{ReflectBundle, ReflectBundleFns};
+pub use command_log::{CommandLog, LogCommandsExt, LogEntityCommandsExt, LoggedCommand};
+#[cfg(feature = "serialize")]
+pub use command_log::{CommandLogDeserializer, CommandLogSerializer};
 pub use component::{ReflectComponent, ReflectComponentFns};
 pub use entity_commands::ReflectCommandExt;
 pub use from_world::{ReflectFromWorld, ReflectFromWorldFns};
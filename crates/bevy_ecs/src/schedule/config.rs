@@ -7,7 +7,7 @@ use crate::{
         set::{InternedSystemSet, IntoSystemSet, SystemSet},
         Chain,
     },
-    system::{BoxedSystem, IntoSystem, System},
+    system::{BoxedSystem, IntoSystem, System, SystemOutput},
 };
 
 fn new_condition<M>(condition: impl Condition<M>) -> BoxedCondition {
@@ -33,12 +33,13 @@ fn ambiguous_with(graph_info: &mut GraphInfo, set: InternedSystemSet) {
     }
 }
 
-impl<Marker, F> IntoSystemConfigs<Marker> for F
+impl<Marker, F, Out> IntoSystemConfigs<(Out, Marker)> for F
 where
-    F: IntoSystem<(), (), Marker>,
+    F: IntoSystem<(), Out, Marker>,
+    Out: SystemOutput,
 {
     fn into_configs(self) -> SystemConfigs {
-        SystemConfigs::new_system(Box::new(IntoSystem::into_system(self)))
+        SystemConfigs::new_system(Out::into_boxed_system(IntoSystem::into_system(self)))
     }
 }
 
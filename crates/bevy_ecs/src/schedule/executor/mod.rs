@@ -6,6 +6,7 @@ pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
 pub use self::simple::SimpleExecutor;
 pub use self::single_threaded::SingleThreadedExecutor;
 
+use bevy_utils::Duration;
 use fixedbitset::FixedBitSet;
 
 use crate::{
@@ -79,6 +80,17 @@ pub struct SystemSchedule {
     ///
     /// If a set doesn't run because of its conditions, this is used to skip all systems in it.
     pub(super) systems_in_sets_with_conditions: Vec<FixedBitSet>,
+    /// Whether the executor measures how long each system takes to run.
+    pub(super) record_system_times: bool,
+    /// Indexed by system node id.
+    /// How long each system took to run, if measured by the executor.
+    pub(super) system_times: Vec<Duration>,
+    /// The [`Debug`](std::fmt::Debug) names of the system sets whose times are recorded in the
+    /// [`SystemTimings`](super::SystemTimings), which exclude anonymous sets and system type sets.
+    pub(super) timed_set_names: Vec<String>,
+    /// Indexed by system node id.
+    /// List of the indices in `timed_set_names` of the sets containing the system.
+    pub(super) timed_sets_of_systems: Vec<Vec<usize>>,
 }
 
 impl SystemSchedule {
@@ -94,6 +106,10 @@ impl SystemSchedule {
             system_dependents: Vec::new(),
             sets_with_conditions_of_systems: Vec::new(),
             systems_in_sets_with_conditions: Vec::new(),
+            record_system_times: false,
+            system_times: Vec::new(),
+            timed_set_names: Vec::new(),
+            timed_sets_of_systems: Vec::new(),
         }
     }
 }
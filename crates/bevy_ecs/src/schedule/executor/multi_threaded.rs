@@ -8,6 +8,7 @@ use bevy_utils::default;
 use bevy_utils::syncunsafecell::SyncUnsafeCell;
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::{info_span, Span};
+use bevy_utils::{Duration, Instant};
 use std::panic::AssertUnwindSafe;
 
 use concurrent_queue::ConcurrentQueue;
@@ -32,6 +33,7 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<BoxedSystem>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    record_system_times: bool,
 }
 
 struct Conditions<'a> {
@@ -49,6 +51,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
     ) -> Self {
         Environment {
             executor,
+            record_system_times: schedule.record_system_times,
             systems: SyncUnsafeCell::from_mut(schedule.systems.as_mut_slice()).as_slice_of_cells(),
             conditions: SyncUnsafeCell::new(Conditions {
                 system_conditions: &mut schedule.system_conditions,
@@ -77,6 +80,8 @@ struct SystemTaskMetadata {
 /// The result of running a system that is sent across a channel.
 struct SystemResult {
     system_index: usize,
+    /// How long the system took to run, if measured.
+    elapsed: Option<Duration>,
 }
 
 /// Runs the schedule using a thread pool. Non-conflicting systems can run in parallel.
@@ -123,6 +128,8 @@ pub struct ExecutorState {
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
+    /// How long each system took to run, if measured.
+    system_times: Vec<Duration>,
 }
 
 /// References to data required by the executor.
@@ -161,6 +168,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         state.completed_systems = FixedBitSet::with_capacity(sys_count);
         state.skipped_systems = FixedBitSet::with_capacity(sys_count);
         state.unapplied_systems = FixedBitSet::with_capacity(sys_count);
+        state.system_times = vec![Duration::ZERO; sys_count];
 
         state.system_task_metadata = Vec::with_capacity(sys_count);
         for index in 0..sys_count {
@@ -251,6 +259,11 @@ impl SystemExecutor for MultiThreadedExecutor {
             std::panic::resume_unwind(payload);
         }
 
+        if schedule.record_system_times {
+            schedule.system_times.clone_from(&state.system_times);
+            state.system_times.fill(Duration::ZERO);
+        }
+
         debug_assert!(state.ready_systems.is_clear());
         debug_assert!(state.running_systems.is_clear());
         state.active_access.clear();
@@ -270,12 +283,16 @@ impl<'scope, 'env: 'scope, 'sys> Context<'scope, 'env, 'sys> {
         system_index: usize,
         res: Result<(), Box<dyn Any + Send>>,
         system: &BoxedSystem,
+        start: Option<Instant>,
     ) {
         // tell the executor that the system finished
         self.environment
             .executor
             .system_completion
-            .push(SystemResult { system_index })
+            .push(SystemResult {
+                system_index,
+                elapsed: start.map(|start| start.elapsed()),
+            })
             .unwrap_or_else(|error| unreachable!("{}", error));
         if let Err(payload) = res {
             eprintln!("Encountered a panic in system `{}`!", &*system.name());
@@ -349,6 +366,7 @@ impl ExecutorState {
             skipped_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
+            system_times: Vec::new(),
         }
     }
 
@@ -584,6 +602,7 @@ impl ExecutorState {
         let system_meta = &self.system_task_metadata[system_index];
 
         let task = async move {
+            let start = context.environment.record_system_times.then(Instant::now);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -596,7 +615,7 @@ impl ExecutorState {
                     );
                 };
             }));
-            context.system_completed(system_index, res, system);
+            context.system_completed(system_index, res, system, start);
         };
 
         self.active_access
@@ -626,17 +645,19 @@ impl ExecutorState {
             let unapplied_systems = self.unapplied_systems.clone();
             self.unapplied_systems.clear();
             let task = async move {
+                let start = context.environment.record_system_times.then(Instant::now);
                 let res = apply_deferred(&unapplied_systems, context.environment.systems, world);
-                context.system_completed(system_index, res, system);
+                context.system_completed(system_index, res, system, start);
             };
 
             context.scope.spawn_on_scope(task);
         } else {
             let task = async move {
+                let start = context.environment.record_system_times.then(Instant::now);
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     __rust_begin_short_backtrace::run(&mut **system, world);
                 }));
-                context.system_completed(system_index, res, system);
+                context.system_completed(system_index, res, system, start);
             };
 
             context.scope.spawn_on_scope(task);
@@ -647,7 +668,14 @@ impl ExecutorState {
     }
 
     fn finish_system_and_handle_dependents(&mut self, result: SystemResult) {
-        let SystemResult { system_index, .. } = result;
+        let SystemResult {
+            system_index,
+            elapsed,
+        } = result;
+
+        if let Some(elapsed) = elapsed {
+            self.system_times[system_index] = elapsed;
+        }
 
         if self.system_task_metadata[system_index].is_exclusive {
             self.exclusive_running = false;
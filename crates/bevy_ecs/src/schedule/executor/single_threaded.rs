@@ -1,5 +1,6 @@
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
+use bevy_utils::Instant;
 use fixedbitset::FixedBitSet;
 use std::panic::AssertUnwindSafe;
 
@@ -95,9 +96,13 @@ impl SystemExecutor for SingleThreadedExecutor {
                 continue;
             }
 
+            let start = schedule.record_system_times.then(Instant::now);
             let system = &mut schedule.systems[system_index];
             if is_apply_deferred(system) {
                 self.apply_deferred(schedule, world);
+                if let Some(start) = start {
+                    schedule.system_times[system_index] = start.elapsed();
+                }
                 continue;
             }
 
@@ -117,6 +122,9 @@ impl SystemExecutor for SingleThreadedExecutor {
                 eprintln!("Encountered a panic in system `{}`!", &*system.name());
                 std::panic::resume_unwind(payload);
             }
+            if let Some(start) = start {
+                schedule.system_times[system_index] = start.elapsed();
+            }
             self.unapplied_systems.insert(system_index);
         }
 
@@ -8,6 +8,7 @@ mod graph_utils;
 mod schedule;
 mod set;
 mod stepping;
+mod timings;
 
 pub use self::condition::*;
 pub use self::config::*;
@@ -15,6 +16,7 @@ pub use self::executor::*;
 use self::graph_utils::*;
 pub use self::schedule::*;
 pub use self::set::*;
+pub use self::timings::*;
 
 pub use self::graph_utils::NodeId;
 
@@ -1105,13 +1107,17 @@ mod tests {
         #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
         pub struct TestSchedule;
 
+        fn ignored_stepping() {
+            panic!("Executor ignored Stepping");
+        }
+
         macro_rules! assert_executor_supports_stepping {
             ($executor:expr) => {
                 // create a test schedule
                 let mut schedule = Schedule::new(TestSchedule);
                 schedule
                     .set_executor_kind($executor)
-                    .add_systems(|| panic!("Executor ignored Stepping"));
+                    .add_systems(ignored_stepping);
 
                 // Add our schedule to stepping & and enable stepping; this should
                 // prevent any systems in the schedule from running
@@ -1,6 +1,7 @@
 use std::{
     collections::BTreeSet,
     fmt::{Debug, Write},
+    mem,
 };
 
 #[cfg(feature = "trace")]
@@ -8,7 +9,7 @@ use bevy_utils::tracing::info_span;
 use bevy_utils::{default, tracing::info};
 use bevy_utils::{
     tracing::{error, warn},
-    HashMap, HashSet,
+    Duration, HashMap, HashSet, Instant,
 };
 use fixedbitset::FixedBitSet;
 use petgraph::{algo::TarjanScc, prelude::*};
@@ -16,7 +17,9 @@ use thiserror::Error;
 
 use crate::{
     self as bevy_ecs,
+    change_detection::DetectChangesMut,
     component::{ComponentId, Components, Tick},
+    error::{ErrorHandler, SystemErrorHandler},
     prelude::Component,
     schedule::*,
     system::{BoxedSystem, IntoSystem, Resource, System},
@@ -263,6 +266,7 @@ pub struct Schedule {
     executable: SystemSchedule,
     executor: Box<dyn SystemExecutor>,
     executor_initialized: bool,
+    error_handler: Option<ErrorHandler>,
 }
 
 #[derive(ScheduleLabel, Hash, PartialEq, Eq, Debug, Clone)]
@@ -287,6 +291,7 @@ impl Schedule {
             executable: SystemSchedule::new(),
             executor: make_executor(ExecutorKind::default()),
             executor_initialized: false,
+            error_handler: None,
         }
     }
 
@@ -373,6 +378,16 @@ impl Schedule {
         self
     }
 
+    /// Sets the [`ErrorHandler`] used for errors returned by systems in this schedule,
+    /// overriding the world's [`SystemErrorHandler`] while the schedule runs.
+    ///
+    /// The resource is updated in place without triggering change detection, and inserted with
+    /// its default value if the world doesn't have one.
+    pub fn set_error_handler(&mut self, error_handler: ErrorHandler) -> &mut Self {
+        self.error_handler = Some(error_handler);
+        self
+    }
+
     /// Runs all systems in this schedule on the `world`, using its current execution strategy.
     pub fn run(&mut self, world: &mut World) {
         #[cfg(feature = "trace")]
@@ -382,6 +397,36 @@ impl Schedule {
         self.initialize(world)
             .unwrap_or_else(|e| panic!("Error when initializing schedule {:?}: {e}", self.label));
 
+        let Some(error_handler) = self.error_handler else {
+            self.run_executor(world);
+            return;
+        };
+        let world_error_handler = mem::replace(
+            &mut world
+                .get_resource_or_insert_with(SystemErrorHandler::default)
+                .bypass_change_detection()
+                .0,
+            error_handler,
+        );
+        // Restores the world's handler even if a system panics.
+        let guard = RestoreErrorHandler {
+            world,
+            error_handler: world_error_handler,
+        };
+        self.run_executor(guard.world);
+    }
+
+    fn run_executor(&mut self, world: &mut World) {
+        let record_system_times = world.contains_resource::<SystemTimings>();
+        self.executable.record_system_times = record_system_times;
+        if record_system_times {
+            self.executable.system_times.clear();
+            self.executable
+                .system_times
+                .resize(self.executable.systems.len(), Duration::ZERO);
+        }
+        let start = record_system_times.then(Instant::now);
+
         #[cfg(not(feature = "bevy_debug_stepping"))]
         self.executor.run(&mut self.executable, world, None);
 
@@ -395,6 +440,40 @@ impl Schedule {
             self.executor
                 .run(&mut self.executable, world, skip_systems.as_ref());
         }
+
+        if let Some(start) = start {
+            self.record_system_times(world, start.elapsed());
+        }
+    }
+
+    /// Adds the system times measured by the executor to the [`SystemTimings`] of the `world`.
+    fn record_system_times(&self, world: &mut World, total: Duration) {
+        let Some(mut timings) = world.get_resource_mut::<SystemTimings>() else {
+            return;
+        };
+        let timings = timings.schedule_mut(self.label);
+        timings.runs += 1;
+        timings.total += total;
+
+        let executable = &self.executable;
+        for ((system, sets), &duration) in executable
+            .systems
+            .iter()
+            .zip(&executable.timed_sets_of_systems)
+            .zip(&executable.system_times)
+        {
+            *timings.systems.entry(system.name()).or_default() += duration;
+
+            for &set in sets {
+                let name = &executable.timed_set_names[set];
+                match timings.sets.get_mut(name) {
+                    Some(set_duration) => *set_duration += duration,
+                    None => {
+                        timings.sets.insert(name.clone(), duration);
+                    }
+                }
+            }
+        }
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -509,6 +588,20 @@ impl Schedule {
     }
 }
 
+/// Puts back the world's [`SystemErrorHandler`] replaced by [`Schedule::run`] when dropped.
+struct RestoreErrorHandler<'w> {
+    world: &'w mut World,
+    error_handler: ErrorHandler,
+}
+
+impl Drop for RestoreErrorHandler<'_> {
+    fn drop(&mut self) {
+        if let Some(mut handler) = self.world.get_resource_mut::<SystemErrorHandler>() {
+            handler.bypass_change_detection().0 = self.error_handler;
+        }
+    }
+}
+
 /// A directed acyclic graph structure.
 #[derive(Default)]
 pub struct Dag {
@@ -1466,6 +1559,30 @@ impl ScheduleGraph {
             }
         }
 
+        // get the sets containing each system, for the system timings
+        let (hg_timed_set_idxs, timed_set_names): (Vec<_>, Vec<_>) = self
+            .hierarchy
+            .topsort
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &id)| {
+                let set = self.get_set_at(id)?;
+                (!set.is_anonymous() && set.system_type().is_none())
+                    .then(|| (i, format!("{set:?}")))
+            })
+            .unzip();
+
+        let mut timed_sets_of_systems = vec![Vec::new(); sys_count];
+        for &(col, sys_id) in &hg_systems {
+            timed_sets_of_systems[dg_system_idx_map[&sys_id]] = hg_timed_set_idxs
+                .iter()
+                .enumerate()
+                .take_while(|&(_idx, &row)| row < col)
+                .filter(|&(_idx, &row)| hier_results_reachable[index(row, col, hg_node_count)])
+                .map(|(idx, _row)| idx)
+                .collect();
+        }
+
         SystemSchedule {
             systems: Vec::with_capacity(sys_count),
             system_conditions: Vec::with_capacity(sys_count),
@@ -1476,6 +1593,10 @@ impl ScheduleGraph {
             system_dependents,
             sets_with_conditions_of_systems,
             systems_in_sets_with_conditions,
+            record_system_times: false,
+            system_times: Vec::new(),
+            timed_set_names,
+            timed_sets_of_systems,
         }
     }
 
@@ -2064,15 +2185,15 @@ mod tests {
         #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
         struct Set;
 
+        fn must_not_run() {
+            panic!("This system must not run");
+        }
+
         let mut world = World::new();
         let mut schedule = Schedule::default();
 
         schedule.configure_sets(Set.run_if(|| false));
-        schedule.add_systems(
-            (|| panic!("This system must not run"))
-                .ambiguous_with(|| ())
-                .in_set(Set),
-        );
+        schedule.add_systems(must_not_run.ambiguous_with(|| ()).in_set(Set));
         schedule.run(&mut world);
     }
 
@@ -324,6 +324,17 @@ impl Stepping {
         self
     }
 
+    /// Returns `true` if a breakpoint is set for the system instance `node`.
+    ///
+    /// NOTE: Breakpoints set or cleared during this render frame are not
+    /// reflected until they are applied at the start of the next render frame.
+    pub fn is_breakpoint(&self, schedule: impl ScheduleLabel, node: NodeId) -> bool {
+        self.schedule_states
+            .get(&schedule.intern())
+            .and_then(|state| state.behaviors.get(&node))
+            .is_some_and(|behavior| matches!(behavior, SystemBehavior::Break))
+    }
+
     /// Clear a breakpoint for the system
     pub fn clear_breakpoint<Marker>(
         &mut self,
@@ -1351,7 +1362,9 @@ mod tests {
         //
         // first system will be configured as `run_if(|| false)`, so it can
         // just panic if called
-        let first_system = move || panic!("first_system should not be run");
+        fn first_system() {
+            panic!("first_system should not be run");
+        }
 
         // The second system, we need to know when it has been called, so we'll
         // add a resource for tracking if it has been run.  The system will
@@ -1557,4 +1570,29 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn is_breakpoint() {
+        let (schedule, _world) = setup();
+        let second = schedule.executable().system_ids[1];
+
+        let mut stepping = Stepping::new();
+        stepping
+            .add_schedule(TestSchedule)
+            .enable()
+            .set_breakpoint(TestSchedule, second_system)
+            .always_run(TestSchedule, first_system);
+
+        // breakpoints set by system type are applied once the schedule runs
+        stepping.next_frame();
+        assert!(!stepping.is_breakpoint(TestSchedule, second));
+        stepping.skipped_systems(&schedule);
+        assert!(stepping.is_breakpoint(TestSchedule, second));
+        assert!(!stepping.is_breakpoint(TestSchedule, schedule.executable().system_ids[0]));
+
+        stepping
+            .clear_breakpoint_node(TestSchedule, second)
+            .next_frame();
+        assert!(!stepping.is_breakpoint(TestSchedule, second));
+    }
 }
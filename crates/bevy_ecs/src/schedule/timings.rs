@@ -0,0 +1,224 @@
+use std::borrow::Cow;
+
+use bevy_utils::{Duration, HashMap};
+
+use crate::{
+    self as bevy_ecs,
+    schedule::{InternedScheduleLabel, ScheduleLabel, SystemSet},
+    system::Resource,
+};
+
+/// Records how long the systems and system sets of each [`Schedule`](super::Schedule) take to run.
+///
+/// Schedules only measure their systems while this resource is in the [`World`](crate::world::World)
+/// they run on. Timings accumulate over the runs of a schedule until [`SystemTimings::clear`] is called,
+/// which is usually done once per frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{ScheduleLabel, SystemTimings};
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// struct Update;
+///
+/// fn slow_system() {
+///     std::thread::sleep(std::time::Duration::from_millis(1));
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<SystemTimings>();
+/// let mut schedule = Schedule::new(Update);
+/// schedule.add_systems(slow_system);
+/// schedule.run(&mut world);
+///
+/// let timings = world.resource::<SystemTimings>();
+/// let (label, name, duration) = timings.slowest_systems(1)[0];
+/// assert_eq!(label, Update.intern());
+/// assert!(name.ends_with("slow_system"));
+/// assert!(duration.as_millis() >= 1);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct SystemTimings {
+    schedules: HashMap<InternedScheduleLabel, ScheduleTimings>,
+}
+
+impl SystemTimings {
+    /// Returns the timings of the schedule with the given label, if it ran since the last
+    /// [`clear`](Self::clear).
+    pub fn get(&self, label: impl ScheduleLabel) -> Option<&ScheduleTimings> {
+        self.schedules.get(&label.intern())
+    }
+
+    /// Iterates over the timings of the schedules that ran since the last [`clear`](Self::clear).
+    pub fn iter(&self) -> impl Iterator<Item = (InternedScheduleLabel, &ScheduleTimings)> {
+        self.schedules
+            .iter()
+            .map(|(label, timings)| (*label, timings))
+    }
+
+    /// Returns the `count` systems that took the longest to run, slowest first.
+    pub fn slowest_systems(&self, count: usize) -> Vec<(InternedScheduleLabel, &str, Duration)> {
+        let mut systems: Vec<_> = self
+            .iter()
+            .flat_map(|(label, timings)| {
+                timings
+                    .systems()
+                    .map(move |(name, duration)| (label, name, duration))
+            })
+            .collect();
+        systems.sort_by(|(_, _, a), (_, _, b)| b.cmp(a));
+        systems.truncate(count);
+        systems
+    }
+
+    /// Forgets all the recorded timings.
+    pub fn clear(&mut self) {
+        self.schedules.clear();
+    }
+
+    pub(super) fn schedule_mut(&mut self, label: InternedScheduleLabel) -> &mut ScheduleTimings {
+        self.schedules.entry(label).or_default()
+    }
+}
+
+/// The timings of a [`Schedule`](super::Schedule), recorded in [`SystemTimings`].
+#[derive(Debug, Default)]
+pub struct ScheduleTimings {
+    pub(super) runs: u32,
+    pub(super) total: Duration,
+    pub(super) systems: HashMap<Cow<'static, str>, Duration>,
+    pub(super) sets: HashMap<String, Duration>,
+}
+
+impl ScheduleTimings {
+    /// Returns how many times the schedule ran.
+    pub fn runs(&self) -> u32 {
+        self.runs
+    }
+
+    /// Returns how long the schedule took to run, including the overhead of the executor.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    /// Iterates over the names of the systems of the schedule and how long they took to run.
+    ///
+    /// Systems that were skipped, for example by their run conditions, took [`Duration::ZERO`].
+    /// The durations of systems with the same name are added together.
+    pub fn systems(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.systems
+            .iter()
+            .map(|(name, duration)| (&**name, *duration))
+    }
+
+    /// Iterates over the [`Debug`](std::fmt::Debug) names of the system sets of the schedule and
+    /// how long their systems took to run.
+    ///
+    /// This is the sum of the durations of the systems in the set, which is more than the time
+    /// spent in the set when its systems run in parallel. Anonymous sets and the sets of each
+    /// system type are not included.
+    pub fn sets(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.sets
+            .iter()
+            .map(|(name, duration)| (name.as_str(), *duration))
+    }
+
+    /// Returns how long the systems named `name` took to run.
+    pub fn system(&self, name: &str) -> Option<Duration> {
+        self.systems.get(name).copied()
+    }
+
+    /// Returns how long the systems in `set` took to run.
+    pub fn set(&self, set: impl SystemSet) -> Option<Duration> {
+        self.sets.get(&format!("{set:?}")).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+
+    use super::*;
+    use crate::{
+        prelude::*,
+        schedule::{ExecutorKind, ScheduleLabel},
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSchedule;
+
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    enum TestSet {
+        Outer,
+        Inner,
+    }
+
+    fn fast() {}
+
+    fn slow() {
+        sleep(Duration::from_millis(5));
+    }
+
+    fn skipped() {
+        sleep(Duration::from_millis(5));
+    }
+
+    fn name<M>(system: impl IntoSystem<(), (), M>) -> Cow<'static, str> {
+        IntoSystem::into_system(system).name()
+    }
+
+    fn record(executor: ExecutorKind) {
+        let mut world = World::new();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule
+            .set_executor_kind(executor)
+            .configure_sets(TestSet::Inner.in_set(TestSet::Outer))
+            .add_systems((
+                fast.in_set(TestSet::Outer),
+                slow.in_set(TestSet::Inner),
+                skipped.run_if(|| false).in_set(TestSet::Inner),
+            ));
+
+        // Nothing is measured without the resource.
+        schedule.run(&mut world);
+        world.init_resource::<SystemTimings>();
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let timings = world.resource::<SystemTimings>();
+        let schedule_timings = timings.get(TestSchedule).unwrap();
+        assert_eq!(schedule_timings.runs(), 2);
+        let slow_time = schedule_timings.system(&name(slow)).unwrap();
+        assert!(slow_time >= Duration::from_millis(10));
+        assert_eq!(
+            schedule_timings.system(&name(skipped)),
+            Some(Duration::ZERO)
+        );
+        assert!(schedule_timings.total() >= slow_time);
+
+        let inner = schedule_timings.set(TestSet::Inner).unwrap();
+        let outer = schedule_timings.set(TestSet::Outer).unwrap();
+        assert_eq!(inner, slow_time);
+        assert!(outer >= inner);
+        assert_eq!(schedule_timings.sets().count(), 2);
+
+        assert_eq!(
+            timings.slowest_systems(1),
+            [(TestSchedule.intern(), &*name(slow), slow_time)]
+        );
+    }
+
+    #[test]
+    fn single_threaded() {
+        record(ExecutorKind::SingleThreaded);
+    }
+
+    #[test]
+    fn simple() {
+        record(ExecutorKind::Simple);
+    }
+
+    #[test]
+    fn multi_threaded() {
+        record(ExecutorKind::MultiThreaded);
+    }
+}
@@ -587,6 +587,29 @@ impl Table {
         }
     }
 
+    /// Removes the entity at the given row without dropping its components, and returns the entity
+    /// swapped in to replace it (if an entity was swapped in). It is the caller's responsibility to
+    /// drop the removed components. Failure to do so may result in resources not being released
+    /// (i.e. files handles not being released, memory leaks, etc.)
+    ///
+    /// # Safety
+    /// `row` must be in-bounds
+    pub(crate) unsafe fn swap_remove_and_forget_unchecked(
+        &mut self,
+        row: TableRow,
+    ) -> Option<Entity> {
+        for column in self.columns.values_mut() {
+            let _ = column.swap_remove_and_forget_unchecked(row);
+        }
+        let is_last = row.as_usize() == self.entities.len() - 1;
+        self.entities.swap_remove(row.as_usize());
+        if is_last {
+            None
+        } else {
+            Some(self.entities[row.as_usize()])
+        }
+    }
+
     /// Moves the `row` column values to `new_table`, for the columns shared between both tables.
     /// Returns the index of the new row in `new_table` and the entity in this table swapped in
     /// to replace it (if an entity was swapped in). missing columns will be "forgotten". It is
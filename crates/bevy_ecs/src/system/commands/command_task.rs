@@ -0,0 +1,403 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
+
+use crate::{
+    self as bevy_ecs,
+    change_detection::DetectChangesMut,
+    component::Component,
+    entity::Entity,
+    query::QueryState,
+    system::{Local, Resource},
+    world::{Command, CommandQueue, World},
+};
+
+use super::{Commands, EntityCommands};
+
+/// The state shared between a [`CommandTask`] and the future running on the task pool.
+#[derive(Default)]
+struct CommandTaskState {
+    /// The commands produced by the future, once it completed.
+    output: Option<CommandQueue>,
+    /// Set once the future has been dropped, whether it completed or not.
+    done: bool,
+    /// Set when the [`CommandTask`] is dropped.
+    cancelled: bool,
+    /// The waker of the future, used to wake it up when the task is cancelled.
+    waker: Option<Waker>,
+}
+
+/// A handle to a future spawned by [`Commands::spawn_task`] or [`EntityCommands::spawn_task`].
+///
+/// The future is cancelled when the handle is dropped.
+struct CommandTask {
+    state: Arc<Mutex<CommandTaskState>>,
+}
+
+impl CommandTask {
+    /// Spawns `future` on the [`AsyncComputeTaskPool`].
+    fn spawn<C: Command>(future: impl Future<Output = C> + Send + 'static) -> Self {
+        let (task, future) = Self::new(future);
+        AsyncComputeTaskPool::get_or_init(TaskPool::default)
+            .spawn(future)
+            .detach();
+        task
+    }
+
+    /// Creates a handle and the future that should be run to complete it.
+    fn new<C: Command, F: Future<Output = C>>(future: F) -> (Self, CancellableFuture<F>) {
+        let state = Arc::new(Mutex::new(CommandTaskState::default()));
+        let future = CancellableFuture {
+            future: Box::pin(future),
+            state: state.clone(),
+        };
+        (Self { state }, future)
+    }
+
+    /// Moves the commands produced by the future to `queue` if it completed.
+    ///
+    /// Returns `true` once the task is over, either because it completed or because its future
+    /// was dropped without completing (for example because it panicked).
+    fn take_output(&self, queue: &mut CommandQueue) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(mut output) = state.output.take() {
+            queue.append(&mut output);
+        }
+        state.done
+    }
+}
+
+impl Drop for CommandTask {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            state.cancelled = true;
+            state.output = None;
+            state.waker.take()
+        };
+        // Wake the future up so that the executor drops it.
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Wraps the future of a [`CommandTask`], stopping as soon as the task is cancelled.
+struct CancellableFuture<F> {
+    future: Pin<Box<F>>,
+    state: Arc<Mutex<CommandTaskState>>,
+}
+
+impl<C: Command, F: Future<Output = C>> Future for CancellableFuture<F> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            if state.cancelled {
+                return Poll::Ready(());
+            }
+            match &state.waker {
+                Some(waker) if waker.will_wake(cx.waker()) => {}
+                _ => state.waker = Some(cx.waker().clone()),
+            }
+        }
+
+        let Poll::Ready(command) = self.future.as_mut().poll(cx) else {
+            return Poll::Pending;
+        };
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if !state.cancelled {
+            let mut queue = CommandQueue::default();
+            queue.push(command);
+            state.output = Some(queue);
+        }
+        Poll::Ready(())
+    }
+}
+
+impl<F> Drop for CancellableFuture<F> {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.done = true;
+        state.waker = None;
+    }
+}
+
+/// The tasks spawned with [`Commands::spawn_task`] that haven't been applied yet.
+///
+/// Dropping this resource cancels the tasks.
+#[derive(Resource, Default)]
+pub struct CommandTasks(Vec<CommandTask>);
+
+impl CommandTasks {
+    /// Returns the number of tasks that haven't been applied yet.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no pending tasks.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn take_outputs(&mut self, queue: &mut CommandQueue) {
+        self.0.retain(|task| !task.take_output(queue));
+    }
+}
+
+/// The tasks spawned with [`EntityCommands::spawn_task`] that haven't been applied yet.
+///
+/// The tasks are cancelled when this component is removed, which includes despawning the entity.
+#[derive(Component, Default)]
+pub struct EntityCommandTasks(Vec<CommandTask>);
+
+impl EntityCommandTasks {
+    /// Returns the number of tasks that haven't been applied yet.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no pending tasks.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'w, 's> Commands<'w, 's> {
+    /// Spawns `future` on the [`AsyncComputeTaskPool`] and applies the [`Command`] it returns to
+    /// the world once it completes.
+    ///
+    /// The command is applied by [`apply_command_tasks`], which `bevy_core`'s `TaskPoolPlugin`
+    /// runs in the `PreUpdate` schedule.
+    /// Use [`EntityCommands::spawn_task`] to cancel the task when an entity is despawned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::world::CommandQueue;
+    /// #[derive(Resource)]
+    /// struct Level(String);
+    ///
+    /// fn load_level(mut commands: Commands) {
+    ///     commands.spawn_task(async move {
+    ///         let level = String::from("level 1");
+    ///         move |world: &mut World| world.insert_resource(Level(level))
+    ///     });
+    ///
+    ///     // A `CommandQueue` can be returned by wrapping it in a closure.
+    ///     commands.spawn_task(async move {
+    ///         let mut queue = CommandQueue::default();
+    ///         queue.push(|world: &mut World| {
+    ///             world.remove_resource::<Level>();
+    ///         });
+    ///         move |world: &mut World| queue.apply(world)
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(load_level);
+    /// ```
+    pub fn spawn_task<C: Command>(&mut self, future: impl Future<Output = C> + Send + 'static) {
+        let task = CommandTask::spawn(future);
+        self.add(move |world: &mut World| {
+            world
+                .get_resource_or_insert_with(CommandTasks::default)
+                .0
+                .push(task);
+        });
+    }
+}
+
+impl EntityCommands<'_> {
+    /// Spawns `future` on the [`AsyncComputeTaskPool`] and applies the [`Command`] it returns to
+    /// the world once it completes, unless the entity has been despawned by then.
+    ///
+    /// The task is stored in the [`EntityCommandTasks`] component of the entity: despawning the
+    /// entity or removing that component cancels it.
+    /// See [`Commands::spawn_task`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Path(Vec<(i32, i32)>);
+    ///
+    /// fn find_path(mut commands: Commands, query: Query<Entity, Without<Path>>) {
+    ///     for entity in &query {
+    ///         commands.entity(entity).spawn_task(async move {
+    ///             let path = Path(vec![(0, 0), (1, 1)]);
+    ///             move |world: &mut World| {
+    ///                 world.entity_mut(entity).insert(path);
+    ///             }
+    ///         });
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(find_path);
+    /// ```
+    pub fn spawn_task<C: Command>(
+        &mut self,
+        future: impl Future<Output = C> + Send + 'static,
+    ) -> &mut Self {
+        let task = CommandTask::spawn(future);
+        self.add(move |entity: Entity, world: &mut World| {
+            let Some(mut entity) = world.get_entity_mut(entity) else {
+                return;
+            };
+            if let Some(mut tasks) = entity.get_mut::<EntityCommandTasks>() {
+                tasks.0.push(task);
+            } else {
+                entity.insert(EntityCommandTasks(vec![task]));
+            }
+        })
+    }
+}
+
+/// Applies the commands returned by the tasks spawned with [`Commands::spawn_task`] and
+/// [`EntityCommands::spawn_task`] that completed.
+///
+/// Commands returned by tasks of the [`CommandTasks`] resource are applied first, then those of the
+/// [`EntityCommandTasks`] components.
+pub fn apply_command_tasks(
+    world: &mut World,
+    mut entity_tasks: Local<QueryState<(Entity, &mut EntityCommandTasks)>>,
+) {
+    let mut queue = CommandQueue::default();
+    if let Some(mut tasks) = world.get_resource_mut::<CommandTasks>() {
+        tasks.bypass_change_detection().take_outputs(&mut queue);
+    }
+
+    let mut finished = Vec::new();
+    for (entity, mut tasks) in entity_tasks.iter_mut(world) {
+        let tasks = tasks.bypass_change_detection();
+        for task in std::mem::take(&mut tasks.0) {
+            if !task.take_output(&mut queue) {
+                tasks.0.push(task);
+            }
+        }
+        if tasks.0.is_empty() {
+            finished.push(entity);
+        }
+    }
+    for entity in finished {
+        world.entity_mut(entity).remove::<EntityCommandTasks>();
+    }
+
+    queue.apply(world);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::{pending, ready},
+        sync::atomic::{AtomicBool, Ordering},
+        time::{Duration, Instant},
+    };
+
+    use bevy_tasks::{block_on, poll_once};
+
+    use super::*;
+    use crate::system::RunSystemOnce;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    fn increment(world: &mut World) {
+        world.resource_mut::<Counter>().0 += 1;
+    }
+
+    /// Runs [`apply_command_tasks`] until `done` returns `true`.
+    fn apply_until(world: &mut World, done: impl Fn(&World) -> bool) {
+        let start = Instant::now();
+        while !done(world) {
+            assert!(start.elapsed() < Duration::from_secs(10), "Timed out");
+            world.run_system_once(apply_command_tasks);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn spawn_task() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.spawn_task(async { increment });
+        commands.spawn_task(async { increment });
+        queue.apply(&mut world);
+
+        apply_until(&mut world, |world| world.resource::<Counter>().0 == 2);
+        assert!(world.resource::<CommandTasks>().is_empty());
+    }
+
+    #[test]
+    fn spawn_entity_task() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let kept = world.spawn_empty().id();
+        let despawned = world.spawn_empty().id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(kept).spawn_task(async { increment });
+        commands.entity(despawned).spawn_task(async { increment });
+        queue.apply(&mut world);
+        assert!(world.get::<EntityCommandTasks>(kept).is_some());
+
+        world.despawn(despawned);
+        apply_until(&mut world, |world| world.resource::<Counter>().0 == 1);
+        assert!(world.get::<EntityCommandTasks>(kept).is_none());
+
+        // The task of the despawned entity is never applied.
+        world.run_system_once(apply_command_tasks);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+
+    #[test]
+    fn cancel_task() {
+        struct SetOnDrop(Arc<AtomicBool>);
+
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::Relaxed);
+            }
+        }
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+        let (task, mut future) = CommandTask::new(async move {
+            let _guard = guard;
+            pending::<fn(&mut World)>().await
+        });
+
+        assert!(block_on(poll_once(&mut future)).is_none());
+        assert!(!dropped.load(Ordering::Relaxed));
+
+        drop(task);
+        assert!(block_on(poll_once(&mut future)).is_some());
+        drop(future);
+        assert!(dropped.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn completed_task_output() {
+        let (task, future) = CommandTask::new(ready(increment));
+        let mut queue = CommandQueue::default();
+        assert!(!task.take_output(&mut queue));
+        assert!(queue.is_empty());
+
+        block_on(future);
+        assert!(task.take_output(&mut queue));
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        queue.apply(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+}
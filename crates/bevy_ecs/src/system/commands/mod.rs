@@ -1,3 +1,4 @@
+mod command_task;
 mod parallel_scope;
 
 use super::{Deferred, IntoObserverSystem, IntoSystem, RegisterSystem, Resource};
@@ -13,6 +14,7 @@ use crate::{
     world::{Command, CommandQueue, EntityWorldMut, FromWorld, World},
 };
 use bevy_utils::tracing::{error, info};
+pub use command_task::*;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
 
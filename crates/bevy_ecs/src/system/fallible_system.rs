@@ -0,0 +1,193 @@
+use std::borrow::Cow;
+
+use super::{ReadOnlySystem, System};
+use crate::{
+    archetype::ArchetypeComponentId,
+    component::{ComponentId, Tick},
+    error::{error_handler, Error, ErrorHandler, SystemErrorContext, SystemErrorHandler},
+    query::Access,
+    schedule::InternedSystemSet,
+    system::BoxedSystem,
+    world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
+};
+
+/// The output of a system that can be added to a schedule: `()`, or a [`Result<(), E>`](Result)
+/// for fallible systems, which are wrapped in a [`FallibleSystem`].
+///
+/// This trait is sealed and can't be implemented for other types.
+pub trait SystemOutput: sealed::SystemOutput + Sized + 'static {
+    /// Boxes `system` so that it can be run by a schedule.
+    fn into_boxed_system<S: System<In = (), Out = Self>>(system: S) -> BoxedSystem;
+}
+
+impl SystemOutput for () {
+    fn into_boxed_system<S: System<In = (), Out = Self>>(system: S) -> BoxedSystem {
+        Box::new(system)
+    }
+}
+
+impl<E: Into<Error> + 'static> SystemOutput for Result<(), E> {
+    fn into_boxed_system<S: System<In = (), Out = Self>>(system: S) -> BoxedSystem {
+        Box::new(FallibleSystem::new(system))
+    }
+}
+
+mod sealed {
+    pub trait SystemOutput {}
+
+    impl SystemOutput for () {}
+
+    impl<E> SystemOutput for Result<(), E> {}
+}
+
+/// A [`System`] that runs a system returning [`Result<(), E>`](Result) and hands any error to
+/// the world's [`SystemErrorHandler`].
+///
+/// Errors are handled as soon as the system returns them, so the system reads the
+/// [`SystemErrorHandler`] resource in addition to the access of the wrapped system.
+/// Systems returning a `Result` are wrapped in this automatically when added to a schedule.
+///
+/// See the [`error`](crate::error) module for more information.
+pub struct FallibleSystem<S> {
+    system: S,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl<S> FallibleSystem<S> {
+    /// Wraps `system` so that its errors are passed to the [`SystemErrorHandler`].
+    pub const fn new(system: S) -> Self {
+        Self {
+            system,
+            component_access: Access::new(),
+            archetype_component_access: Access::new(),
+        }
+    }
+}
+
+impl<S, E> FallibleSystem<S>
+where
+    S: System<In = (), Out = Result<(), E>>,
+    E: Into<Error> + 'static,
+{
+    fn handle_error(&self, error: E, error_handler: Option<&SystemErrorHandler>) {
+        let handler =
+            error_handler.map_or(error_handler::panic as ErrorHandler, |handler| handler.0);
+        handler(
+            error.into(),
+            SystemErrorContext {
+                name: self.system.name(),
+                last_run: self.system.get_last_run(),
+            },
+        );
+    }
+}
+
+impl<S, E> System for FallibleSystem<S>
+where
+    S: System<In = (), Out = Result<(), E>>,
+    E: Into<Error> + 'static,
+{
+    type In = ();
+    type Out = ();
+
+    fn name(&self) -> Cow<'static, str> {
+        self.system.name()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    #[inline]
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    fn is_exclusive(&self) -> bool {
+        self.system.is_exclusive()
+    }
+
+    fn has_deferred(&self) -> bool {
+        self.system.has_deferred()
+    }
+
+    #[inline]
+    unsafe fn run_unsafe(&mut self, input: (), world: UnsafeWorldCell) {
+        // SAFETY: `system.run_unsafe` has the same invariants as `self.run_unsafe`.
+        if let Err(error) = unsafe { self.system.run_unsafe(input, world) } {
+            // SAFETY: The read access to the resource was registered in `initialize`.
+            self.handle_error(error, unsafe { world.get_resource() });
+        }
+    }
+
+    #[inline]
+    fn run(&mut self, input: (), world: &mut World) {
+        if let Err(error) = self.system.run(input, world) {
+            self.handle_error(error, world.get_resource());
+        }
+    }
+
+    #[inline]
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.system.apply_deferred(world);
+    }
+
+    #[inline]
+    fn queue_deferred(&mut self, world: DeferredWorld) {
+        self.system.queue_deferred(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+        let id = world.components.init_resource::<SystemErrorHandler>();
+        world.initialize_resource_internal(id);
+        assert!(
+            !self.system.component_access().has_write(id),
+            "Fallible system {} conflicts with its read of the SystemErrorHandler resource.",
+            self.system.name(),
+        );
+        self.component_access.extend(self.system.component_access());
+        self.component_access.add_read(id);
+        let archetype_component_id = world.get_resource_archetype_component_id(id).unwrap();
+        self.archetype_component_access
+            .extend(self.system.archetype_component_access());
+        self.archetype_component_access
+            .add_read(archetype_component_id);
+    }
+
+    #[inline]
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        self.system.update_archetype_component_access(world);
+        self.archetype_component_access
+            .extend(self.system.archetype_component_access());
+    }
+
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.system.check_change_tick(change_tick);
+    }
+
+    fn default_system_sets(&self) -> Vec<InternedSystemSet> {
+        self.system.default_system_sets()
+    }
+
+    fn get_last_run(&self) -> Tick {
+        self.system.get_last_run()
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system.set_last_run(last_run);
+    }
+}
+
+// SAFETY: The inner system is read-only.
+unsafe impl<S, E> ReadOnlySystem for FallibleSystem<S>
+where
+    S: ReadOnlySystem<In = (), Out = Result<(), E>>,
+    E: Into<Error> + 'static,
+{
+}
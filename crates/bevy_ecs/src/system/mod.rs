@@ -107,6 +107,7 @@ mod combinator;
 mod commands;
 mod exclusive_function_system;
 mod exclusive_system_param;
+mod fallible_system;
 mod function_system;
 mod observer_system;
 mod query;
@@ -124,6 +125,7 @@ pub use combinator::*;
 pub use commands::*;
 pub use exclusive_function_system::*;
 pub use exclusive_system_param::*;
+pub use fallible_system::*;
 pub use function_system::*;
 pub use observer_system::*;
 pub use query::*;
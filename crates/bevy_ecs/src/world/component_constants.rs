@@ -7,8 +7,10 @@ use crate::{self as bevy_ecs};
 pub const ON_ADD: ComponentId = ComponentId::new(0);
 /// [`ComponentId`] for [`OnInsert`]
 pub const ON_INSERT: ComponentId = ComponentId::new(1);
+/// [`ComponentId`] for [`OnReplace`]
+pub const ON_REPLACE: ComponentId = ComponentId::new(2);
 /// [`ComponentId`] for [`OnRemove`]
-pub const ON_REMOVE: ComponentId = ComponentId::new(2);
+pub const ON_REMOVE: ComponentId = ComponentId::new(3);
 
 /// Trigger emitted when a component is added to an entity.
 #[derive(Event)]
@@ -18,6 +20,12 @@ pub struct OnAdd;
 #[derive(Event)]
 pub struct OnInsert;
 
+/// Trigger emitted when a component is replaced on an entity, or is about to be removed from it.
+///
+/// The previous value of the component is still accessible when this trigger runs.
+#[derive(Event)]
+pub struct OnReplace;
+
 /// Trigger emitted when a component is removed from an entity.
 #[derive(Event)]
 pub struct OnRemove;
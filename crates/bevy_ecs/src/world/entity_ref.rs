@@ -10,13 +10,19 @@ use crate::{
     removal_detection::RemovedComponentEvents,
     storage::Storages,
     system::IntoObserverSystem,
-    world::{DeferredWorld, Mut, World},
+    world::{error::TransferEntityError, DeferredWorld, Mut, World},
 };
 use bevy_ptr::{OwningPtr, Ptr};
-use std::{any::TypeId, marker::PhantomData};
+use std::{
+    alloc::{handle_alloc_error, Layout},
+    any::TypeId,
+    marker::PhantomData,
+    num::NonZeroUsize,
+    ptr::NonNull,
+};
 use thiserror::Error;
 
-use super::{unsafe_world_cell::UnsafeEntityCell, Ref, ON_REMOVE};
+use super::{unsafe_world_cell::UnsafeEntityCell, Ref, ON_REMOVE, ON_REPLACE};
 
 /// A read-only reference to a particular [`Entity`] and all of its components.
 ///
@@ -1209,6 +1215,57 @@ impl<'w> EntityWorldMut<'w> {
     ///
     /// See [`World::despawn`] for more details.
     pub fn despawn(self) {
+        self.despawn_inner::<true>(|_| {});
+    }
+
+    /// Moves the current entity and all of its components into `other`, returning the entity's
+    /// id in `other`.
+    ///
+    /// See [`World::transfer_entity`] for more details.
+    pub fn transfer(self, other: &mut World) -> Result<Entity, TransferEntityError> {
+        let entity = self.entity;
+        self.world.check_transferable(entity)?;
+
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        let mut component_ids = Vec::with_capacity(archetype.component_count());
+        let mut other_component_ids = Vec::with_capacity(archetype.component_count());
+        let mut layouts = Vec::with_capacity(archetype.component_count());
+        for component_id in archetype.components() {
+            // SAFETY: All components in the archetype exist in world
+            let info = unsafe { self.world.components.get_info_unchecked(component_id) };
+            let other_component_id = other
+                .components
+                .init_component_from_info(&mut other.storages, info)
+                .expect("components were checked to correspond to Rust types");
+            component_ids.push(component_id);
+            other_component_ids.push(other_component_id);
+            layouts.push(info.layout());
+        }
+
+        let mut moved = MovedComponents::new(&layouts);
+        self.despawn_inner::<false>(|world| {
+            let source = world.entity(entity);
+            for (index, &component_id) in component_ids.iter().enumerate() {
+                // SAFETY: The value has the layout it was allocated for, and its original is
+                // forgotten when the entity is despawned.
+                unsafe { moved.write(index, source.get_by_id(component_id).unwrap()) };
+            }
+        });
+
+        let mut destination = other.spawn_empty();
+        // SAFETY:
+        // - The component ids were initialized in `other`, from the infos of the moved values
+        // - Each value's type matches the type of its component in `other`, as they share a `TypeId`
+        unsafe { destination.insert_by_ids(&other_component_ids, moved.iter()) };
+        Ok(destination.flush())
+    }
+
+    /// Despawns the current entity. `before_remove` is called once the entity's hooks and
+    /// observers have run, right before its components are removed from storage.
+    ///
+    /// When `DROP` is false, the components are forgotten instead of dropped, and
+    /// `before_remove` is responsible for moving them out of the world.
+    fn despawn_inner<const DROP: bool>(self, before_remove: impl FnOnce(&World)) {
         let world = self.world;
         world.flush_entities();
         let archetype = &world.archetypes[self.location.archetype_id];
@@ -1222,6 +1279,10 @@ impl<'w> EntityWorldMut<'w> {
 
         // SAFETY: All components in the archetype exist in world
         unsafe {
+            deferred_world.trigger_on_replace(archetype, self.entity, archetype.components());
+            if archetype.has_replace_observer() {
+                deferred_world.trigger_observers(ON_REPLACE, self.entity, archetype.components());
+            }
             deferred_world.trigger_on_remove(archetype, self.entity, archetype.components());
             if archetype.has_remove_observer() {
                 deferred_world.trigger_observers(ON_REMOVE, self.entity, archetype.components());
@@ -1232,6 +1293,8 @@ impl<'w> EntityWorldMut<'w> {
             world.removed_components.send(component_id, self.entity);
         }
 
+        before_remove(world);
+
         let location = world
             .entities
             .free(self.entity)
@@ -1262,11 +1325,20 @@ impl<'w> EntityWorldMut<'w> {
 
             for component_id in archetype.sparse_set_components() {
                 let sparse_set = world.storages.sparse_sets.get_mut(component_id).unwrap();
-                sparse_set.remove(self.entity);
+                if DROP {
+                    sparse_set.remove(self.entity);
+                } else {
+                    let _ = sparse_set.remove_and_forget(self.entity);
+                }
             }
+            let table = &mut world.storages.tables[archetype.table_id()];
             // SAFETY: table rows stored in archetypes always exist
             moved_entity = unsafe {
-                world.storages.tables[archetype.table_id()].swap_remove_unchecked(table_row)
+                if DROP {
+                    table.swap_remove_unchecked(table_row)
+                } else {
+                    table.swap_remove_and_forget_unchecked(table_row)
+                }
             };
         };
 
@@ -1429,6 +1501,10 @@ unsafe fn trigger_on_remove_hooks_and_observers(
     entity: Entity,
     bundle_info: &BundleInfo,
 ) {
+    deferred_world.trigger_on_replace(archetype, entity, bundle_info.iter_components());
+    if archetype.has_replace_observer() {
+        deferred_world.trigger_observers(ON_REPLACE, entity, bundle_info.iter_components());
+    }
     deferred_world.trigger_on_remove(archetype, entity, bundle_info.iter_components());
     if archetype.has_remove_observer() {
         deferred_world.trigger_observers(ON_REMOVE, entity, bundle_info.iter_components());
@@ -2435,6 +2511,78 @@ fn sorted_remove<T: Eq + Ord + Copy>(source: &mut Vec<T>, remove: &[T]) {
     });
 }
 
+/// Component values moved out of a world's storage by [`EntityWorldMut::transfer`], packed into a
+/// single allocation until they are inserted into another world.
+///
+/// Dropping this frees the allocation without dropping the values, so values that were never
+/// inserted are leaked rather than dropped twice.
+struct MovedComponents {
+    data: NonNull<u8>,
+    layout: Layout,
+    /// The offset and size of each value in `data`.
+    values: Vec<(usize, usize)>,
+}
+
+impl MovedComponents {
+    fn new(layouts: &[Layout]) -> Self {
+        let mut layout = Layout::new::<()>();
+        let values = layouts
+            .iter()
+            .map(|&value_layout| {
+                let (extended, offset) = layout
+                    .extend(value_layout)
+                    .expect("moved components should fit in memory");
+                layout = extended;
+                (offset, value_layout.size())
+            })
+            .collect();
+
+        let data = if layout.size() == 0 {
+            // SAFETY: Alignment is always non-zero
+            bevy_ptr::dangling_with_align(unsafe { NonZeroUsize::new_unchecked(layout.align()) })
+        } else {
+            // SAFETY: `layout` has a non-zero size
+            let data = unsafe { std::alloc::alloc(layout) };
+            NonNull::new(data).unwrap_or_else(|| handle_alloc_error(layout))
+        };
+
+        Self {
+            data,
+            layout,
+            values,
+        }
+    }
+
+    /// Copies `value` into the slot at `index`.
+    ///
+    /// # Safety
+    /// `value` must have the layout the slot at `index` was allocated for.
+    unsafe fn write(&mut self, index: usize, value: Ptr<'_>) {
+        let (offset, size) = self.values[index];
+        std::ptr::copy_nonoverlapping(value.as_ptr(), self.data.as_ptr().add(offset), size);
+    }
+
+    /// Returns the moved values, in the order of the layouts passed to [`MovedComponents::new`].
+    ///
+    /// # Safety
+    /// Every slot must have been written to, and the values must not be read again afterwards.
+    unsafe fn iter(&mut self) -> impl Iterator<Item = OwningPtr<'_>> {
+        let data = self.data;
+        self.values.iter().map(move |&(offset, _)| {
+            OwningPtr::new(NonNull::new_unchecked(data.as_ptr().add(offset)))
+        })
+    }
+}
+
+impl Drop for MovedComponents {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            // SAFETY: `data` was allocated with `layout`
+            unsafe { std::alloc::dealloc(self.data.as_ptr(), self.layout) };
+        }
+    }
+}
+
 /// Moves component data out of storage.
 ///
 /// This function leaves the underlying memory unchanged, but the component behind
@@ -1,8 +1,8 @@
-//! Contains error types returned by bevy's schedule.
+//! Contains error types returned by bevy's schedule and [`World`](crate::world::World).
 
 use thiserror::Error;
 
-use crate::schedule::InternedScheduleLabel;
+use crate::{entity::Entity, schedule::InternedScheduleLabel};
 
 /// The error type returned by [`World::try_run_schedule`] if the provided schedule does not exist.
 ///
@@ -10,3 +10,21 @@ use crate::schedule::InternedScheduleLabel;
 #[derive(Error, Debug)]
 #[error("The schedule with the label {0:?} was not found.")]
 pub struct TryRunScheduleError(pub InternedScheduleLabel);
+
+/// An error that occurs when moving an entity to another [`World`](crate::world::World) with
+/// [`World::transfer_entity`](crate::world::World::transfer_entity).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TransferEntityError {
+    /// The entity does not exist.
+    #[error("The entity {0:?} does not exist.")]
+    NoSuchEntity(Entity),
+    /// The entity has a component that does not correspond to a Rust type, so it cannot be
+    /// matched with a component of the other world.
+    #[error("The entity {entity:?} has the component {component}, which does not correspond to a Rust type.")]
+    UntypedComponent {
+        /// The entity that could not be moved.
+        entity: Entity,
+        /// The name of the component.
+        component: String,
+    },
+}
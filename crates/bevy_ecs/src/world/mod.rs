@@ -6,6 +6,7 @@ mod deferred_world;
 mod entity_ref;
 pub mod error;
 mod identifier;
+mod snapshot;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 
@@ -20,6 +21,7 @@ pub use entity_ref::{
     OccupiedEntry, VacantEntry,
 };
 pub use identifier::WorldId;
+pub use snapshot::{SnapshotRegistry, WorldSnapshot};
 pub use spawn_batch::*;
 
 use crate::{
@@ -30,7 +32,9 @@ use crate::{
         Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentInfo, ComponentTicks,
         Components, Tick,
     },
-    entity::{AllocAtWithoutReplacement, Entities, Entity, EntityHashSet, EntityLocation},
+    entity::{
+        AllocAtWithoutReplacement, Entities, Entity, EntityHashMap, EntityHashSet, EntityLocation,
+    },
     event::{Event, EventId, Events, SendBatchIds},
     observer::Observers,
     query::{DebugCheckedUnwrap, QueryData, QueryEntityError, QueryFilter, QueryState},
@@ -39,7 +43,7 @@ use crate::{
     storage::{ResourceData, Storages},
     system::{Commands, Res, Resource},
     world::command_queue::RawCommandQueue,
-    world::error::TryRunScheduleError,
+    world::error::{TransferEntityError, TryRunScheduleError},
 };
 use bevy_ptr::{OwningPtr, Ptr};
 use bevy_utils::tracing::warn;
@@ -164,6 +168,7 @@ impl World {
     fn bootstrap(&mut self) {
         assert_eq!(ON_ADD, self.init_component::<OnAdd>());
         assert_eq!(ON_INSERT, self.init_component::<OnInsert>());
+        assert_eq!(ON_REPLACE, self.init_component::<OnReplace>());
         assert_eq!(ON_REMOVE, self.init_component::<OnRemove>());
     }
     /// Creates a new empty [`World`].
@@ -1110,6 +1115,105 @@ impl World {
         }
     }
 
+    /// Moves `entity` and all of its components from this world into `other`, returning the
+    /// entity's id in `other`.
+    ///
+    /// The component values are moved as they are, without being cloned or going through
+    /// reflection. Components are matched between the worlds by their [`TypeId`]: those that
+    /// are not registered in `other` yet are registered with the same storage type and hooks as in
+    /// this world. This triggers the `on_replace` and `on_remove` hooks and observers in this
+    /// world, and the `on_add` and `on_insert` ones in `other`.
+    ///
+    /// Any [`Entity`] stored inside the components still refers to this world. Use
+    /// [`transfer_entities`](World::transfer_entities) and [`MapEntities`](crate::entity::MapEntities)
+    /// to move groups of entities that refer to each other.
+    ///
+    /// Returns an error, and leaves both worlds untouched, if the entity does not exist or if it
+    /// has a component that does not correspond to a Rust type.
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// #[derive(Component, PartialEq, Debug)]
+    /// struct Level(u32);
+    ///
+    /// let mut staging = World::new();
+    /// let entity = staging.spawn(Level(3)).id();
+    ///
+    /// let mut world = World::new();
+    /// let moved = staging.transfer_entity(entity, &mut world).unwrap();
+    /// assert!(staging.get_entity(entity).is_none());
+    /// assert_eq!(world.get::<Level>(moved), Some(&Level(3)));
+    /// ```
+    pub fn transfer_entity(
+        &mut self,
+        entity: Entity,
+        other: &mut World,
+    ) -> Result<Entity, TransferEntityError> {
+        self.flush();
+        self.get_entity_mut(entity)
+            .ok_or(TransferEntityError::NoSuchEntity(entity))?
+            .transfer(other)
+    }
+
+    /// Moves each of `entities` and all of their components from this world into `other`,
+    /// returning a map from their ids in this world to their ids in `other`.
+    ///
+    /// All entities are checked before any of them is moved, so that an invalid entity leaves both
+    /// worlds untouched. Hooks and observers that run as the entities are removed from this world
+    /// may still change the entities that follow: the ones they despawn are skipped and missing
+    /// from the map, and the ones they make impossible to move return an error once the entities
+    /// before them have been moved.
+    ///
+    /// See [`transfer_entity`](World::transfer_entity) for more details.
+    pub fn transfer_entities(
+        &mut self,
+        entities: impl IntoIterator<Item = Entity>,
+        other: &mut World,
+    ) -> Result<EntityHashMap<Entity>, TransferEntityError> {
+        self.flush();
+        let entities: Vec<Entity> = entities.into_iter().collect();
+        for &entity in &entities {
+            self.check_transferable(entity)?;
+        }
+
+        let mut entity_map = EntityHashMap::default();
+        for entity in entities {
+            if entity_map.contains_key(&entity) {
+                continue;
+            }
+            match self.transfer_entity(entity, other) {
+                Ok(moved) => {
+                    entity_map.insert(entity, moved);
+                }
+                // Despawned by the hooks of a previous entity.
+                Err(TransferEntityError::NoSuchEntity(_)) => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(entity_map)
+    }
+
+    /// Returns an error if `entity` can't be moved to another world with
+    /// [`transfer_entity`](World::transfer_entity).
+    pub(crate) fn check_transferable(&self, entity: Entity) -> Result<(), TransferEntityError> {
+        let location = self
+            .entities
+            .get(entity)
+            .ok_or(TransferEntityError::NoSuchEntity(entity))?;
+        for component_id in self.archetypes[location.archetype_id].components() {
+            // SAFETY: All components in the archetype exist in world
+            let info = unsafe { self.components.get_info_unchecked(component_id) };
+            if info.type_id().is_none() {
+                return Err(TransferEntityError::UntypedComponent {
+                    entity,
+                    component: info.name().to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Clears the internal component tracker state.
     ///
     /// The world maintains some internal state about changed and removed components. This state
@@ -2812,6 +2916,7 @@ mod tests {
     use crate::{
         change_detection::DetectChangesMut,
         component::{ComponentDescriptor, ComponentInfo, StorageType},
+        entity::Entity,
         ptr::OwningPtr,
         system::Resource,
     };
@@ -3332,4 +3437,183 @@ mod tests {
         ])
         .is_err());
     }
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Shared(Arc<u32>);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct Sparse(Vec<u8>);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Marker;
+
+    #[test]
+    fn transfer_entity() {
+        let value = Arc::new(7);
+        let mut source = World::new();
+        let kept = source.spawn(Shared(value.clone())).id();
+        let entity = source
+            .spawn((Shared(value.clone()), Sparse(vec![1, 2]), Marker))
+            .id();
+
+        let mut destination = World::new();
+        destination.spawn(Marker);
+        let moved = source.transfer_entity(entity, &mut destination).unwrap();
+
+        assert!(source.get_entity(entity).is_none());
+        assert_eq!(source.get::<Shared>(kept), Some(&Shared(value.clone())));
+        assert_eq!(source.get::<Sparse>(kept), None);
+        assert_eq!(
+            destination.get::<Shared>(moved),
+            Some(&Shared(value.clone()))
+        );
+        assert_eq!(destination.get::<Sparse>(moved), Some(&Sparse(vec![1, 2])));
+        assert!(destination.entity(moved).contains::<Marker>());
+
+        // The moved value is neither cloned, leaked nor dropped twice.
+        assert_eq!(Arc::strong_count(&value), 3);
+        drop(source);
+        assert_eq!(Arc::strong_count(&value), 2);
+        drop(destination);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn transfer_entity_runs_hooks() {
+        #[derive(Resource, Default)]
+        struct Log(Vec<&'static str>);
+
+        let mut source = World::new();
+        source.init_resource::<Log>();
+        source
+            .register_component_hooks::<Marker>()
+            .on_remove(|mut world, _, _| world.resource_mut::<Log>().0.push("remove"));
+        let entity = source.spawn(Marker).id();
+
+        let mut destination = World::new();
+        destination.init_resource::<Log>();
+        destination
+            .register_component_hooks::<Marker>()
+            .on_add(|mut world, _, _| world.resource_mut::<Log>().0.push("add"));
+        source.transfer_entity(entity, &mut destination).unwrap();
+
+        assert_eq!(source.resource::<Log>().0, vec!["remove"]);
+        assert_eq!(destination.resource::<Log>().0, vec!["add"]);
+    }
+
+    #[test]
+    fn transfer_entities() {
+        let mut source = World::new();
+        let a = source.spawn(Sparse(vec![1])).id();
+        let b = source.spawn(Sparse(vec![2])).id();
+
+        let mut destination = World::new();
+        let entity_map = source
+            .transfer_entities([a, b, a], &mut destination)
+            .unwrap();
+        assert_eq!(entity_map.len(), 2);
+        assert_eq!(
+            destination.get::<Sparse>(entity_map[&a]),
+            Some(&Sparse(vec![1]))
+        );
+        assert_eq!(
+            destination.get::<Sparse>(entity_map[&b]),
+            Some(&Sparse(vec![2]))
+        );
+        assert_eq!(source.entities().len(), 0);
+    }
+
+    #[test]
+    fn transfer_entities_despawned_by_hooks() {
+        #[derive(Component)]
+        struct Linked(Entity);
+
+        let mut source = World::new();
+        source
+            .register_component_hooks::<Linked>()
+            .on_remove(|mut world, entity, _| {
+                let linked = world.get::<Linked>(entity).unwrap().0;
+                world.commands().entity(linked).despawn();
+            });
+        let b = source.spawn(Marker).id();
+        let a = source.spawn(Linked(b)).id();
+
+        let mut destination = World::new();
+        let entity_map = source.transfer_entities([a, b], &mut destination).unwrap();
+        assert_eq!(entity_map.len(), 1);
+        assert!(destination.get::<Linked>(entity_map[&a]).is_some());
+        assert_eq!(source.entities().len(), 0);
+        assert_eq!(destination.entities().len(), 1);
+    }
+
+    #[test]
+    fn transfer_entity_keeps_component_type_hooks_only() {
+        use crate::component::{Component, ComponentHooks};
+
+        #[derive(Resource, Default)]
+        struct Count(u32);
+
+        struct Hooked;
+
+        impl Component for Hooked {
+            const STORAGE_TYPE: StorageType = StorageType::Table;
+
+            fn register_component_hooks(hooks: &mut ComponentHooks) {
+                hooks.on_add(|mut world, _, _| world.resource_mut::<Count>().0 += 1);
+            }
+        }
+
+        let mut source = World::new();
+        source.init_resource::<Count>();
+        // Hooks registered at runtime may rely on resources only this world has.
+        source
+            .register_component_hooks::<Marker>()
+            .on_add(|mut world, _, _| world.resource_mut::<Count>().0 += 1);
+        let entity = source.spawn((Hooked, Marker)).id();
+
+        let mut destination = World::new();
+        destination.init_resource::<Count>();
+        source.transfer_entity(entity, &mut destination).unwrap();
+        assert_eq!(destination.resource::<Count>().0, 1);
+    }
+
+    #[test]
+    fn transfer_untyped_component() {
+        let mut source = World::new();
+        // SAFETY: `u32` has no drop function and is `Send + Sync`.
+        let descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                "Untyped".to_string(),
+                StorageType::Table,
+                std::alloc::Layout::new::<u32>(),
+                None,
+            )
+        };
+        let component_id = source.init_component_with_descriptor(descriptor);
+        let typed = source.spawn(Marker).id();
+        let mut untyped = source.spawn_empty();
+        OwningPtr::make(5u32, |ptr| {
+            // SAFETY: `ptr` points to a `u32`, which matches the component's layout.
+            unsafe { untyped.insert_by_id(component_id, ptr) };
+        });
+        let untyped = untyped.id();
+
+        let mut destination = World::new();
+        assert_eq!(
+            source.transfer_entities([typed, untyped], &mut destination),
+            Err(super::TransferEntityError::UntypedComponent {
+                entity: untyped,
+                component: "Untyped".to_string(),
+            })
+        );
+        assert!(source.get_entity(typed).is_some());
+        assert_eq!(destination.entities().len(), 0);
+        assert_eq!(
+            source.transfer_entity(Entity::from_raw(42), &mut destination),
+            Err(super::TransferEntityError::NoSuchEntity(Entity::from_raw(
+                42
+            )))
+        );
+    }
 }
@@ -0,0 +1,493 @@
+use std::{any::Any, marker::PhantomData, sync::Arc};
+
+use crate as bevy_ecs;
+use crate::{
+    component::{Component, ComponentId},
+    entity::{EntitiesSnapshot, Entity, EntityHashSet},
+    system::Resource,
+    world::World,
+};
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    crate::reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    bevy_reflect::{Reflect, TypeRegistration},
+    std::any::TypeId,
+};
+
+/// The type-erased data captured for a single registered component or resource type.
+type SnapshotData = Box<dyn Any + Send + Sync>;
+
+/// Captures and restores the values of one component or resource type.
+trait SnapshotFns: Send + Sync + 'static {
+    fn capture(&self, world: &World) -> SnapshotData;
+
+    fn restore(&self, world: &mut World, data: &SnapshotData);
+}
+
+/// The set of components and resources captured by [`WorldSnapshot`]s, used for rollback.
+///
+/// A snapshot always covers all of the entities of the [`World`] and the state of its entity
+/// allocator, but only the values of the registered component and resource types.
+/// Restoring a snapshot:
+/// - despawns the entities that were spawned since the snapshot was taken,
+/// - respawns the entities that were despawned since then, with the same [`Entity`] ids,
+/// - overwrites, inserts or removes the registered components and resources so that they match the snapshot,
+/// - restores the entity allocator, so that the entities spawned afterwards get the same ids as
+///   they did after the snapshot was taken.
+///
+/// Components and resources that aren't registered are left untouched, except on the despawned entities.
+/// Hooks and observers run for the components that are inserted or removed, and change detection
+/// is triggered for the restored values.
+/// Note that the order in which queries iterate over the entities may differ from the one at the
+/// time the snapshot was taken, as entities may have moved in their tables.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::world::SnapshotRegistry;
+/// #[derive(Component, Clone, PartialEq, Debug)]
+/// struct Position(i32);
+///
+/// let mut registry = SnapshotRegistry::default();
+/// registry.register_component::<Position>();
+///
+/// let mut world = World::new();
+/// let player = world.spawn(Position(0)).id();
+/// let snapshot = registry.capture(&mut world);
+///
+/// world.get_mut::<Position>(player).unwrap().0 = 10;
+/// let bullet = world.spawn(Position(5)).id();
+///
+/// registry.restore(&mut world, &snapshot);
+/// assert_eq!(world.get::<Position>(player), Some(&Position(0)));
+/// assert!(world.get_entity(bullet).is_none());
+///
+/// // Entities are allocated just like they were after the snapshot was taken.
+/// assert_eq!(world.spawn(Position(5)).id(), bullet);
+/// ```
+#[derive(Resource, Clone, Default)]
+pub struct SnapshotRegistry {
+    fns: Vec<Arc<dyn SnapshotFns>>,
+}
+
+/// The state of a [`World`] captured by [`SnapshotRegistry::capture`].
+///
+/// A snapshot can only be restored with the [`SnapshotRegistry`] that captured it.
+pub struct WorldSnapshot {
+    entities: EntitiesSnapshot,
+    alive: Vec<Entity>,
+    data: Vec<SnapshotData>,
+}
+
+impl WorldSnapshot {
+    /// Returns the entities that were alive when the snapshot was taken.
+    pub fn entities(&self) -> &[Entity] {
+        &self.alive
+    }
+}
+
+impl SnapshotRegistry {
+    /// Registers the component `C` to be captured by its [`Clone`] implementation.
+    pub fn register_component<C: Component + Clone>(&mut self) -> &mut Self {
+        self.fns.push(Arc::new(CloneComponent::<C>(PhantomData)));
+        self
+    }
+
+    /// Registers the resource `R` to be captured by its [`Clone`] implementation.
+    pub fn register_resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        self.fns.push(Arc::new(CloneResource::<R>(PhantomData)));
+        self
+    }
+
+    /// Registers a component to be captured through reflection.
+    ///
+    /// Restoring snapshots with reflected components requires the [`AppTypeRegistry`] resource.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type doesn't have [`ReflectComponent`] type data.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_reflect_component(&mut self, registration: &TypeRegistration) -> &mut Self {
+        let reflect = registration
+            .data::<ReflectComponent>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "`{}` does not have `ReflectComponent` type data",
+                    registration.type_info().type_path()
+                )
+            })
+            .clone();
+        self.fns.push(Arc::new(ReflectedComponent {
+            type_id: registration.type_id(),
+            reflect,
+        }));
+        self
+    }
+
+    /// Registers a resource to be captured through reflection.
+    ///
+    /// Restoring snapshots with reflected resources requires the [`AppTypeRegistry`] resource.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type doesn't have [`ReflectResource`] type data.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_reflect_resource(&mut self, registration: &TypeRegistration) -> &mut Self {
+        let reflect = registration
+            .data::<ReflectResource>()
+            .unwrap_or_else(|| {
+                panic!(
+                    "`{}` does not have `ReflectResource` type data",
+                    registration.type_info().type_path()
+                )
+            })
+            .clone();
+        self.fns.push(Arc::new(ReflectedResource(reflect)));
+        self
+    }
+
+    /// Captures the entities of `world` and the values of the registered components and resources.
+    pub fn capture(&self, world: &mut World) -> WorldSnapshot {
+        world.flush();
+        WorldSnapshot {
+            entities: world.entities.snapshot(),
+            alive: world
+                .archetypes()
+                .iter()
+                .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.id()))
+                .collect(),
+            data: self.fns.iter().map(|fns| fns.capture(world)).collect(),
+        }
+    }
+
+    /// Restores `world` to the state captured by `snapshot`.
+    ///
+    /// See [`SnapshotRegistry`] for the details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `snapshot` wasn't captured by this registry, or if the hooks or observers
+    /// triggered while despawning entities spawn new ones.
+    pub fn restore(&self, world: &mut World, snapshot: &WorldSnapshot) {
+        assert_eq!(
+            self.fns.len(),
+            snapshot.data.len(),
+            "the snapshot was captured with a different `SnapshotRegistry`"
+        );
+        world.flush();
+
+        let alive: EntityHashSet = snapshot.alive.iter().copied().collect();
+        let spawned: Vec<Entity> = world
+            .archetypes()
+            .iter()
+            .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.id()))
+            .filter(|entity| !alive.contains(entity))
+            .collect();
+        for entity in spawned {
+            world.despawn(entity);
+        }
+        for &entity in &snapshot.alive {
+            world.get_or_spawn(entity);
+        }
+        world.flush();
+        world.entities.restore(&snapshot.entities);
+
+        for (fns, data) in self.fns.iter().zip(&snapshot.data) {
+            fns.restore(world, data);
+        }
+    }
+}
+
+/// Returns the entities that have the component `id`.
+fn entities_with(world: &World, id: Option<ComponentId>) -> impl Iterator<Item = Entity> + '_ {
+    world
+        .archetypes()
+        .iter()
+        .filter(move |archetype| id.is_some_and(|id| archetype.contains(id)))
+        .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.id()))
+}
+
+/// Removes the component `id` from the entities that don't have it in the snapshot.
+fn remove_from_others(world: &mut World, id: Option<ComponentId>, keep: EntityHashSet) {
+    let Some(id) = id else {
+        return;
+    };
+    let others: Vec<Entity> = entities_with(world, Some(id))
+        .filter(|entity| !keep.contains(entity))
+        .collect();
+    for entity in others {
+        world.entity_mut(entity).remove_by_id(id);
+    }
+}
+
+struct CloneComponent<C>(PhantomData<fn() -> C>);
+
+impl<C: Component + Clone> SnapshotFns for CloneComponent<C> {
+    fn capture(&self, world: &World) -> SnapshotData {
+        let values: Vec<(Entity, C)> = entities_with(world, world.component_id::<C>())
+            .filter_map(|entity| Some((entity, world.get::<C>(entity)?.clone())))
+            .collect();
+        Box::new(values)
+    }
+
+    fn restore(&self, world: &mut World, data: &SnapshotData) {
+        let values = data.downcast_ref::<Vec<(Entity, C)>>().unwrap();
+        let keep = values.iter().map(|(entity, _)| *entity).collect();
+        remove_from_others(world, world.component_id::<C>(), keep);
+        for (entity, value) in values {
+            world.entity_mut(*entity).insert(value.clone());
+        }
+    }
+}
+
+struct CloneResource<R>(PhantomData<fn() -> R>);
+
+impl<R: Resource + Clone> SnapshotFns for CloneResource<R> {
+    fn capture(&self, world: &World) -> SnapshotData {
+        Box::new(world.get_resource::<R>().cloned())
+    }
+
+    fn restore(&self, world: &mut World, data: &SnapshotData) {
+        match data.downcast_ref::<Option<R>>().unwrap() {
+            Some(value) => world.insert_resource(value.clone()),
+            None => {
+                world.remove_resource::<R>();
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct ReflectedComponent {
+    type_id: TypeId,
+    reflect: ReflectComponent,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl SnapshotFns for ReflectedComponent {
+    fn capture(&self, world: &World) -> SnapshotData {
+        let values: Vec<(Entity, Box<dyn Reflect>)> =
+            entities_with(world, world.components().get_id(self.type_id))
+                .filter_map(|entity| {
+                    let value = self.reflect.reflect(world.entity(entity))?;
+                    Some((entity, value.clone_value()))
+                })
+                .collect();
+        Box::new(values)
+    }
+
+    fn restore(&self, world: &mut World, data: &SnapshotData) {
+        let values = data
+            .downcast_ref::<Vec<(Entity, Box<dyn Reflect>)>>()
+            .unwrap();
+        let keep = values.iter().map(|(entity, _)| *entity).collect();
+        remove_from_others(world, world.components().get_id(self.type_id), keep);
+
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        for (entity, value) in values {
+            // `insert` replaces the whole value, while `apply` would merge lists and maps.
+            self.reflect
+                .insert(&mut world.entity_mut(*entity), &**value, &registry);
+        }
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct ReflectedResource(ReflectResource);
+
+#[cfg(feature = "bevy_reflect")]
+impl SnapshotFns for ReflectedResource {
+    fn capture(&self, world: &World) -> SnapshotData {
+        Box::new(self.0.reflect(world).map(Reflect::clone_value))
+    }
+
+    fn restore(&self, world: &mut World, data: &SnapshotData) {
+        match data.downcast_ref::<Option<Box<dyn Reflect>>>().unwrap() {
+            Some(value) => {
+                let registry = world.resource::<AppTypeRegistry>().clone();
+                self.0.insert(world, &**value, &registry.read());
+            }
+            None => self.0.remove(world),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Position(i32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Velocity(i32);
+
+    #[derive(Component)]
+    struct NotRegistered;
+
+    #[derive(Resource, Clone, PartialEq, Debug)]
+    struct Frame(u32);
+
+    fn registry() -> SnapshotRegistry {
+        let mut registry = SnapshotRegistry::default();
+        registry
+            .register_component::<Position>()
+            .register_component::<Velocity>()
+            .register_resource::<Frame>();
+        registry
+    }
+
+    fn simulate(world: &mut World) -> Vec<Entity> {
+        world.resource_mut::<Frame>().0 += 1;
+        let mut spawned = Vec::new();
+        let entities: Vec<(Entity, i32)> = world
+            .query::<(Entity, &Position)>()
+            .iter(world)
+            .map(|(entity, position)| (entity, position.0))
+            .collect();
+        for (entity, position) in entities {
+            if position > 3 {
+                world.despawn(entity);
+                spawned.push(world.spawn((Position(0), Velocity(1))).id());
+            } else if let Some(velocity) = world.get::<Velocity>(entity) {
+                let velocity = velocity.0;
+                world.get_mut::<Position>(entity).unwrap().0 += velocity;
+            } else {
+                world.entity_mut(entity).insert(Velocity(2));
+            }
+        }
+        spawned
+    }
+
+    #[test]
+    fn resimulation_is_exact() {
+        let registry = registry();
+        let mut world = World::new();
+        world.insert_resource(Frame(0));
+        world.spawn(Position(0));
+        world.spawn((Position(2), Velocity(1)));
+        let despawned = world.spawn(Position(0)).id();
+        world.despawn(despawned);
+
+        let snapshot = registry.capture(&mut world);
+        let mut spawned = Vec::new();
+        for _ in 0..5 {
+            spawned.extend(simulate(&mut world));
+        }
+        assert!(!spawned.is_empty());
+        let positions: Vec<_> = world
+            .query::<(Entity, &Position, Option<&Velocity>)>()
+            .iter(&world)
+            .map(|(entity, position, velocity)| (entity, position.clone(), velocity.cloned()))
+            .collect();
+
+        registry.restore(&mut world, &snapshot);
+        assert_eq!(world.resource::<Frame>(), &Frame(0));
+        assert_eq!(world.entities().len(), 2);
+
+        let mut resimulated = Vec::new();
+        for _ in 0..5 {
+            resimulated.extend(simulate(&mut world));
+        }
+        assert_eq!(resimulated, spawned);
+        let mut resimulated_positions: Vec<_> = world
+            .query::<(Entity, &Position, Option<&Velocity>)>()
+            .iter(&world)
+            .map(|(entity, position, velocity)| (entity, position.clone(), velocity.cloned()))
+            .collect();
+        resimulated_positions.sort_by_key(|(entity, ..)| *entity);
+        let mut positions = positions;
+        positions.sort_by_key(|(entity, ..)| *entity);
+        assert_eq!(resimulated_positions, positions);
+    }
+
+    #[test]
+    fn restore_components_and_resources() {
+        let registry = registry();
+        let mut world = World::new();
+        let a = world.spawn((Position(1), NotRegistered)).id();
+        let b = world.spawn((Position(2), Velocity(2))).id();
+        let snapshot = registry.capture(&mut world);
+        assert_eq!(snapshot.entities(), &[a, b]);
+
+        world.insert_resource(Frame(3));
+        world.entity_mut(a).insert(Velocity(1)).remove::<Position>();
+        world.despawn(b);
+
+        registry.restore(&mut world, &snapshot);
+        assert!(!world.contains_resource::<Frame>());
+        assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+        assert!(world.get::<Velocity>(a).is_none());
+        assert!(world.get::<NotRegistered>(a).is_some());
+        assert_eq!(world.get::<Position>(b), Some(&Position(2)));
+        assert_eq!(world.get::<Velocity>(b), Some(&Velocity(2)));
+    }
+
+    #[test]
+    fn restore_allocator_with_reserved_entities() {
+        let registry = registry();
+        let mut world = World::new();
+        let despawned = world.spawn_empty().id();
+        world.spawn_empty();
+        world.despawn(despawned);
+        let snapshot = registry.capture(&mut world);
+
+        let reserved = world.entities().reserve_entity();
+        world.flush();
+        let spawned = world.spawn_empty().id();
+
+        registry.restore(&mut world, &snapshot);
+        assert!(world.get_entity(reserved).is_none());
+        assert_eq!(world.entities().reserve_entity(), reserved);
+        world.flush();
+        assert_eq!(world.spawn_empty().id(), spawned);
+    }
+
+    #[test]
+    #[should_panic(expected = "different `SnapshotRegistry`")]
+    fn restore_with_other_registry() {
+        let mut world = World::new();
+        let snapshot = registry().capture(&mut world);
+        SnapshotRegistry::default().restore(&mut world, &snapshot);
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn reflected_components() {
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Path(Vec<u32>);
+
+        #[derive(Resource, Reflect, PartialEq, Debug)]
+        #[reflect(Resource)]
+        struct Seed(u64);
+
+        let type_registry = AppTypeRegistry::default();
+        type_registry.write().register::<Path>();
+        type_registry.write().register::<Seed>();
+
+        let mut registry = SnapshotRegistry::default();
+        {
+            let types = type_registry.read();
+            registry
+                .register_reflect_component(types.get(TypeId::of::<Path>()).unwrap())
+                .register_reflect_resource(types.get(TypeId::of::<Seed>()).unwrap());
+        }
+
+        let mut world = World::new();
+        world.insert_resource(type_registry);
+        world.insert_resource(Seed(4));
+        let entity = world.spawn(Path(vec![1, 2, 3])).id();
+        let snapshot = registry.capture(&mut world);
+
+        world.get_mut::<Path>(entity).unwrap().0.pop();
+        world.remove_resource::<Seed>();
+        registry.restore(&mut world, &snapshot);
+
+        assert_eq!(world.get::<Path>(entity), Some(&Path(vec![1, 2, 3])));
+        assert_eq!(world.resource::<Seed>(), &Seed(4));
+    }
+}
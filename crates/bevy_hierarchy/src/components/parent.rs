@@ -3,6 +3,7 @@ use bevy_ecs::reflect::{ReflectComponent, ReflectMapEntities};
 use bevy_ecs::{
     component::Component,
     entity::{Entity, EntityMapper, MapEntities},
+    traversal::Traversal,
     world::{FromWorld, World},
 };
 use std::ops::Deref;
@@ -55,6 +56,17 @@ impl FromWorld for Parent {
     }
 }
 
+/// This provides generalized hierarchy traversal for use in [event propagation].
+///
+/// `Parent::traverse` will never form loops in properly-constructed hierarchies.
+///
+/// [event propagation]: bevy_ecs::observer::Trigger::propagate
+impl Traversal for Parent {
+    fn traverse(&self) -> Option<Entity> {
+        Some(self.0)
+    }
+}
+
 impl MapEntities for Parent {
     fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
         self.0 = entity_mapper.map_entity(self.0);
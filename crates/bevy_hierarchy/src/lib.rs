@@ -24,6 +24,15 @@
 //! and [events] to notify hierarchical changes.
 //! There is also a [diagnostic plugin] to validate property propagation.
 //!
+//! # Typed relationships
+//!
+//! Beyond parent-child hierarchies, entities can be linked by arbitrary [typed relationships].
+//! Each [`RelationshipKind`] gets its own pair of components:
+//! [`Relationship`] on the source and [`RelationshipSources`] on the target.
+//! Both sides are kept in sync through component hooks,
+//! and the kind's [`DespawnPolicy`] decides what happens to the sources
+//! when their target is despawned.
+//!
 //! # Hierarchy management
 //!
 //! The methods defined in this crate fully manage
@@ -50,6 +59,7 @@
 //! [hierarchical despawn extension methods]: DespawnRecursiveExt
 //! [plugin]: HierarchyPlugin
 //! [query extension methods]: HierarchyQueryExt
+//! [typed relationships]: BuildRelationships
 //! [world]: BuildWorldChildren
 
 mod components;
@@ -70,10 +80,15 @@ pub use valid_parent_check_plugin::*;
 mod query_extension;
 pub use query_extension::*;
 
+mod relationship;
+pub use relationship::*;
+
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{child_builder::*, components::*, hierarchy::*, query_extension::*};
+    pub use crate::{
+        child_builder::*, components::*, hierarchy::*, query_extension::*, relationship::*,
+    };
 
     #[doc(hidden)]
     #[cfg(feature = "bevy_app")]
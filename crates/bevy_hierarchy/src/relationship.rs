@@ -0,0 +1,653 @@
+use std::{collections::VecDeque, fmt, marker::PhantomData, ops::Deref};
+
+use bevy_ecs::{
+    component::{Component, ComponentHooks, StorageType},
+    entity::{Entity, EntityMapper, MapEntities},
+    query::{QueryData, QueryFilter, WorldQuery},
+    system::{EntityCommands, Query},
+    traversal::Traversal,
+    world::{DeferredWorld, EntityWorldMut, World},
+};
+use smallvec::SmallVec;
+
+use crate::despawn_with_children_recursive;
+
+/// Describes what happens to the sources of a relationship when its target is despawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DespawnPolicy {
+    /// The [`Relationship`] component is removed from every source. The sources are left alive.
+    #[default]
+    Detach,
+    /// Every source is despawned alongside the target.
+    Despawn,
+    /// Every source is despawned alongside the target, together with its [`Children`] hierarchy.
+    ///
+    /// [`Children`]: crate::Children
+    DespawnRecursive,
+}
+
+/// A kind of typed relationship between entities, such as "targets", "owned by" or "attached to".
+///
+/// Implementors are usually empty marker types. They are used as the type parameter of the
+/// [`Relationship`] component, stored on the source entity, and of the [`RelationshipSources`]
+/// component, stored on the target entity. Both sides are kept in sync automatically through
+/// component hooks.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_hierarchy::prelude::*;
+/// struct OwnedBy;
+///
+/// impl RelationshipKind for OwnedBy {
+///     const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Despawn;
+/// }
+///
+/// let mut world = World::new();
+/// let player = world.spawn_empty().id();
+/// let sword = world.spawn_empty().relate::<OwnedBy>(player).id();
+///
+/// assert_eq!(world.get::<Relationship<OwnedBy>>(sword).unwrap().get(), player);
+/// assert_eq!(&**world.get::<RelationshipSources<OwnedBy>>(player).unwrap(), &[sword]);
+///
+/// // Despawning the owner despawns everything it owns.
+/// world.despawn(player);
+/// assert!(world.get_entity(sword).is_none());
+/// ```
+pub trait RelationshipKind: Send + Sync + 'static {
+    /// What happens to the sources of this relationship when the target is despawned.
+    const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Detach;
+}
+
+/// Holds a reference to the target entity of a relationship of kind `R`.
+///
+/// The target entity stores this entity in its [`RelationshipSources<R>`] component.
+/// This component cannot be constructed directly,
+/// use [`BuildRelationships::relate`] or [`BuildWorldRelationships::relate`] instead.
+///
+/// See [`RelationshipQueryExt`] for relationship related methods on [`Query`].
+pub struct Relationship<R: RelationshipKind> {
+    target: Entity,
+    _marker: PhantomData<R>,
+}
+
+impl<R: RelationshipKind> Relationship<R> {
+    fn new(target: Entity) -> Self {
+        Self {
+            target,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets the [`Entity`] ID of the target.
+    #[inline(always)]
+    pub fn get(&self) -> Entity {
+        self.target
+    }
+}
+
+impl<R: RelationshipKind> fmt::Debug for Relationship<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Relationship").field(&self.target).finish()
+    }
+}
+
+impl<R: RelationshipKind> PartialEq for Relationship<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.target == other.target
+    }
+}
+
+impl<R: RelationshipKind> Eq for Relationship<R> {}
+
+impl<R: RelationshipKind> Deref for Relationship<R> {
+    type Target = Entity;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.target
+    }
+}
+
+impl<R: RelationshipKind> MapEntities for Relationship<R> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.target = entity_mapper.map_entity(self.target);
+    }
+}
+
+/// Relationships can be followed when propagating events, going from a source to its target.
+///
+/// [`Relationship::traverse`] may form loops if a chain of relationships of the same kind loops back on itself.
+impl<R: RelationshipKind> Traversal for Relationship<R> {
+    fn traverse(&self) -> Option<Entity> {
+        Some(self.target)
+    }
+}
+
+impl<R: RelationshipKind> Component for Relationship<R> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_insert(|mut world, source, _| {
+            let target = world.get::<Relationship<R>>(source).unwrap().target;
+            world.commands().add(move |world: &mut World| {
+                link::<R>(world, source, target);
+            });
+        });
+        hooks.on_remove(|mut world, source, _| {
+            let target = world.get::<Relationship<R>>(source).unwrap().target;
+            world.commands().add(move |world: &mut World| {
+                unlink::<R>(world, source, target);
+            });
+        });
+    }
+}
+
+/// Contains references to all the source entities that have a [`Relationship<R>`] pointing at this entity.
+///
+/// This component is created, updated and removed automatically as relationships are added and removed.
+/// When it is removed because its entity was despawned, the [`RelationshipKind::DESPAWN_POLICY`] of `R`
+/// is applied to the sources.
+pub struct RelationshipSources<R: RelationshipKind> {
+    sources: SmallVec<[Entity; 8]>,
+    _marker: PhantomData<R>,
+}
+
+impl<R: RelationshipKind> fmt::Debug for RelationshipSources<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RelationshipSources")
+            .field(&self.sources)
+            .finish()
+    }
+}
+
+impl<R: RelationshipKind> Deref for RelationshipSources<R> {
+    type Target = [Entity];
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.sources[..]
+    }
+}
+
+impl<'a, R: RelationshipKind> IntoIterator for &'a RelationshipSources<R> {
+    type Item = <Self::IntoIter as Iterator>::Item;
+
+    type IntoIter = std::slice::Iter<'a, Entity>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.sources.iter()
+    }
+}
+
+impl<R: RelationshipKind> MapEntities for RelationshipSources<R> {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        for entity in &mut self.sources {
+            *entity = entity_mapper.map_entity(*entity);
+        }
+    }
+}
+
+impl<R: RelationshipKind> Component for RelationshipSources<R> {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_remove(|mut world: DeferredWorld, target, _| {
+            let sources = world
+                .get::<RelationshipSources<R>>(target)
+                .unwrap()
+                .sources
+                .clone();
+            if sources.is_empty() {
+                return;
+            }
+            world.commands().add(move |world: &mut World| {
+                // Only apply the despawn policy if the target itself is gone, otherwise the
+                // sources were merely cleared and just need to be detached.
+                let policy = if world.get_entity(target).is_some() {
+                    DespawnPolicy::Detach
+                } else {
+                    R::DESPAWN_POLICY
+                };
+                for source in sources {
+                    let still_related = world
+                        .get::<Relationship<R>>(source)
+                        .is_some_and(|relationship| relationship.target == target);
+                    if !still_related {
+                        continue;
+                    }
+                    match policy {
+                        DespawnPolicy::Detach => {
+                            world.entity_mut(source).remove::<Relationship<R>>();
+                        }
+                        DespawnPolicy::Despawn => {
+                            world.despawn(source);
+                        }
+                        DespawnPolicy::DespawnRecursive => {
+                            despawn_with_children_recursive(world, source);
+                        }
+                    }
+                }
+            });
+        });
+    }
+}
+
+/// Adds `source` to the [`RelationshipSources`] of `target`, if `source` still points at `target`.
+fn link<R: RelationshipKind>(world: &mut World, source: Entity, target: Entity) {
+    let still_related = world
+        .get::<Relationship<R>>(source)
+        .is_some_and(|relationship| relationship.target == target);
+    if !still_related {
+        return;
+    }
+    let Some(mut target_entity) = world.get_entity_mut(target) else {
+        return;
+    };
+    if let Some(mut sources) = target_entity.get_mut::<RelationshipSources<R>>() {
+        if !sources.sources.contains(&source) {
+            sources.sources.push(source);
+        }
+    } else {
+        target_entity.insert(RelationshipSources::<R> {
+            sources: SmallVec::from_slice(&[source]),
+            _marker: PhantomData,
+        });
+    }
+}
+
+/// Removes `source` from the [`RelationshipSources`] of `target`, removing the component once it is empty.
+fn unlink<R: RelationshipKind>(world: &mut World, source: Entity, target: Entity) {
+    let Some(mut target_entity) = world.get_entity_mut(target) else {
+        return;
+    };
+    let Some(mut sources) = target_entity.get_mut::<RelationshipSources<R>>() else {
+        return;
+    };
+    sources.sources.retain(|entity| *entity != source);
+    if sources.sources.is_empty() {
+        target_entity.remove::<RelationshipSources<R>>();
+    }
+}
+
+/// Trait for adding and removing typed relationships of an entity through [`Commands`].
+///
+/// [`Commands`]: bevy_ecs::system::Commands
+pub trait BuildRelationships {
+    /// Relates this entity to `target` with a relationship of kind `R`.
+    ///
+    /// If this entity was already related to another entity with the same kind of relationship,
+    /// that relationship is replaced. The [`RelationshipSources<R>`] of both targets are updated.
+    fn relate<R: RelationshipKind>(&mut self, target: Entity) -> &mut Self;
+
+    /// Removes the relationship of kind `R` of this entity, if any.
+    ///
+    /// Also removes this entity from its target's [`RelationshipSources<R>`] component. Removing all
+    /// sources from a target causes its [`RelationshipSources<R>`] component to be removed from the entity.
+    fn unrelate<R: RelationshipKind>(&mut self) -> &mut Self;
+}
+
+impl BuildRelationships for EntityCommands<'_> {
+    fn relate<R: RelationshipKind>(&mut self, target: Entity) -> &mut Self {
+        self.add(move |source: Entity, world: &mut World| {
+            world.entity_mut(source).relate::<R>(target);
+        })
+    }
+
+    fn unrelate<R: RelationshipKind>(&mut self) -> &mut Self {
+        self.add(|source: Entity, world: &mut World| {
+            world.entity_mut(source).unrelate::<R>();
+        })
+    }
+}
+
+/// Trait for adding and removing typed relationships of an entity directly through the [`World`].
+pub trait BuildWorldRelationships {
+    /// Relates this entity to `target` with a relationship of kind `R`.
+    ///
+    /// If this entity was already related to another entity with the same kind of relationship,
+    /// that relationship is replaced. The [`RelationshipSources<R>`] of both targets are updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` does not exist.
+    fn relate<R: RelationshipKind>(&mut self, target: Entity) -> &mut Self;
+
+    /// Removes the relationship of kind `R` of this entity, if any.
+    ///
+    /// Also removes this entity from its target's [`RelationshipSources<R>`] component. Removing all
+    /// sources from a target causes its [`RelationshipSources<R>`] component to be removed from the entity.
+    fn unrelate<R: RelationshipKind>(&mut self) -> &mut Self;
+}
+
+impl<'w> BuildWorldRelationships for EntityWorldMut<'w> {
+    fn relate<R: RelationshipKind>(&mut self, target: Entity) -> &mut Self {
+        if self
+            .get::<Relationship<R>>()
+            .is_some_and(|relationship| relationship.target == target)
+        {
+            return self;
+        }
+        self.world_scope(|world| {
+            if world.get_entity(target).is_none() {
+                panic!("Could not relate to entity {target:?} because it doesn't exist.");
+            }
+        });
+        self.remove::<Relationship<R>>();
+        self.insert(Relationship::<R>::new(target));
+        self.world_scope(World::flush);
+        self
+    }
+
+    fn unrelate<R: RelationshipKind>(&mut self) -> &mut Self {
+        self.remove::<Relationship<R>>();
+        self.world_scope(World::flush);
+        self
+    }
+}
+
+/// An extension trait for [`Query`] that adds methods to walk typed relationships.
+pub trait RelationshipQueryExt<'w, 's, D: QueryData, F: QueryFilter> {
+    /// Returns an [`Iterator`] of [`Entity`]s following the chain of [`Relationship<R>`] targets starting at `entity`.
+    ///
+    /// Can only be called on a [`Query`] of [`Relationship<R>`] (i.e. `Query<&Relationship<R>>`).
+    ///
+    /// If the chain of relationships loops back on itself, this iterator never ends.
+    fn iter_relationship_targets<R: RelationshipKind>(
+        &'w self,
+        entity: Entity,
+    ) -> RelationshipTargetIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w Relationship<R>>;
+
+    /// Returns an [`Iterator`] of [`Entity`]s over all entities that are transitively related to `entity`.
+    ///
+    /// Can only be called on a [`Query`] of [`RelationshipSources<R>`] (i.e. `Query<&RelationshipSources<R>>`).
+    ///
+    /// Traverses the relationships breadth-first. If the relationships form a loop, this iterator never ends.
+    fn iter_relationship_sources<R: RelationshipKind>(
+        &'w self,
+        entity: Entity,
+    ) -> RelationshipSourceIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w RelationshipSources<R>>;
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter> RelationshipQueryExt<'w, 's, D, F>
+    for Query<'w, 's, D, F>
+{
+    fn iter_relationship_targets<R: RelationshipKind>(
+        &'w self,
+        entity: Entity,
+    ) -> RelationshipTargetIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w Relationship<R>>,
+    {
+        RelationshipTargetIter::new(self, entity)
+    }
+
+    fn iter_relationship_sources<R: RelationshipKind>(
+        &'w self,
+        entity: Entity,
+    ) -> RelationshipSourceIter<'w, 's, D, F, R>
+    where
+        D::ReadOnly: WorldQuery<Item<'w> = &'w RelationshipSources<R>>,
+    {
+        RelationshipSourceIter::new(self, entity)
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s following a chain of [`Relationship<R>`] targets.
+pub struct RelationshipTargetIter<'w, 's, D: QueryData, F: QueryFilter, R: RelationshipKind>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w Relationship<R>>,
+{
+    relationship_query: &'w Query<'w, 's, D, F>,
+    next: Option<Entity>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: RelationshipKind>
+    RelationshipTargetIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w Relationship<R>>,
+{
+    /// Returns a new [`RelationshipTargetIter`].
+    pub fn new(relationship_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        RelationshipTargetIter {
+            relationship_query,
+            next: Some(entity),
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: RelationshipKind> Iterator
+    for RelationshipTargetIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w Relationship<R>>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next = self
+            .relationship_query
+            .get(self.next?)
+            .ok()
+            .map(Relationship::get);
+        self.next
+    }
+}
+
+/// An [`Iterator`] of [`Entity`]s over everything transitively related to an [`Entity`].
+///
+/// Traverses the relationships breadth-first.
+pub struct RelationshipSourceIter<'w, 's, D: QueryData, F: QueryFilter, R: RelationshipKind>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w RelationshipSources<R>>,
+{
+    sources_query: &'w Query<'w, 's, D, F>,
+    vecdeque: VecDeque<Entity>,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: RelationshipKind>
+    RelationshipSourceIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w RelationshipSources<R>>,
+{
+    /// Returns a new [`RelationshipSourceIter`].
+    pub fn new(sources_query: &'w Query<'w, 's, D, F>, entity: Entity) -> Self {
+        RelationshipSourceIter {
+            sources_query,
+            vecdeque: sources_query
+                .get(entity)
+                .into_iter()
+                .flatten()
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, R: RelationshipKind> Iterator
+    for RelationshipSourceIter<'w, 's, D, F, R>
+where
+    D::ReadOnly: WorldQuery<Item<'w> = &'w RelationshipSources<R>>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.vecdeque.pop_front()?;
+
+        if let Ok(sources) = self.sources_query.get(entity) {
+            self.vecdeque.extend(sources);
+        }
+
+        Some(entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        system::{Query, SystemState},
+        world::{CommandQueue, World},
+    };
+
+    use super::*;
+    use crate::BuildWorldChildren;
+
+    struct Targets;
+
+    impl RelationshipKind for Targets {}
+
+    struct OwnedBy;
+
+    impl RelationshipKind for OwnedBy {
+        const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Despawn;
+    }
+
+    struct AttachedTo;
+
+    impl RelationshipKind for AttachedTo {
+        const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::DespawnRecursive;
+    }
+
+    fn sources<R: RelationshipKind>(world: &World, entity: Entity) -> Option<Vec<Entity>> {
+        world
+            .get::<RelationshipSources<R>>(entity)
+            .map(|sources| sources.to_vec())
+    }
+
+    #[test]
+    fn relate_updates_both_sides() {
+        let world = &mut World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world.entity_mut(a).relate::<Targets>(c);
+        world.entity_mut(b).relate::<Targets>(c);
+
+        assert_eq!(world.get::<Relationship<Targets>>(a).unwrap().get(), c);
+        assert_eq!(sources::<Targets>(world, c), Some(vec![a, b]));
+
+        // Retargeting moves the source over to the new target.
+        world.entity_mut(a).relate::<Targets>(b);
+        assert_eq!(sources::<Targets>(world, c), Some(vec![b]));
+        assert_eq!(sources::<Targets>(world, b), Some(vec![a]));
+
+        // Removing the last source removes the collection.
+        world.entity_mut(b).unrelate::<Targets>();
+        assert_eq!(sources::<Targets>(world, c), None);
+        assert!(world.get::<Relationship<Targets>>(b).is_none());
+    }
+
+    #[test]
+    fn relate_with_commands() {
+        let world = &mut World::new();
+        let mut queue = CommandQueue::default();
+        let [a, b] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        {
+            let mut commands = bevy_ecs::system::Commands::new(&mut queue, world);
+            commands.entity(a).relate::<Targets>(b);
+        }
+        queue.apply(world);
+        assert_eq!(sources::<Targets>(world, b), Some(vec![a]));
+
+        {
+            let mut commands = bevy_ecs::system::Commands::new(&mut queue, world);
+            commands.entity(a).unrelate::<Targets>();
+        }
+        queue.apply(world);
+        assert_eq!(sources::<Targets>(world, b), None);
+    }
+
+    #[test]
+    fn relationship_kinds_are_independent() {
+        let world = &mut World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world
+            .entity_mut(a)
+            .relate::<Targets>(b)
+            .relate::<OwnedBy>(c);
+
+        assert_eq!(sources::<Targets>(world, b), Some(vec![a]));
+        assert_eq!(sources::<OwnedBy>(world, c), Some(vec![a]));
+        assert_eq!(sources::<OwnedBy>(world, b), None);
+    }
+
+    #[test]
+    fn despawn_source() {
+        let world = &mut World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world.entity_mut(a).relate::<Targets>(c);
+        world.entity_mut(b).relate::<Targets>(c);
+
+        world.despawn(a);
+        assert_eq!(sources::<Targets>(world, c), Some(vec![b]));
+    }
+
+    #[test]
+    fn despawn_policy_detach() {
+        let world = &mut World::new();
+        let [a, b] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world.entity_mut(a).relate::<Targets>(b);
+        world.despawn(b);
+
+        assert!(world.get_entity(a).is_some());
+        assert!(world.get::<Relationship<Targets>>(a).is_none());
+    }
+
+    #[test]
+    fn despawn_policy_despawn() {
+        let world = &mut World::new();
+        let [a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world.entity_mut(a).relate::<OwnedBy>(b);
+        world.entity_mut(b).relate::<OwnedBy>(c);
+        world.despawn(c);
+
+        // The policy applies transitively through the despawned sources.
+        assert!(world.get_entity(a).is_none());
+        assert!(world.get_entity(b).is_none());
+    }
+
+    #[test]
+    fn despawn_policy_despawn_recursive() {
+        let world = &mut World::new();
+        let [a, b, child] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world.entity_mut(a).add_child(child).relate::<AttachedTo>(b);
+        world.despawn(b);
+
+        assert!(world.get_entity(a).is_none());
+        assert!(world.get_entity(child).is_none());
+    }
+
+    #[test]
+    fn query_extension() {
+        let world = &mut World::new();
+        let [a, b, c, d] = std::array::from_fn(|_| world.spawn_empty().id());
+
+        world.entity_mut(a).relate::<Targets>(b);
+        world.entity_mut(b).relate::<Targets>(c);
+        world.entity_mut(d).relate::<Targets>(c);
+
+        let mut system_state = SystemState::<(
+            Query<&Relationship<Targets>>,
+            Query<&RelationshipSources<Targets>>,
+        )>::new(world);
+        let (relationship_query, sources_query) = system_state.get(world);
+
+        let result: Vec<_> = relationship_query
+            .iter_relationship_targets::<Targets>(a)
+            .collect();
+        assert_eq!([b, c], result[..]);
+
+        let result: Vec<_> = sources_query
+            .iter_relationship_sources::<Targets>(c)
+            .collect();
+        assert_eq!([b, d, a], result[..]);
+    }
+}
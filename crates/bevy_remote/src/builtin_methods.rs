@@ -0,0 +1,783 @@
+//! Built-in verbs for the Bevy Remote Protocol.
+
+use std::any::TypeId;
+
+use anyhow::{anyhow, Result as AnyhowResult};
+use bevy_ecs::{
+    component::ComponentId,
+    entity::Entity,
+    query::QueryBuilder,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    system::In,
+    world::{EntityRef, EntityWorldMut, FilteredEntityRef, World},
+};
+use bevy_hierarchy::BuildWorldChildren as _;
+use bevy_reflect::{
+    serde::{TypedReflectDeserializer, TypedReflectSerializer},
+    Reflect, TypeRegistration, TypeRegistry,
+};
+use bevy_utils::HashMap;
+use serde::{de::DeserializeSeed as _, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{error_codes, BrpError, BrpResult};
+
+/// The method path for a `bevy/get` request.
+pub const BRP_GET_METHOD: &str = "bevy/get";
+
+/// The method path for a `bevy/query` request.
+pub const BRP_QUERY_METHOD: &str = "bevy/query";
+
+/// The method path for a `bevy/spawn` request.
+pub const BRP_SPAWN_METHOD: &str = "bevy/spawn";
+
+/// The method path for a `bevy/insert` request.
+pub const BRP_INSERT_METHOD: &str = "bevy/insert";
+
+/// The method path for a `bevy/remove` request.
+pub const BRP_REMOVE_METHOD: &str = "bevy/remove";
+
+/// The method path for a `bevy/destroy` request.
+pub const BRP_DESTROY_METHOD: &str = "bevy/destroy";
+
+/// The method path for a `bevy/reparent` request.
+pub const BRP_REPARENT_METHOD: &str = "bevy/reparent";
+
+/// The method path for a `bevy/list` request.
+pub const BRP_LIST_METHOD: &str = "bevy/list";
+
+/// `bevy/get`: Retrieves one or more components from the entity with the
+/// given ID.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpGetParams {
+    /// The ID of the entity from which components are to be requested.
+    pub entity: Entity,
+
+    /// The full paths of the component types that are to be requested.
+    pub components: Vec<String>,
+}
+
+/// `bevy/query`: Performs a query over components in the ECS, returning
+/// entities and component values that match.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpQueryParams {
+    /// The components to select.
+    pub data: BrpQuery,
+
+    /// An optional filter that specifies which entities to include or
+    /// exclude from the results.
+    #[serde(default)]
+    pub filter: BrpQueryFilter,
+}
+
+/// `bevy/spawn`: Creates a new entity with the given components and responds
+/// with its ID.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSpawnParams {
+    /// A map from each component's full path to its serialized value.
+    pub components: HashMap<String, Value>,
+}
+
+/// `bevy/insert`: Adds one or more components to an entity, replacing the
+/// values of any it already has.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpInsertParams {
+    /// The ID of the entity that components are to be added to.
+    pub entity: Entity,
+
+    /// A map from each component's full path to its serialized value.
+    pub components: HashMap<String, Value>,
+}
+
+/// `bevy/remove`: Deletes one or more components from an entity.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpRemoveParams {
+    /// The ID of the entity from which components are to be removed.
+    pub entity: Entity,
+
+    /// The full paths of the component types that are to be removed.
+    pub components: Vec<String>,
+}
+
+/// `bevy/destroy`: Given an ID, despawns the entity with that ID.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpDestroyParams {
+    /// The ID of the entity to despawn.
+    pub entity: Entity,
+}
+
+/// `bevy/reparent`: Assigns a new parent to one or more entities.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpReparentParams {
+    /// The IDs of the entities that are to become the new children of the
+    /// `parent`.
+    pub entities: Vec<Entity>,
+
+    /// The ID of the entity that will become the new parent of the
+    /// `entities`.
+    ///
+    /// If this is `None`, then the entities are removed from all parents.
+    #[serde(default)]
+    pub parent: Option<Entity>,
+}
+
+/// `bevy/list`: Returns a list of all type paths of registered components in
+/// the system, or those on an entity if `entity` is given.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpListParams {
+    /// The entity to query.
+    pub entity: Entity,
+}
+
+/// Describes the data that is to be fetched in a query.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpQuery {
+    /// The full paths of the component types that are to be fetched.
+    ///
+    /// Entities that are missing any of these are not matched.
+    #[serde(default)]
+    pub components: Vec<String>,
+
+    /// The full paths of the component types that are to be optionally
+    /// fetched.
+    #[serde(default)]
+    pub option: Vec<String>,
+
+    /// The full paths of the component types whose presence is to be
+    /// reported, without fetching their values.
+    #[serde(default)]
+    pub has: Vec<String>,
+}
+
+/// Additional constraints that can be placed on a query to include or exclude
+/// certain entities.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpQueryFilter {
+    /// The full paths of the component types that matched entities must not
+    /// have.
+    #[serde(default)]
+    pub without: Vec<String>,
+
+    /// The full paths of the component types that matched entities must have,
+    /// without fetching their values.
+    #[serde(default)]
+    pub with: Vec<String>,
+}
+
+/// A single row of the response to a `bevy/query` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpQueryRow {
+    /// The ID of the entity that matched.
+    pub entity: Entity,
+
+    /// The serialized values of the requested components.
+    pub components: HashMap<String, Value>,
+
+    /// The presence of the components listed in [`BrpQuery::has`].
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub has: HashMap<String, Value>,
+}
+
+/// The response to a `bevy/get` request.
+pub type BrpGetResponse = HashMap<String, Value>;
+
+/// The response to a `bevy/list` request.
+pub type BrpListResponse = Vec<String>;
+
+/// The response to a `bevy/query` request.
+pub type BrpQueryResponse = Vec<BrpQueryRow>;
+
+/// The response to a `bevy/spawn` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSpawnResponse {
+    /// The ID of the entity in question.
+    pub entity: Entity,
+}
+
+/// Handles a `bevy/get` request coming from a client.
+pub fn process_remote_get_request(In(params): In<Option<Value>>, world: &World) -> BrpResult {
+    let BrpGetParams { entity, components } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let entity_ref = get_entity(world, entity)?;
+
+    let mut response = BrpGetResponse::default();
+    for component_path in components {
+        let reflect_component = get_reflect_component(&type_registry, &component_path)
+            .map_err(BrpError::component_error)?;
+
+        let Some(reflected) = reflect_component.reflect(entity_ref) else {
+            return Err(BrpError::component_not_present(&component_path, entity));
+        };
+
+        let value = serialize_reflected(reflected, &type_registry)?;
+        response.insert(component_path, value);
+    }
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/query` request coming from a client.
+pub fn process_remote_query_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpQueryParams {
+        data: BrpQuery {
+            components,
+            option,
+            has,
+        },
+        filter: BrpQueryFilter { without, with },
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let components =
+        get_component_ids(&type_registry, world, components).map_err(BrpError::component_error)?;
+    let option =
+        get_component_ids(&type_registry, world, option).map_err(BrpError::component_error)?;
+    let has = get_component_ids(&type_registry, world, has).map_err(BrpError::component_error)?;
+    let without =
+        get_component_ids(&type_registry, world, without).map_err(BrpError::component_error)?;
+    let with = get_component_ids(&type_registry, world, with).map_err(BrpError::component_error)?;
+
+    let mut query = QueryBuilder::<FilteredEntityRef>::new(world);
+    for (_, component_id) in &components {
+        query.ref_id(*component_id);
+    }
+    for (_, component_id) in &option {
+        query.optional(|query| {
+            query.ref_id(*component_id);
+        });
+    }
+    for (_, component_id) in &with {
+        query.with_id(*component_id);
+    }
+    for (_, component_id) in &without {
+        query.without_id(*component_id);
+    }
+    let mut query = query.build();
+
+    let mut response = BrpQueryResponse::default();
+    for row in query.iter(world) {
+        let mut row_components = HashMap::default();
+        for (type_id, component_id) in components.iter().chain(option.iter()) {
+            let Some(reflected) = reflect_component_by_id(&type_registry, row.clone(), *type_id)?
+            else {
+                continue;
+            };
+            let path = component_path(world, *component_id)?;
+            row_components.insert(path, serialize_reflected(reflected, &type_registry)?);
+        }
+
+        let mut row_has = HashMap::default();
+        for (_, component_id) in &has {
+            let path = component_path(world, *component_id)?;
+            row_has.insert(path, Value::Bool(row.contains_id(*component_id)));
+        }
+
+        response.push(BrpQueryRow {
+            entity: row.id(),
+            components: row_components,
+            has: row_has,
+        });
+    }
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/spawn` request coming from a client.
+pub fn process_remote_spawn_request(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpSpawnParams { components } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let reflect_components =
+        deserialize_components(&type_registry, components).map_err(BrpError::component_error)?;
+
+    let mut entity_world_mut = world.spawn_empty();
+    insert_reflected_components(&type_registry, &mut entity_world_mut, reflect_components)
+        .map_err(BrpError::component_error)?;
+
+    let response = BrpSpawnResponse {
+        entity: entity_world_mut.id(),
+    };
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/insert` request coming from a client.
+pub fn process_remote_insert_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpInsertParams { entity, components } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let reflect_components =
+        deserialize_components(&type_registry, components).map_err(BrpError::component_error)?;
+
+    let mut entity_world_mut = get_entity_mut(world, entity)?;
+    insert_reflected_components(&type_registry, &mut entity_world_mut, reflect_components)
+        .map_err(BrpError::component_error)?;
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/remove` request coming from a client.
+pub fn process_remote_remove_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpRemoveParams { entity, components } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+
+    let component_ids =
+        get_component_ids(&type_registry, world, components).map_err(BrpError::component_error)?;
+
+    let mut entity_world_mut = get_entity_mut(world, entity)?;
+    for (_, component_id) in component_ids {
+        entity_world_mut.remove_by_id(component_id);
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/destroy` request coming from a client.
+pub fn process_remote_destroy_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpDestroyParams { entity } = parse_some(params)?;
+
+    get_entity_mut(world, entity)?.despawn();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/reparent` request coming from a client.
+pub fn process_remote_reparent_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpReparentParams {
+        entities,
+        parent: maybe_parent,
+    } = parse_some(params)?;
+
+    for entity in &entities {
+        get_entity(world, *entity)?;
+    }
+
+    match maybe_parent {
+        // If `Some`, reparent the entities.
+        Some(parent) => {
+            if entities.contains(&parent) {
+                return Err(BrpError {
+                    code: error_codes::SELF_REPARENT,
+                    message: format!("Cannot make entity {parent:?} a child of itself"),
+                    data: None,
+                });
+            }
+            get_entity_mut(world, parent)?.push_children(&entities);
+        }
+        // If `None`, remove the entities from their parents.
+        None => {
+            for entity in entities {
+                get_entity_mut(world, entity)?.remove_parent();
+            }
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/list` request (list all components) coming from a client.
+pub fn process_remote_list_request(In(params): In<Option<Value>>, world: &World) -> BrpResult {
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+
+    let mut response = BrpListResponse::default();
+
+    // If `Some`, return all components of the provided entity.
+    if let Some(BrpListParams { entity }) = params.map(parse).transpose()? {
+        let entity = get_entity(world, entity)?;
+        for component_id in entity.archetype().components() {
+            let Some(component_info) = world.components().get_info(component_id) else {
+                continue;
+            };
+            let Some(registration) = component_info
+                .type_id()
+                .and_then(|type_id| type_registry.get(type_id))
+            else {
+                continue;
+            };
+            if registration.data::<ReflectComponent>().is_some() {
+                response.push(registration.type_info().type_path().to_owned());
+            }
+        }
+    }
+    // If `None`, list all registered components.
+    else {
+        for registration in type_registry.iter() {
+            if registration.data::<ReflectComponent>().is_some() {
+                response.push(registration.type_info().type_path().to_owned());
+            }
+        }
+    }
+
+    // Sort both for cleanliness and to reduce the risk that clients start
+    // accidentally depending on the order.
+    response.sort();
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Immutably retrieves an entity from the [`World`], returning an error if
+/// the entity isn't present.
+fn get_entity(world: &World, entity: Entity) -> Result<EntityRef<'_>, BrpError> {
+    world
+        .get_entity(entity)
+        .ok_or_else(|| BrpError::entity_not_found(entity))
+}
+
+/// Mutably retrieves an entity from the [`World`], returning an error if the
+/// entity isn't present.
+fn get_entity_mut(world: &mut World, entity: Entity) -> Result<EntityWorldMut<'_>, BrpError> {
+    world
+        .get_entity_mut(entity)
+        .ok_or_else(|| BrpError::entity_not_found(entity))
+}
+
+/// Returns the full type path of a component, as known to the [`World`].
+fn component_path(world: &World, component_id: ComponentId) -> Result<String, BrpError> {
+    world
+        .components()
+        .get_info(component_id)
+        .map(|info| info.name().to_owned())
+        .ok_or_else(|| BrpError::component_error(anyhow!("Unknown component: {component_id:?}")))
+}
+
+/// Returns the [`TypeId`] and [`ComponentId`] of the components with the
+/// given full type paths.
+fn get_component_ids(
+    type_registry: &TypeRegistry,
+    world: &World,
+    component_paths: Vec<String>,
+) -> AnyhowResult<Vec<(TypeId, ComponentId)>> {
+    component_paths
+        .into_iter()
+        .map(|component_path| {
+            let type_id =
+                get_component_type_registration(type_registry, &component_path)?.type_id();
+            let component_id = world
+                .components()
+                .get_id(type_id)
+                .ok_or_else(|| anyhow!("Component `{component_path}` isn't used in the world"))?;
+            Ok((type_id, component_id))
+        })
+        .collect()
+}
+
+/// Reflects the component with the given [`TypeId`] on a query row, if it
+/// is present.
+fn reflect_component_by_id<'w>(
+    type_registry: &TypeRegistry,
+    row: FilteredEntityRef<'w>,
+    type_id: TypeId,
+) -> Result<Option<&'w dyn Reflect>, BrpError> {
+    let reflect_component = type_registry
+        .get_type_data::<ReflectComponent>(type_id)
+        .ok_or_else(|| BrpError::component_error(anyhow!("Component is not reflectable")))?;
+    Ok(reflect_component.reflect(row))
+}
+
+/// Serializes a reflected value to JSON through the type registry.
+fn serialize_reflected(reflected: &dyn Reflect, type_registry: &TypeRegistry) -> BrpResult {
+    let serializer = TypedReflectSerializer::new(reflected, type_registry);
+    serde_json::to_value(serializer).map_err(BrpError::component_error)
+}
+
+/// Given a collection of component paths and their associated serialized
+/// values (`components`), return the associated collection of deserialized
+/// reflected values.
+fn deserialize_components(
+    type_registry: &TypeRegistry,
+    components: HashMap<String, Value>,
+) -> AnyhowResult<Vec<Box<dyn Reflect>>> {
+    let mut reflect_components = vec![];
+
+    for (component_path, component) in components {
+        let Some(component_type) = type_registry.get_with_type_path(&component_path) else {
+            return Err(anyhow!("Unknown component type: `{}`", component_path));
+        };
+        let reflected: Box<dyn Reflect> =
+            TypedReflectDeserializer::new(component_type, type_registry)
+                .deserialize(&component)
+                .map_err(|err| anyhow!("{component_path} is invalid: {err}"))?;
+        reflect_components.push(reflected);
+    }
+
+    Ok(reflect_components)
+}
+
+/// Given a collection `reflect_components` of reflected component values,
+/// insert them into the given entity (`entity_world_mut`).
+fn insert_reflected_components(
+    type_registry: &TypeRegistry,
+    entity_world_mut: &mut EntityWorldMut,
+    reflect_components: Vec<Box<dyn Reflect>>,
+) -> AnyhowResult<()> {
+    for reflected in reflect_components {
+        // Deserialized values are usually dynamic types, so look the component
+        // up by the type that they represent.
+        let type_path = reflected
+            .get_represented_type_info()
+            .map_or_else(|| reflected.reflect_type_path(), |info| info.type_path());
+        let reflect_component = get_reflect_component(type_registry, type_path)?;
+        reflect_component.apply_or_insert(entity_world_mut, &*reflected, type_registry);
+    }
+
+    Ok(())
+}
+
+/// Given a component's type path, return the associated [`ReflectComponent`]
+/// from the given `type_registry` if possible.
+fn get_reflect_component<'r>(
+    type_registry: &'r TypeRegistry,
+    component_path: &str,
+) -> AnyhowResult<&'r ReflectComponent> {
+    let component_registration = get_component_type_registration(type_registry, component_path)?;
+
+    component_registration
+        .data::<ReflectComponent>()
+        .ok_or_else(|| anyhow!("Component `{}` isn't reflectable", component_path))
+}
+
+/// Given a component's type path, return the associated [`TypeRegistration`]
+/// from the given `type_registry` if possible.
+fn get_component_type_registration<'r>(
+    type_registry: &'r TypeRegistry,
+    component_path: &str,
+) -> AnyhowResult<&'r TypeRegistration> {
+    type_registry
+        .get_with_type_path(component_path)
+        .ok_or_else(|| anyhow!("Unknown component type: `{}`", component_path))
+}
+
+/// A helper function used to parse a `serde_json::Value`.
+fn parse<T: for<'de> Deserialize<'de>>(value: Value) -> Result<T, BrpError> {
+    serde_json::from_value(value).map_err(|err| BrpError {
+        code: error_codes::INVALID_PARAMS,
+        message: err.to_string(),
+        data: None,
+    })
+}
+
+/// A helper function used to parse a `serde_json::Value` wrapped in an `Option`.
+fn parse_some<T: for<'de> Deserialize<'de>>(value: Option<Value>) -> Result<T, BrpError> {
+    match value {
+        Some(value) => parse(value),
+        None => Err(BrpError {
+            code: error_codes::INVALID_PARAMS,
+            message: String::from("Params not provided"),
+            data: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{component::Component, system::RunSystemOnce};
+    use bevy_reflect::Reflect;
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Dead;
+
+    /// A component whose type path differs from its type name.
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    #[type_path = "game"]
+    struct Renamed;
+
+    fn test_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let registry = world.resource::<AppTypeRegistry>();
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Dead>();
+            registry.register::<Renamed>();
+        }
+        world.init_component::<Health>();
+        world.init_component::<Dead>();
+        world.init_component::<Renamed>();
+        world
+    }
+
+    fn health_path() -> &'static str {
+        <Health as bevy_reflect::TypePath>::type_path()
+    }
+
+    fn dead_path() -> &'static str {
+        <Dead as bevy_reflect::TypePath>::type_path()
+    }
+
+    fn run(
+        world: &mut World,
+        method: fn(In<Option<Value>>, &mut World) -> BrpResult,
+        params: Value,
+    ) -> BrpResult {
+        world.run_system_once_with(Some(params), method)
+    }
+
+    #[test]
+    fn spawn_get_insert_remove() {
+        let mut world = test_world();
+
+        let spawned = run(
+            &mut world,
+            process_remote_spawn_request,
+            json!({ "components": { health_path(): [10] } }),
+        )
+        .unwrap();
+        let BrpSpawnResponse { entity } = serde_json::from_value(spawned).unwrap();
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+
+        run(
+            &mut world,
+            process_remote_insert_request,
+            json!({ "entity": entity, "components": { health_path(): [7], dead_path(): {} } }),
+        )
+        .unwrap();
+        assert_eq!(world.get::<Health>(entity), Some(&Health(7)));
+        assert!(world.entity(entity).contains::<Dead>());
+
+        let got = world
+            .run_system_once_with(
+                Some(json!({ "entity": entity, "components": [health_path()] })),
+                process_remote_get_request,
+            )
+            .unwrap();
+        assert_eq!(got, json!({ health_path(): [7] }));
+
+        run(
+            &mut world,
+            process_remote_remove_request,
+            json!({ "entity": entity, "components": [dead_path()] }),
+        )
+        .unwrap();
+        assert!(!world.entity(entity).contains::<Dead>());
+
+        run(
+            &mut world,
+            process_remote_destroy_request,
+            json!({ "entity": entity }),
+        )
+        .unwrap();
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    #[test]
+    fn list() {
+        let mut world = test_world();
+        let entity = world.spawn((Health(1), Renamed)).id();
+
+        let all = world
+            .run_system_once_with(None, process_remote_list_request)
+            .unwrap();
+        assert_eq!(all, json!([dead_path(), health_path(), "game::Renamed"]));
+
+        // The components of an entity are listed by the same type paths as the other methods use.
+        let listed = world
+            .run_system_once_with(
+                Some(json!({ "entity": entity })),
+                process_remote_list_request,
+            )
+            .unwrap();
+        assert_eq!(listed, json!([health_path(), "game::Renamed"]));
+        let got = world
+            .run_system_once_with(
+                Some(json!({ "entity": entity, "components": listed })),
+                process_remote_get_request,
+            )
+            .unwrap();
+        assert_eq!(got, json!({ health_path(): [1], "game::Renamed": {} }));
+    }
+
+    #[test]
+    fn query_with_filters() {
+        let mut world = test_world();
+        let alive = world.spawn(Health(3)).id();
+        let dead = world.spawn((Health(0), Dead)).id();
+
+        let result = run(
+            &mut world,
+            process_remote_query_request,
+            json!({
+                "data": { "components": [health_path()], "has": [dead_path()] },
+            }),
+        )
+        .unwrap();
+        let mut rows: BrpQueryResponse = serde_json::from_value(result).unwrap();
+        rows.sort_by_key(|row| row.entity);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].entity, alive);
+        assert_eq!(rows[0].components[health_path()], json!([3]));
+        assert_eq!(rows[0].has[dead_path()], json!(false));
+        assert_eq!(rows[1].entity, dead);
+        assert_eq!(rows[1].has[dead_path()], json!(true));
+
+        let result = run(
+            &mut world,
+            process_remote_query_request,
+            json!({
+                "data": { "components": [health_path()] },
+                "filter": { "without": [dead_path()] },
+            }),
+        )
+        .unwrap();
+        let rows: BrpQueryResponse = serde_json::from_value(result).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].entity, alive);
+    }
+
+    #[test]
+    fn errors() {
+        let mut world = test_world();
+        let entity = world.spawn(Health(1)).id();
+        world.despawn(entity);
+
+        let error = run(
+            &mut world,
+            process_remote_destroy_request,
+            json!({ "entity": entity }),
+        )
+        .unwrap_err();
+        assert_eq!(error.code, error_codes::ENTITY_NOT_FOUND);
+
+        let error = run(
+            &mut world,
+            process_remote_spawn_request,
+            json!({ "components": { "not::a::Component": 1 } }),
+        )
+        .unwrap_err();
+        assert_eq!(error.code, error_codes::COMPONENT_ERROR);
+
+        let error = run(&mut world, process_remote_spawn_request, json!(1)).unwrap_err();
+        assert_eq!(error.code, error_codes::INVALID_PARAMS);
+    }
+}
@@ -0,0 +1,262 @@
+//! The HTTP transport for the Bevy Remote Protocol.
+//!
+//! Requests are sent as the JSON body of a `POST` to the server, and the
+//! response body is the JSON-RPC response. Batches (JSON arrays of requests)
+//! are supported as well.
+//!
+//! For example, using `curl`:
+//!
+//! ```sh
+//! curl -X POST http://127.0.0.1:15702 \
+//!     -d '{"jsonrpc": "2.0", "id": 1, "method": "bevy/list"}'
+//! ```
+
+use std::net::{IpAddr, Ipv4Addr, TcpListener, TcpStream};
+
+use anyhow::Result as AnyhowResult;
+use async_channel::Sender;
+use async_io::Async;
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::system::{Res, Resource};
+use bevy_tasks::IoTaskPool;
+use bevy_utils::tracing::{error, info};
+use http_body_util::{BodyExt as _, Full};
+use hyper::{
+    body::{Bytes, Incoming},
+    header::{HeaderValue, CONTENT_TYPE},
+    server::conn::http1,
+    service, Request, Response,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use smol_hyper::rt::{FuturesIo, SmolTimer};
+
+use crate::{error_codes, BrpError, BrpMessage, BrpRequest, BrpResponse, BrpSender};
+
+/// The default port that the Bevy Remote Protocol listens on.
+pub const DEFAULT_PORT: u16 = 15702;
+
+/// The default host address that the Bevy Remote Protocol listens on.
+///
+/// By default, only connections from the local machine are accepted.
+pub const DEFAULT_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+/// Serves the Bevy Remote Protocol over HTTP.
+///
+/// This requires the [`RemotePlugin`](crate::RemotePlugin) to be added as well.
+pub struct RemoteHttpPlugin {
+    /// The address that the server binds to.
+    address: IpAddr,
+    /// The port that the server listens on.
+    port: u16,
+}
+
+impl Default for RemoteHttpPlugin {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDR,
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+impl RemoteHttpPlugin {
+    /// Sets the address that the server binds to.
+    ///
+    /// Binding to anything other than a loopback address exposes the whole
+    /// [`World`](bevy_ecs::world::World) to the network: only do this on
+    /// trusted networks.
+    #[must_use]
+    pub fn with_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// Sets the port that the server listens on.
+    #[must_use]
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+}
+
+impl Plugin for RemoteHttpPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HostAddress(self.address))
+            .insert_resource(HostPort(self.port))
+            .add_systems(Startup, start_http_server);
+    }
+}
+
+/// The address that the HTTP server is bound to.
+#[derive(Debug, Resource)]
+pub struct HostAddress(pub IpAddr);
+
+/// The port that the HTTP server listens on.
+#[derive(Debug, Resource)]
+pub struct HostPort(pub u16);
+
+/// A single request or a batch of requests.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+enum BrpBatch {
+    Batch(Vec<Value>),
+    Single(Value),
+}
+
+/// A single response or a batch of responses.
+#[derive(Debug, Serialize, Clone)]
+#[serde(untagged)]
+enum BrpHttpResponse {
+    Batch(Vec<BrpResponse>),
+    Single(BrpResponse),
+}
+
+/// Spawns the task that accepts connections on the configured address.
+fn start_http_server(
+    request_sender: Res<BrpSender>,
+    address: Res<HostAddress>,
+    port: Res<HostPort>,
+) {
+    IoTaskPool::get()
+        .spawn(server_main(address.0, port.0, request_sender.0.clone()))
+        .detach();
+}
+
+async fn server_main(address: IpAddr, port: u16, request_sender: Sender<BrpMessage>) {
+    let listener = match Async::<TcpListener>::bind((address, port)) {
+        Ok(listener) => listener,
+        Err(err) => {
+            error!("Failed to bind the remote protocol server to {address}:{port}: {err}");
+            return;
+        }
+    };
+    info!("Remote protocol server listening on http://{address}:{port}");
+
+    loop {
+        let client = match listener.accept().await {
+            Ok((client, _)) => client,
+            Err(err) => {
+                error!("Failed to accept a remote protocol connection: {err}");
+                continue;
+            }
+        };
+
+        let request_sender = request_sender.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let _ = handle_client(client, request_sender).await;
+            })
+            .detach();
+    }
+}
+
+async fn handle_client(
+    client: Async<TcpStream>,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    http1::Builder::new()
+        .timer(SmolTimer::new())
+        .serve_connection(
+            FuturesIo::new(client),
+            service::service_fn(|request| process_request_batch(request, &request_sender)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Parses the body of an HTTP request, which may contain one request or a
+/// batch of them, and waits for all of the responses.
+async fn process_request_batch(
+    request: Request<Incoming>,
+    request_sender: &Sender<BrpMessage>,
+) -> AnyhowResult<Response<Full<Bytes>>> {
+    let batch_bytes = request.into_body().collect().await?.to_bytes();
+    let batch: Result<BrpBatch, _> = serde_json::from_slice(&batch_bytes);
+
+    let serialized = match batch {
+        Ok(BrpBatch::Single(request)) => {
+            let response = process_single_request(request, request_sender).await;
+            serde_json::to_string(&BrpHttpResponse::Single(response))?
+        }
+        Ok(BrpBatch::Batch(requests)) => {
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.push(process_single_request(request, request_sender).await);
+            }
+            serde_json::to_string(&BrpHttpResponse::Batch(responses))?
+        }
+        Err(err) => {
+            let response = BrpResponse::new(
+                None,
+                Err(BrpError {
+                    code: error_codes::PARSE_ERROR,
+                    message: err.to_string(),
+                    data: None,
+                }),
+            );
+            serde_json::to_string(&BrpHttpResponse::Single(response))?
+        }
+    };
+
+    let mut response = Response::new(Full::new(Bytes::from(serialized.into_bytes())));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    Ok(response)
+}
+
+/// Validates a single request, forwards it to the [`World`](bevy_ecs::world::World)
+/// and waits for the result.
+async fn process_single_request(
+    request: Value,
+    request_sender: &Sender<BrpMessage>,
+) -> BrpResponse {
+    // Reach in and get the request ID early so that we can report it even
+    // when parsing fails.
+    let id = request.as_object().and_then(|map| map.get("id")).cloned();
+
+    let request: BrpRequest = match serde_json::from_value(request) {
+        Ok(request) => request,
+        Err(err) => {
+            return BrpResponse::new(
+                id,
+                Err(BrpError {
+                    code: error_codes::INVALID_REQUEST,
+                    message: err.to_string(),
+                    data: None,
+                }),
+            );
+        }
+    };
+
+    if request.jsonrpc != "2.0" {
+        return BrpResponse::new(
+            id,
+            Err(BrpError {
+                code: error_codes::INVALID_REQUEST,
+                message: String::from("JSON-RPC request requires `\"jsonrpc\": \"2.0\"`"),
+                data: None,
+            }),
+        );
+    }
+
+    let (result_sender, result_receiver) = async_channel::bounded(1);
+
+    let _ = request_sender
+        .send(BrpMessage {
+            method: request.method,
+            params: request.params,
+            sender: result_sender,
+        })
+        .await;
+
+    let result = result_receiver.recv().await.unwrap_or_else(|_| {
+        Err(BrpError::internal(
+            "The app stopped before the request was processed",
+        ))
+    });
+
+    BrpResponse::new(request.id, result)
+}
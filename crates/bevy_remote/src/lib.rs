@@ -0,0 +1,476 @@
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![forbid(unsafe_code)]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! An implementation of the Bevy Remote Protocol (BRP), which allows external
+//! processes to inspect and mutate a running Bevy [`World`].
+//!
+//! The protocol is a small dialect of [JSON-RPC 2.0]: requests and responses
+//! are JSON objects, and components are (de)serialized through the
+//! [`TypeRegistry`](bevy_reflect::TypeRegistry) using [`ReflectComponent`]
+//! data. This makes it possible to build external editors, inspectors and
+//! test harnesses that drive a game without linking against it.
+//!
+//! Only components that derive [`Reflect`](bevy_reflect::Reflect), are
+//! registered in the [`AppTypeRegistry`] and carry `#[reflect(Component)]`
+//! are visible to the protocol.
+//!
+//! Add the [`RemotePlugin`] to enable the protocol, and a transport such as
+//! [`RemoteHttpPlugin`](http::RemoteHttpPlugin) to expose it:
+//!
+//! ```no_run
+//! # use bevy_app::App;
+//! # use bevy_remote::{http::RemoteHttpPlugin, RemotePlugin};
+//! App::new()
+//!     .add_plugins(RemotePlugin::default())
+//!     .add_plugins(RemoteHttpPlugin::default())
+//!     .run();
+//! ```
+//!
+//! ## Requests
+//!
+//! A request is a JSON object with the following fields:
+//!
+//! - `jsonrpc`: must be the string `"2.0"`.
+//! - `method`: the name of the method to call, such as `bevy/get`.
+//! - `params`: the method arguments, if any.
+//! - `id`: an arbitrary value that is echoed back in the response. May be omitted.
+//!
+//! For example, asking for the [`Transform`] of entity `4294967298`:
+//!
+//! ```json
+//! {
+//!     "jsonrpc": "2.0",
+//!     "id": 0,
+//!     "method": "bevy/get",
+//!     "params": {
+//!         "entity": 4294967298,
+//!         "components": ["bevy_transform::components::transform::Transform"]
+//!     }
+//! }
+//! ```
+//!
+//! Entities are sent as their [`Entity::to_bits`] representation, and
+//! component types are named by their full type path.
+//!
+//! ## Responses
+//!
+//! A response carries the `jsonrpc` version, the `id` of the request and
+//! either a `result` or an `error`. Errors contain a numeric `code` (see
+//! [`error_codes`]), a human-readable `message` and optional `data`.
+//!
+//! ## Built-in methods
+//!
+//! - `bevy/get`: Returns the values of the given components on an entity.
+//!   - `params`: `entity`, `components`
+//!   - `result`: a map from component type path to serialized value
+//! - `bevy/query`: Returns all entities that match a set of components, along
+//!   with the values of the requested components.
+//!   - `params`: `data` (`components`, `option`, `has`) and optional
+//!     `filter` (`with`, `without`)
+//!   - `result`: a list of objects with `entity`, `components` and `has` fields
+//! - `bevy/spawn`: Spawns a new entity with the given components.
+//!   - `params`: `components`, a map from type path to serialized value
+//!   - `result`: `entity`, the id of the new entity
+//! - `bevy/insert`: Inserts components into an entity, replacing existing values.
+//!   - `params`: `entity`, `components`
+//!   - `result`: `null`
+//! - `bevy/remove`: Removes components from an entity.
+//!   - `params`: `entity`, `components`
+//!   - `result`: `null`
+//! - `bevy/destroy`: Despawns an entity.
+//!   - `params`: `entity`
+//!   - `result`: `null`
+//! - `bevy/reparent`: Assigns a new parent to a set of entities, or removes
+//!   their parent if `parent` is omitted.
+//!   - `params`: `entities`, `parent`
+//!   - `result`: `null`
+//! - `bevy/list`: Lists the registered components, or those present on an
+//!   entity if `entity` is given.
+//!   - `params`: optional `entity`
+//!   - `result`: a list of type paths
+//!
+//! Custom methods can be registered with [`RemotePlugin::with_method`].
+//!
+//! [JSON-RPC 2.0]: https://www.jsonrpc.org/specification
+//! [`Transform`]: https://docs.rs/bevy/latest/bevy/transform/components/struct.Transform.html
+//! [`ReflectComponent`]: bevy_ecs::reflect::ReflectComponent
+
+use std::sync::RwLock;
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    entity::Entity,
+    system::{IntoSystem, Resource, System, SystemId},
+    world::World,
+};
+use bevy_utils::{prelude::default, tracing::warn, HashMap};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub mod builtin_methods;
+#[cfg(feature = "http")]
+pub mod http;
+
+#[cfg(doc)]
+use bevy_ecs::reflect::AppTypeRegistry;
+
+/// Adds the Bevy Remote Protocol to an [`App`].
+///
+/// This plugin only processes requests; pair it with a transport such as
+/// [`RemoteHttpPlugin`](http::RemoteHttpPlugin) to receive them.
+pub struct RemotePlugin {
+    /// The methods to register, paired with their handler systems.
+    ///
+    /// These are stored behind a lock so that they can be moved into the
+    /// world from [`Plugin::finish`], which only receives `&self`.
+    methods: RwLock<Vec<(String, RemoteMethodSystem)>>,
+}
+
+impl RemotePlugin {
+    /// Creates a [`RemotePlugin`] with no methods registered.
+    ///
+    /// Use [`RemotePlugin::default`] to start with the built-in `bevy/*`
+    /// methods instead.
+    pub fn empty() -> Self {
+        Self {
+            methods: RwLock::new(vec![]),
+        }
+    }
+
+    /// Registers a remote method named `name`.
+    ///
+    /// The handler is a system that receives the request `params` as input
+    /// and returns the response `result`, or an error.
+    pub fn with_method<M>(
+        mut self,
+        name: impl Into<String>,
+        handler: impl IntoSystem<Option<Value>, BrpResult, M>,
+    ) -> Self {
+        self.methods
+            .get_mut()
+            .unwrap()
+            .push((name.into(), Box::new(IntoSystem::into_system(handler))));
+        self
+    }
+}
+
+impl Default for RemotePlugin {
+    fn default() -> Self {
+        Self::empty()
+            .with_method(
+                builtin_methods::BRP_GET_METHOD,
+                builtin_methods::process_remote_get_request,
+            )
+            .with_method(
+                builtin_methods::BRP_QUERY_METHOD,
+                builtin_methods::process_remote_query_request,
+            )
+            .with_method(
+                builtin_methods::BRP_SPAWN_METHOD,
+                builtin_methods::process_remote_spawn_request,
+            )
+            .with_method(
+                builtin_methods::BRP_INSERT_METHOD,
+                builtin_methods::process_remote_insert_request,
+            )
+            .with_method(
+                builtin_methods::BRP_REMOVE_METHOD,
+                builtin_methods::process_remote_remove_request,
+            )
+            .with_method(
+                builtin_methods::BRP_DESTROY_METHOD,
+                builtin_methods::process_remote_destroy_request,
+            )
+            .with_method(
+                builtin_methods::BRP_REPARENT_METHOD,
+                builtin_methods::process_remote_reparent_request,
+            )
+            .with_method(
+                builtin_methods::BRP_LIST_METHOD,
+                builtin_methods::process_remote_list_request,
+            )
+    }
+}
+
+impl Plugin for RemotePlugin {
+    fn build(&self, app: &mut App) {
+        let (request_sender, request_receiver) = async_channel::bounded(CHANNEL_SIZE);
+
+        app.insert_resource(BrpSender(request_sender))
+            .insert_resource(BrpReceiver(request_receiver))
+            .init_resource::<RemoteMethods>()
+            .add_systems(Last, process_remote_requests);
+    }
+
+    fn finish(&self, app: &mut App) {
+        let methods = std::mem::take(&mut *self.methods.write().unwrap());
+        let world = app.world_mut();
+        for (name, system) in methods {
+            let system_id = world.register_boxed_system(system);
+            world
+                .resource_mut::<RemoteMethods>()
+                .insert(name, system_id);
+        }
+    }
+}
+
+/// The maximum number of requests that can be waiting to be processed at once.
+const CHANNEL_SIZE: usize = 16;
+
+type RemoteMethodSystem = Box<dyn System<In = Option<Value>, Out = BrpResult>>;
+
+/// The [`SystemId`] of a registered remote method handler.
+pub type RemoteMethod = SystemId<Option<Value>, BrpResult>;
+
+/// Holds all the remote methods that the server can respond to, by name.
+#[derive(Debug, Resource, Default)]
+pub struct RemoteMethods(HashMap<String, RemoteMethod>);
+
+impl RemoteMethods {
+    /// Creates an empty [`RemoteMethods`] resource.
+    pub fn new() -> Self {
+        default()
+    }
+
+    /// Adds a new method, replacing any existing method with the same name.
+    ///
+    /// If there was an existing method with that name, returns its handler.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        handler: RemoteMethod,
+    ) -> Option<RemoteMethod> {
+        self.0.insert(name.into(), handler)
+    }
+
+    /// Returns the handler of the method with the given name, if it exists.
+    pub fn get(&self, name: &str) -> Option<&RemoteMethod> {
+        self.0.get(name)
+    }
+
+    /// Returns an iterator over the names of all registered methods.
+    pub fn methods(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+/// A single request from a Bevy Remote Protocol client.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrpRequest {
+    /// The protocol version, which must be `"2.0"`.
+    pub jsonrpc: String,
+
+    /// The name of the method to invoke.
+    pub method: String,
+
+    /// An arbitrary value echoed back in the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+
+    /// The arguments to the method, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+/// A response to a [`BrpRequest`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BrpResponse {
+    /// The protocol version, always `"2.0"`.
+    pub jsonrpc: &'static str,
+
+    /// The `id` of the request this responds to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+
+    /// Either the result of the method or an error.
+    #[serde(flatten)]
+    pub payload: BrpPayload,
+}
+
+impl BrpResponse {
+    /// Creates a response to the request with the given `id`.
+    #[must_use]
+    pub fn new(id: Option<Value>, result: BrpResult) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            payload: BrpPayload::from(result),
+        }
+    }
+}
+
+/// The body of a [`BrpResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpPayload {
+    /// The method succeeded and produced this value.
+    Result(Value),
+    /// The method failed.
+    Error(BrpError),
+}
+
+impl From<BrpResult> for BrpPayload {
+    fn from(value: BrpResult) -> Self {
+        match value {
+            Ok(result) => BrpPayload::Result(result),
+            Err(error) => BrpPayload::Error(error),
+        }
+    }
+}
+
+/// An error returned by a remote method.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpError {
+    /// The error code, see [`error_codes`].
+    pub code: i16,
+
+    /// A short description of the error.
+    pub message: String,
+
+    /// Additional information about the error, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl BrpError {
+    /// The entity does not exist.
+    #[must_use]
+    pub fn entity_not_found(entity: Entity) -> Self {
+        Self {
+            code: error_codes::ENTITY_NOT_FOUND,
+            message: format!("Entity {entity:?} does not exist"),
+            data: None,
+        }
+    }
+
+    /// The component does not exist on the entity.
+    #[must_use]
+    pub fn component_not_present(component: &str, entity: Entity) -> Self {
+        Self {
+            code: error_codes::COMPONENT_NOT_PRESENT,
+            message: format!("Component `{component}` not present on entity {entity:?}"),
+            data: None,
+        }
+    }
+
+    /// A component could not be found, (de)serialized or reflected.
+    #[must_use]
+    pub fn component_error<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::COMPONENT_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+
+    /// An unspecified error inside the method.
+    #[must_use]
+    pub fn internal<E: ToString>(error: E) -> Self {
+        Self {
+            code: error_codes::INTERNAL_ERROR,
+            message: error.to_string(),
+            data: None,
+        }
+    }
+}
+
+/// Error codes used by the Bevy Remote Protocol.
+pub mod error_codes {
+    // JSON-RPC errors
+    // Note that the range -32728 to -32000 (inclusive) is reserved by the JSON-RPC specification.
+
+    /// Invalid JSON.
+    pub const PARSE_ERROR: i16 = -32700;
+
+    /// JSON sent is not a valid request object.
+    pub const INVALID_REQUEST: i16 = -32600;
+
+    /// The method does not exist or is not available.
+    pub const METHOD_NOT_FOUND: i16 = -32601;
+
+    /// Invalid method parameter(s).
+    pub const INVALID_PARAMS: i16 = -32602;
+
+    /// Internal error.
+    pub const INTERNAL_ERROR: i16 = -32603;
+
+    // Bevy errors (i.e. application errors)
+
+    /// The entity does not exist.
+    pub const ENTITY_NOT_FOUND: i16 = -23401;
+
+    /// Could not reflect or find the component.
+    pub const COMPONENT_ERROR: i16 = -23402;
+
+    /// The entity does not have the requested component.
+    pub const COMPONENT_NOT_PRESENT: i16 = -23403;
+
+    /// An entity cannot be made a child of itself.
+    pub const SELF_REPARENT: i16 = -23404;
+}
+
+/// The result of a remote method: either a JSON value or an error.
+pub type BrpResult = Result<Value, BrpError>;
+
+/// A request on its way from a transport to the [`World`], along with the
+/// channel on which to send the result back.
+#[derive(Debug, Clone)]
+pub struct BrpMessage {
+    /// The name of the method to invoke.
+    pub method: String,
+
+    /// The arguments to the method, if any.
+    pub params: Option<Value>,
+
+    /// The channel on which the result is sent back to the transport.
+    pub sender: async_channel::Sender<BrpResult>,
+}
+
+/// The sending half of the channel used by transports to submit requests.
+///
+/// Transports clone the inner sender and push a [`BrpMessage`] for every
+/// incoming request; results are sent back on [`BrpMessage::sender`].
+#[derive(Debug, Resource, Clone)]
+pub struct BrpSender(pub async_channel::Sender<BrpMessage>);
+
+/// The receiving half of the channel that [`process_remote_requests`] drains.
+#[derive(Debug, Resource)]
+struct BrpReceiver(async_channel::Receiver<BrpMessage>);
+
+/// Runs the handlers of all pending remote requests and sends back their results.
+///
+/// Handlers get exclusive access to the [`World`], so requests are processed
+/// one at a time, in the order in which they were received.
+pub fn process_remote_requests(world: &mut World) {
+    if !world.contains_resource::<BrpReceiver>() {
+        return;
+    }
+
+    while let Ok(message) = world.resource::<BrpReceiver>().0.try_recv() {
+        let Some(&handler) = world.resource::<RemoteMethods>().get(&message.method) else {
+            let _ = message.sender.force_send(Err(BrpError {
+                code: error_codes::METHOD_NOT_FOUND,
+                message: format!("Method `{}` not found", message.method),
+                data: None,
+            }));
+            continue;
+        };
+
+        let result = match world.run_system_with_input(handler, message.params) {
+            Ok(result) => result,
+            Err(error) => {
+                warn!(
+                    "Failed to run remote method `{}`: {error:?}",
+                    message.method
+                );
+                Err(BrpError::internal(format!("{error:?}")))
+            }
+        };
+
+        let _ = message.sender.force_send(result);
+    }
+}
@@ -3,6 +3,7 @@
 //! For more info, see [`RenderDiagnosticsPlugin`].
 
 pub(crate) mod internal;
+mod render_asset_memory;
 
 use std::{borrow::Cow, marker::PhantomData, sync::Arc};
 
@@ -16,6 +17,8 @@ use self::internal::{
 
 use super::{RenderDevice, RenderQueue};
 
+pub use render_asset_memory::RenderAssetMemoryDiagnosticsPlugin;
+
 /// Enables collecting render diagnostics, such as CPU/GPU elapsed time per render pass,
 /// as well as pipeline statistics (number of primitives, number of shader invocations, etc).
 ///
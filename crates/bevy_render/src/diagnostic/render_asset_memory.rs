@@ -0,0 +1,104 @@
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use bevy_app::{App, Plugin, Update};
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::{
+    schedule::IntoSystemConfigs,
+    system::{Res, Resource},
+};
+use bevy_reflect::TypePath;
+
+use crate::{
+    render_asset::{prepare_assets, RenderAsset, RenderAssets},
+    Render, RenderApp,
+};
+
+/// Adds a "render asset memory" diagnostic for the render assets of type `A` to an App, measuring the bytes
+/// uploaded to the GPU for them as reported by [`RenderAssets::memory_usage`].
+///
+/// The diagnostic is named `render_asset_memory/<type>`, using the short type path of
+/// [`RenderAsset::SourceAsset`]. Only assets implementing [`RenderAsset::byte_len`] are counted.
+///
+/// # See also
+///
+/// [`AssetMemoryDiagnosticsPlugin`](bevy_asset::AssetMemoryDiagnosticsPlugin) for the CPU memory used by assets.
+pub struct RenderAssetMemoryDiagnosticsPlugin<A: RenderAsset> {
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A: RenderAsset> Default for RenderAssetMemoryDiagnosticsPlugin<A> {
+    fn default() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The memory used by the render assets of type `A`, shared between the main app and the render app.
+#[derive(Resource)]
+struct RenderAssetMemory<A: RenderAsset> {
+    bytes: Arc<AtomicUsize>,
+    marker: PhantomData<fn() -> A>,
+}
+
+impl<A: RenderAsset> Clone for RenderAssetMemory<A> {
+    fn clone(&self) -> Self {
+        Self {
+            bytes: self.bytes.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<A: RenderAsset> Plugin for RenderAssetMemoryDiagnosticsPlugin<A> {
+    fn build(&self, app: &mut App) {
+        let memory = RenderAssetMemory::<A> {
+            bytes: Default::default(),
+            marker: PhantomData,
+        };
+        app.insert_resource(memory.clone())
+            .register_diagnostic(Diagnostic::new(Self::diagnostic_path()).with_suffix("B"))
+            .add_systems(Update, Self::diagnostic_system);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(memory)
+                .add_systems(Render, store_memory_usage::<A>.after(prepare_assets::<A>));
+        }
+    }
+}
+
+impl<A: RenderAsset> RenderAssetMemoryDiagnosticsPlugin<A> {
+    pub const RENDER_ASSET_MEMORY: &'static str = "render_asset_memory";
+
+    /// Returns the [`DiagnosticPath`] of the memory used by the render assets of type `A`.
+    pub fn diagnostic_path() -> DiagnosticPath {
+        DiagnosticPath::from_components([
+            Self::RENDER_ASSET_MEMORY,
+            <A::SourceAsset as TypePath>::short_type_path(),
+        ])
+    }
+
+    fn diagnostic_system(mut diagnostics: Diagnostics, memory: Res<RenderAssetMemory<A>>) {
+        diagnostics.add_measurement(&Self::diagnostic_path(), || {
+            memory.bytes.load(Ordering::Relaxed) as f64
+        });
+    }
+}
+
+fn store_memory_usage<A: RenderAsset>(
+    render_assets: Option<Res<RenderAssets<A>>>,
+    memory: Res<RenderAssetMemory<A>>,
+) {
+    if let Some(render_assets) = render_assets {
+        memory
+            .bytes
+            .store(render_assets.memory_usage(), Ordering::Relaxed);
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
     renderer::RenderDevice,
     texture::GpuImage,
 };
-use bevy_asset::{Asset, Handle};
+use bevy_asset::{Asset, Handle, VisitAssetDependencies};
 use bevy_derive::EnumVariantMeta;
 use bevy_ecs::system::{
     lifetimeless::{SRes, SResMut},
@@ -119,7 +119,7 @@ pub const VERTEX_ATTRIBUTE_BUFFER_ID: u64 = 10;
 /// - Vertex winding order: by default, `StandardMaterial.cull_mode` is [`Some(Face::Back)`](crate::render_resource::Face),
 ///     which means that Bevy would *only* render the "front" of each triangle, which
 ///     is the side of the triangle from where the vertices appear in a *counter-clockwise* order.
-#[derive(Asset, Debug, Clone, Reflect)]
+#[derive(VisitAssetDependencies, Debug, Clone, Reflect)]
 pub struct Mesh {
     #[reflect(ignore)]
     primitive_topology: PrimitiveTopology,
@@ -135,6 +135,18 @@ pub struct Mesh {
     pub asset_usage: RenderAssetUsages,
 }
 
+impl Asset for Mesh {
+    fn memory_usage(&self) -> usize {
+        let attribute_bytes: usize = self
+            .attributes
+            .values()
+            .map(|attribute_data| attribute_data.values.get_bytes().len())
+            .sum();
+        let index_bytes = self.get_index_buffer_bytes().map(<[_]>::len).unwrap_or(0);
+        std::mem::size_of::<Self>() + attribute_bytes + index_bytes
+    }
+}
+
 impl Mesh {
     /// Where the vertex is located in space. Use in conjunction with [`Mesh::insert_attribute`]
     /// or [`Mesh::with_inserted_attribute`].
@@ -186,37 +186,70 @@ impl<A: RenderAsset> Default for ExtractedAssets<A> {
 /// Stores all GPU representations ([`RenderAsset`])
 /// of [`RenderAsset::SourceAsset`] as long as they exist.
 #[derive(Resource)]
-pub struct RenderAssets<A: RenderAsset>(HashMap<AssetId<A::SourceAsset>, A>);
+pub struct RenderAssets<A: RenderAsset> {
+    assets: HashMap<AssetId<A::SourceAsset>, A>,
+    byte_lens: HashMap<AssetId<A::SourceAsset>, usize>,
+    memory_usage: usize,
+}
 
 impl<A: RenderAsset> Default for RenderAssets<A> {
     fn default() -> Self {
-        Self(Default::default())
+        Self {
+            assets: Default::default(),
+            byte_lens: Default::default(),
+            memory_usage: 0,
+        }
     }
 }
 
 impl<A: RenderAsset> RenderAssets<A> {
     pub fn get(&self, id: impl Into<AssetId<A::SourceAsset>>) -> Option<&A> {
-        self.0.get(&id.into())
+        self.assets.get(&id.into())
     }
 
     pub fn get_mut(&mut self, id: impl Into<AssetId<A::SourceAsset>>) -> Option<&mut A> {
-        self.0.get_mut(&id.into())
+        self.assets.get_mut(&id.into())
     }
 
     pub fn insert(&mut self, id: impl Into<AssetId<A::SourceAsset>>, value: A) -> Option<A> {
-        self.0.insert(id.into(), value)
+        self.insert_with_byte_len(id, value, 0)
+    }
+
+    /// Inserts an asset which uploaded `byte_len` bytes to the GPU, counted by [`RenderAssets::memory_usage`].
+    pub fn insert_with_byte_len(
+        &mut self,
+        id: impl Into<AssetId<A::SourceAsset>>,
+        value: A,
+        byte_len: usize,
+    ) -> Option<A> {
+        let id = id.into();
+        self.memory_usage += byte_len;
+        if let Some(previous) = self.byte_lens.insert(id, byte_len) {
+            self.memory_usage -= previous;
+        }
+        self.assets.insert(id, value)
     }
 
     pub fn remove(&mut self, id: impl Into<AssetId<A::SourceAsset>>) -> Option<A> {
-        self.0.remove(&id.into())
+        let id = id.into();
+        if let Some(byte_len) = self.byte_lens.remove(&id) {
+            self.memory_usage -= byte_len;
+        }
+        self.assets.remove(&id)
+    }
+
+    /// Returns the total size in bytes of the data uploaded to the GPU for these assets, as reported by
+    /// [`RenderAsset::byte_len`] when they were prepared.
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (AssetId<A::SourceAsset>, &A)> {
-        self.0.iter().map(|(k, v)| (*k, v))
+        self.assets.iter().map(|(k, v)| (*k, v))
     }
 
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (AssetId<A::SourceAsset>, &mut A)> {
-        self.0.iter_mut().map(|(k, v)| (*k, v))
+        self.assets.iter_mut().map(|(k, v)| (*k, v))
     }
 }
 
@@ -342,7 +375,7 @@ pub fn prepare_assets<A: RenderAsset>(
 
         match A::prepare_asset(extracted_asset, &mut param) {
             Ok(prepared_asset) => {
-                render_assets.insert(id, prepared_asset);
+                render_assets.insert_with_byte_len(id, prepared_asset, write_bytes);
                 bpf.write_bytes(write_bytes);
                 wrote_asset_count += 1;
             }
@@ -374,7 +407,7 @@ pub fn prepare_assets<A: RenderAsset>(
 
         match A::prepare_asset(extracted_asset, &mut param) {
             Ok(prepared_asset) => {
-                render_assets.insert(id, prepared_asset);
+                render_assets.insert_with_byte_len(id, prepared_asset, write_bytes);
                 bpf.write_bytes(write_bytes);
                 wrote_asset_count += 1;
             }
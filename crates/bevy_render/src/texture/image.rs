@@ -11,7 +11,7 @@ use crate::{
     renderer::{RenderDevice, RenderQueue},
     texture::BevyDefault,
 };
-use bevy_asset::Asset;
+use bevy_asset::{Asset, VisitAssetDependencies};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::system::{lifetimeless::SRes, Resource, SystemParamItem};
 use bevy_math::{AspectRatio, UVec2, Vec2};
@@ -131,7 +131,7 @@ impl ImageFormat {
     }
 }
 
-#[derive(Asset, Reflect, Debug, Clone)]
+#[derive(VisitAssetDependencies, Reflect, Debug, Clone)]
 #[reflect_value(Default)]
 pub struct Image {
     pub data: Vec<u8>,
@@ -143,6 +143,12 @@ pub struct Image {
     pub asset_usage: RenderAssetUsages,
 }
 
+impl Asset for Image {
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>() + self.data.len()
+    }
+}
+
 /// Used in [`Image`], this determines what image sampler to use when rendering. The default setting,
 /// [`ImageSampler::Default`], will read the sampler from the [`ImagePlugin`](super::ImagePlugin) at setup.
 /// Setting this to [`ImageSampler::Descriptor`] will override the global default descriptor for this [`Image`].
@@ -2,15 +2,20 @@ use bevy_app::{App, MainScheduleOrder, Plugin, PreUpdate, Startup, SubApp};
 use bevy_ecs::{
     event::Events,
     schedule::{IntoSystemConfigs, ScheduleLabel},
+    system::IntoSystem,
     world::FromWorld,
 };
 use bevy_utils::{tracing::warn, warn_once};
 
 use crate::state::{
-    setup_state_transitions_in_world, ComputedStates, FreelyMutableState, NextState, State,
-    StateTransition, StateTransitionEvent, StateTransitionSteps, States, SubStates,
+    last_transition, register_state_history_systems, register_state_stack_systems,
+    setup_state_transitions_in_world, ComputedStates, ExitSchedules, FreelyMutableState, NextState,
+    State, StateStack, StateTransition, StateTransitionEvent, StateTransitionHistory,
+    StateTransitionSteps, States, SubStates,
+};
+use crate::state_scoped::{
+    clear_state_scoped_entities, clear_state_scoped_resources, StateScopedResources,
 };
-use crate::state_scoped::clear_state_scoped_entities;
 
 /// State installation methods for [`App`](bevy_app::App) and [`SubApp`](bevy_app::SubApp).
 pub trait AppExtStates {
@@ -55,6 +60,27 @@ pub trait AppExtStates {
     ///
     /// For more information refer to [`StateScoped`](crate::state_scoped::StateScoped).
     fn enable_state_scoped_entities<S: States>(&mut self) -> &mut Self;
+
+    /// Enable state-scoped resource removal for state `S`.
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    ///
+    /// For more information refer to [`StateScopedResources`].
+    fn enable_state_scoped_resources<S: States>(&mut self) -> &mut Self;
+
+    /// Enables stack-based transitions for state `S` through the [`StateStack<S>`] resource.
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    ///
+    /// For more information refer to [`StateStack`].
+    fn enable_state_stack<S: FreelyMutableState>(&mut self) -> &mut Self;
+
+    /// Enables recording the transitions of state `S` in the [`StateTransitionHistory<S>`] resource.
+    ///
+    /// This method is idempotent: it has no effect when called again using the same generic type.
+    ///
+    /// For more information refer to [`StateTransitionHistory`].
+    fn enable_state_history<S: States>(&mut self) -> &mut Self;
 }
 
 /// Separate function to only warn once for all state installation methods.
@@ -183,6 +209,77 @@ impl AppExtStates for SubApp {
             clear_state_scoped_entities::<S>.in_set(StateTransitionSteps::ExitSchedules),
         )
     }
+
+    fn enable_state_scoped_resources<S: States>(&mut self) -> &mut Self {
+        if self.world().contains_resource::<StateScopedResources<S>>() {
+            let name = std::any::type_name::<S>();
+            warn!(
+                "State scoped resources for state `{}` are already enabled.",
+                name
+            );
+            return self;
+        }
+        if !self
+            .world()
+            .contains_resource::<Events<StateTransitionEvent<S>>>()
+        {
+            let name = std::any::type_name::<S>();
+            warn!("State scoped resources are enabled for state `{}`, but the state isn't installed in the app!", name);
+        }
+        setup_state_transitions_in_world(self.world_mut(), Some(Startup.intern()));
+        self.init_resource::<StateScopedResources<S>>();
+        // Resources are removed after [`OnExit`] so that its systems can still access them.
+        self.add_systems(
+            StateTransition,
+            last_transition::<S>
+                .pipe(clear_state_scoped_resources::<S>)
+                .in_set(StateTransitionSteps::ExitSchedules)
+                .after(ExitSchedules::<S>::default()),
+        )
+    }
+
+    fn enable_state_stack<S: FreelyMutableState>(&mut self) -> &mut Self {
+        if self.world().contains_resource::<StateStack<S>>() {
+            let name = std::any::type_name::<S>();
+            warn!("State stack for state `{}` is already enabled.", name);
+            return self;
+        }
+        if !self.world().contains_resource::<NextState<S>>() {
+            let name = std::any::type_name::<S>();
+            warn!(
+                "State stack is enabled for state `{}`, but the state isn't installed in the app!",
+                name
+            );
+        }
+        setup_state_transitions_in_world(self.world_mut(), Some(Startup.intern()));
+        self.init_resource::<StateStack<S>>();
+        let schedule = self.get_schedule_mut(StateTransition).unwrap();
+        register_state_stack_systems::<S>(schedule);
+        self
+    }
+
+    fn enable_state_history<S: States>(&mut self) -> &mut Self {
+        if self
+            .world()
+            .contains_resource::<StateTransitionHistory<S>>()
+        {
+            let name = std::any::type_name::<S>();
+            warn!("State history for state `{}` is already enabled.", name);
+            return self;
+        }
+        if !self
+            .world()
+            .contains_resource::<Events<StateTransitionEvent<S>>>()
+        {
+            let name = std::any::type_name::<S>();
+            warn!("State history is enabled for state `{}`, but the state isn't installed in the app!", name);
+        }
+        setup_state_transitions_in_world(self.world_mut(), Some(Startup.intern()));
+        self.init_resource::<StateTransitionHistory<S>>();
+        let schedule = self.get_schedule_mut(StateTransition).unwrap();
+        register_state_history_systems::<S>(schedule);
+        self
+    }
 }
 
 impl AppExtStates for App {
@@ -210,6 +307,21 @@ impl AppExtStates for App {
         self.main_mut().enable_state_scoped_entities::<S>();
         self
     }
+
+    fn enable_state_scoped_resources<S: States>(&mut self) -> &mut Self {
+        self.main_mut().enable_state_scoped_resources::<S>();
+        self
+    }
+
+    fn enable_state_stack<S: FreelyMutableState>(&mut self) -> &mut Self {
+        self.main_mut().enable_state_stack::<S>();
+        self
+    }
+
+    fn enable_state_history<S: States>(&mut self) -> &mut Self {
+        self.main_mut().enable_state_history::<S>();
+        self
+    }
 }
 
 /// Registers the [`StateTransition`] schedule in the [`MainScheduleOrder`] to enable state processing.
@@ -226,10 +338,15 @@ impl Plugin for StatesPlugin {
 mod tests {
     use crate::{
         self as bevy_state,
-        state::{State, StateTransition, StateTransitionEvent},
+        state::{NextState, State, StateStack, StateTransition, StateTransitionEvent},
+        state_scoped::{StateScoped, StateScopedCommandsExt},
     };
     use bevy_app::App;
-    use bevy_ecs::event::Events;
+    use bevy_ecs::{
+        event::{Event, Events},
+        observer::Trigger,
+        system::{Commands, ResMut, Resource, RunSystemOnce},
+    };
     use bevy_state_macros::States;
 
     use super::AppExtStates;
@@ -279,4 +396,74 @@ mod tests {
         assert_eq!(last.exited, None);
         assert_eq!(last.entered, Some(TestState::C));
     }
+
+    #[test]
+    fn state_stack_keeps_paused_state_scoped_entities() {
+        let mut app = App::new();
+
+        app.init_state::<TestState>()
+            .enable_state_stack::<TestState>()
+            .enable_state_scoped_entities::<TestState>();
+        let entity = app.world_mut().spawn(StateScoped(TestState::A)).id();
+
+        let world = app.world_mut();
+        world.run_schedule(StateTransition);
+        world
+            .resource_mut::<StateStack<TestState>>()
+            .push(TestState::B);
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::B);
+        assert!(world.get_entity(entity).is_some());
+
+        world.resource_mut::<StateStack<TestState>>().pop();
+        world.run_schedule(StateTransition);
+        world
+            .resource_mut::<StateStack<TestState>>()
+            .replace(TestState::C);
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<TestState>>().0, TestState::C);
+        assert!(world.get_entity(entity).is_none());
+    }
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    #[derive(Event)]
+    struct Increment;
+
+    #[test]
+    fn state_scoped_resources_observers_and_systems() {
+        let mut app = App::new();
+
+        app.init_state::<TestState>()
+            .enable_state_scoped_entities::<TestState>()
+            .enable_state_scoped_resources::<TestState>();
+        let world = app.world_mut();
+        world.run_schedule(StateTransition);
+        let system = world.run_system_once(|mut commands: Commands| {
+            commands.insert_state_scoped_resource(TestState::A, Counter::default());
+            commands.observe_state_scoped(
+                TestState::A,
+                |_: Trigger<Increment>, mut counter: ResMut<Counter>| counter.0 += 1,
+            );
+            commands.register_state_scoped_system(TestState::A, |mut counter: ResMut<Counter>| {
+                counter.0 += 10;
+            })
+        });
+        world.flush();
+
+        world.trigger(Increment);
+        world.run_system(system).unwrap();
+        assert_eq!(world.resource::<Counter>().0, 11);
+
+        world.insert_resource(NextState::Pending(TestState::B));
+        world.run_schedule(StateTransition);
+        assert!(!world.contains_resource::<Counter>());
+        assert!(world.run_system(system).is_err());
+
+        // The observer was despawned along with the resource.
+        world.init_resource::<Counter>();
+        world.trigger(Increment);
+        assert_eq!(world.resource::<Counter>().0, 0);
+    }
 }
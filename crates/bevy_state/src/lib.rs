@@ -26,6 +26,10 @@
 //! - A [`StateTransitionEvent<S>`](crate::state::StateTransitionEvent) that gets fired when a given state changes.
 //! - The [`in_state<S>`](crate::condition::in_state) and [`state_changed<S>`](crate::condition::state_changed) run conditions - which are used
 //!   to determine whether a system should run based on the current state.
+//! - A [`StateStack<S>`](crate::state::StateStack) to push and pop states, pausing the covered ones with the
+//!   [`OnPause<S>`](crate::state::OnPause) and [`OnResume<S>`](crate::state::OnResume) schedules.
+//! - A [`StateTransitionHistory<S>`](crate::state::StateTransitionHistory) recording the transitions of a state,
+//!   which can be walked back with [`undo_state_transition`](crate::state::undo_state_transition).
 
 #[cfg(feature = "bevy_app")]
 /// Provides [`App`](bevy_app::App) and [`SubApp`](bevy_app::SubApp) with state installation methods
@@ -35,7 +39,7 @@ pub mod condition;
 /// Provides definitions for the basic traits required by the state system
 pub mod state;
 
-/// Provides [`StateScoped`] and [`clear_state_scoped_entities`] for managing lifetime of entities.
+/// Provides [`StateScoped`], [`StateScopedResources`] and [`clear_state_scoped_entities`] for managing lifetime of entities and resources.
 pub mod state_scoped;
 
 /// Most commonly used re-exported types.
@@ -47,10 +51,11 @@ pub mod prelude {
     pub use crate::condition::*;
     #[doc(hidden)]
     pub use crate::state::{
-        last_transition, ComputedStates, EnterSchedules, ExitSchedules, NextState, OnEnter, OnExit,
-        OnTransition, State, StateSet, StateTransition, StateTransitionEvent, States, SubStates,
+        last_transition, undo_state_transition, ComputedStates, EnterSchedules, ExitSchedules,
+        NextState, OnEnter, OnExit, OnPause, OnResume, OnTransition, State, StateSet, StateStack,
+        StateTransition, StateTransitionEvent, StateTransitionHistory, States, SubStates,
         TransitionSchedules,
     };
     #[doc(hidden)]
-    pub use crate::state_scoped::StateScoped;
+    pub use crate::state_scoped::{StateScoped, StateScopedCommandsExt};
 }
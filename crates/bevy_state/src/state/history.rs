@@ -0,0 +1,278 @@
+use std::collections::VecDeque;
+
+use bevy_ecs::{
+    event::EventReader,
+    schedule::{IntoSystemConfigs, Schedule},
+    system::{ResMut, Resource},
+};
+
+use super::{
+    freely_mutable_state::FreelyMutableState, resources::NextState, states::States, transitions::*,
+};
+
+/// The recorded transitions of a [`State<S>`](crate::state::State), oldest first.
+///
+/// Identity transitions are not recorded. Once [`capacity`](Self::capacity) transitions are
+/// recorded, the oldest ones are forgotten.
+///
+/// To enable this resource, call
+/// [`enable_state_history`](crate::app::AppExtStates::enable_state_history) on your app.
+/// Its capacity can be changed with [`StateTransitionHistory::set_capacity`].
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     SettingsMenu,
+///     InGame,
+/// }
+///
+/// fn came_from_settings(history: Res<StateTransitionHistory<GameState>>) -> bool {
+///     history.previous() == Some(&GameState::SettingsMenu)
+/// }
+/// ```
+#[derive(Resource, Debug)]
+pub struct StateTransitionHistory<S: States> {
+    transitions: VecDeque<StateTransitionEvent<S>>,
+    capacity: usize,
+    undoing: bool,
+}
+
+impl<S: States> Default for StateTransitionHistory<S> {
+    fn default() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl<S: States> StateTransitionHistory<S> {
+    /// The number of transitions recorded by default.
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// Creates an empty history recording up to `capacity` transitions.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            transitions: VecDeque::with_capacity(capacity),
+            capacity,
+            undoing: false,
+        }
+    }
+
+    /// Returns the maximum number of recorded transitions.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the maximum number of recorded transitions, forgetting the oldest ones if needed.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        let excess = self.transitions.len().saturating_sub(capacity);
+        self.transitions.drain(..excess);
+        self.capacity = capacity;
+    }
+
+    /// Returns the number of recorded transitions.
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Returns `true` if no transition is recorded.
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Iterates over the recorded transitions, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &StateTransitionEvent<S>> {
+        self.transitions.iter()
+    }
+
+    /// Returns the latest recorded transition.
+    pub fn last(&self) -> Option<&StateTransitionEvent<S>> {
+        self.transitions.back()
+    }
+
+    /// Returns the state exited by the latest recorded transition.
+    pub fn previous(&self) -> Option<&S> {
+        self.last()?.exited.as_ref()
+    }
+
+    /// Forgets all the recorded transitions.
+    pub fn clear(&mut self) {
+        self.transitions.clear();
+    }
+
+    fn record(&mut self, transition: &StateTransitionEvent<S>) {
+        let undoing = std::mem::take(&mut self.undoing);
+        if transition.entered == transition.exited {
+            return;
+        }
+        let undone = self.last().is_some_and(|last| {
+            last.exited == transition.entered && last.entered == transition.exited
+        });
+        if undoing && undone {
+            self.transitions.pop_back();
+            return;
+        }
+        if self.capacity == 0 {
+            return;
+        }
+        if self.transitions.len() == self.capacity {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(transition.clone());
+    }
+}
+
+/// Queues a transition back to the state exited by the latest recorded transition of `S`.
+///
+/// Once applied, the undone transition is removed from the [`StateTransitionHistory<S>`], so
+/// running this system repeatedly walks back through the history.
+/// Nothing happens if the history is empty, or if its latest transition didn't exit a state.
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum MenuState {
+///     #[default]
+///     Main,
+///     Settings,
+///     Audio,
+/// }
+///
+/// # let mut schedule = Schedule::default();
+/// # let back_pressed = || true;
+/// schedule.add_systems(undo_state_transition::<MenuState>.run_if(back_pressed));
+/// ```
+pub fn undo_state_transition<S: FreelyMutableState>(
+    mut history: ResMut<StateTransitionHistory<S>>,
+    mut next_state: ResMut<NextState<S>>,
+) {
+    let Some(previous) = history.previous().cloned() else {
+        return;
+    };
+    history.undoing = true;
+    next_state.set(previous);
+}
+
+/// Registers the system recording the transitions of `S` in [`StateTransitionHistory<S>`].
+pub(crate) fn register_state_history_systems<S: States>(schedule: &mut Schedule) {
+    schedule.add_systems(
+        record_state_transitions::<S>
+            .in_set(StateTransitionSteps::DependentTransitions)
+            .after(ApplyStateTransition::<S>::default()),
+    );
+}
+
+fn record_state_transitions<S: States>(
+    mut transitions: EventReader<StateTransitionEvent<S>>,
+    mut history: ResMut<StateTransitionHistory<S>>,
+) {
+    for transition in transitions.read() {
+        history.record(transition);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::event::EventRegistry;
+    use bevy_ecs::prelude::*;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_state_macros::States;
+
+    use super::*;
+    use crate as bevy_state;
+    use crate::state::{setup_state_transitions_in_world, State};
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    enum MenuState {
+        #[default]
+        Main,
+        Settings,
+        Audio,
+    }
+
+    fn world(capacity: usize) -> World {
+        let mut world = World::new();
+        setup_state_transitions_in_world(&mut world, None);
+        EventRegistry::register_event::<StateTransitionEvent<MenuState>>(&mut world);
+        world.init_resource::<State<MenuState>>();
+        world.init_resource::<NextState<MenuState>>();
+        world.insert_resource(StateTransitionHistory::<MenuState>::with_capacity(capacity));
+        let mut schedules = world.resource_mut::<Schedules>();
+        let apply_changes = schedules.get_mut(StateTransition).unwrap();
+        MenuState::register_state(apply_changes);
+        register_state_history_systems::<MenuState>(apply_changes);
+        world
+    }
+
+    fn set(world: &mut World, state: MenuState) {
+        world.resource_mut::<NextState<MenuState>>().set(state);
+        world.run_schedule(StateTransition);
+    }
+
+    fn undo(world: &mut World) {
+        world.run_system_once(undo_state_transition::<MenuState>);
+        world.run_schedule(StateTransition);
+    }
+
+    fn recorded(world: &World) -> Vec<(Option<MenuState>, Option<MenuState>)> {
+        world
+            .resource::<StateTransitionHistory<MenuState>>()
+            .iter()
+            .map(|transition| (transition.exited.clone(), transition.entered.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn records_transitions() {
+        let mut world = world(2);
+
+        set(&mut world, MenuState::Settings);
+        // Identity transitions are not recorded.
+        set(&mut world, MenuState::Settings);
+        assert_eq!(
+            recorded(&world),
+            [(Some(MenuState::Main), Some(MenuState::Settings))]
+        );
+
+        set(&mut world, MenuState::Audio);
+        set(&mut world, MenuState::Main);
+        assert_eq!(
+            recorded(&world),
+            [
+                (Some(MenuState::Settings), Some(MenuState::Audio)),
+                (Some(MenuState::Audio), Some(MenuState::Main)),
+            ]
+        );
+        let history = world.resource::<StateTransitionHistory<MenuState>>();
+        assert_eq!(history.previous(), Some(&MenuState::Audio));
+    }
+
+    #[test]
+    fn undo_walks_back() {
+        let mut world = world(8);
+        set(&mut world, MenuState::Settings);
+        set(&mut world, MenuState::Audio);
+
+        undo(&mut world);
+        assert_eq!(world.resource::<State<MenuState>>().0, MenuState::Settings);
+        undo(&mut world);
+        assert_eq!(world.resource::<State<MenuState>>().0, MenuState::Main);
+        assert!(recorded(&world).is_empty());
+
+        // Nothing left to undo.
+        undo(&mut world);
+        assert_eq!(world.resource::<State<MenuState>>().0, MenuState::Main);
+
+        // Undone transitions are recorded again when redone manually.
+        set(&mut world, MenuState::Audio);
+        assert_eq!(
+            recorded(&world),
+            [(Some(MenuState::Main), Some(MenuState::Audio))]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 mod computed_states;
 mod freely_mutable_state;
+mod history;
 mod resources;
+mod stack;
 mod state_set;
 mod states;
 mod sub_states;
@@ -9,12 +11,17 @@ mod transitions;
 pub use bevy_state_macros::*;
 pub use computed_states::*;
 pub use freely_mutable_state::*;
+pub use history::{undo_state_transition, StateTransitionHistory};
 pub use resources::*;
+pub use stack::{OnPause, OnResume, StateStack, StateStackOperation};
 pub use state_set::*;
 pub use states::*;
 pub use sub_states::*;
 pub use transitions::*;
 
+pub(crate) use history::register_state_history_systems;
+pub(crate) use stack::{is_pausing, register_state_stack_systems};
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs::event::EventRegistry;
@@ -0,0 +1,348 @@
+use bevy_ecs::{
+    schedule::{IntoSystemConfigs, Schedule, ScheduleLabel},
+    system::{In, IntoSystem, Res, ResMut, Resource},
+    world::World,
+};
+use bevy_utils::tracing::warn;
+
+use super::{
+    freely_mutable_state::FreelyMutableState,
+    resources::{NextState, State},
+    states::States,
+    transitions::*,
+};
+
+/// The label of a [`Schedule`] that **only** runs whenever the provided state gets covered by
+/// a state pushed on top of it with [`StateStack::push`].
+///
+/// The covered state doesn't run its [`OnExit`] schedule, and its
+/// [`StateScoped`](crate::state_scoped::StateScoped) entities are kept around.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnPause<S: States>(pub S);
+
+/// The label of a [`Schedule`] that **only** runs whenever the provided state gets uncovered by
+/// a [`StateStack::pop`].
+///
+/// The uncovered state doesn't run its [`OnEnter`] schedule.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OnResume<S: States>(pub S);
+
+/// A change to a [`StateStack`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StateStackOperation<S: States> {
+    /// Pauses the current state and enters the given one on top of it.
+    Push(S),
+    /// Exits the current state and resumes the one below it.
+    Pop,
+    /// Exits the current state and enters the given one, without touching the states below it.
+    Replace(S),
+}
+
+/// Stack-based transitions for a [`State<S>`].
+///
+/// The current state is the top of the stack and is still stored in [`State<S>`], so run
+/// conditions such as [`in_state`](crate::condition::in_state) keep working. Pushing a state
+/// *pauses* the current one instead of exiting it: [`OnPause`] runs in place of [`OnExit`],
+/// and popping back to it runs [`OnResume`] in place of [`OnEnter`].
+///
+/// Like with [`NextState<S>`], the queued operation is applied during the
+/// [`StateTransition`] schedule, and overrides any transition queued in [`NextState<S>`].
+/// Transitions made through [`NextState<S>`] alone behave like [`StateStack::replace`].
+///
+/// To enable this resource, call
+/// [`enable_state_stack`](crate::app::AppExtStates::enable_state_stack) on your app.
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     InGame,
+///     PauseMenu,
+/// }
+///
+/// fn open_pause_menu(mut stack: ResMut<StateStack<GameState>>) {
+///     stack.push(GameState::PauseMenu);
+/// }
+///
+/// fn close_pause_menu(mut stack: ResMut<StateStack<GameState>>) {
+///     stack.pop();
+/// }
+/// ```
+#[derive(Resource, Debug)]
+pub struct StateStack<S: States> {
+    covered: Vec<S>,
+    pending: Option<StateStackOperation<S>>,
+    applied: Option<StateStackOperation<S>>,
+}
+
+impl<S: States> Default for StateStack<S> {
+    fn default() -> Self {
+        Self {
+            covered: Vec::new(),
+            pending: None,
+            applied: None,
+        }
+    }
+}
+
+impl<S: States> StateStack<S> {
+    /// Queues pausing the current state and entering `state` on top of it.
+    pub fn push(&mut self, state: S) {
+        self.pending = Some(StateStackOperation::Push(state));
+    }
+
+    /// Queues exiting the current state and resuming the one below it.
+    ///
+    /// Popping the last state of the stack is ignored with a warning.
+    pub fn pop(&mut self) {
+        self.pending = Some(StateStackOperation::Pop);
+    }
+
+    /// Queues exiting the current state and entering `state` in its place.
+    pub fn replace(&mut self, state: S) {
+        self.pending = Some(StateStackOperation::Replace(state));
+    }
+
+    /// Removes the queued operation, if any.
+    pub fn reset(&mut self) {
+        self.pending = None;
+    }
+
+    /// Returns the queued operation, if any.
+    pub fn pending(&self) -> Option<&StateStackOperation<S>> {
+        self.pending.as_ref()
+    }
+
+    /// Returns the paused states below the current one, from the bottom of the stack.
+    pub fn covered(&self) -> &[S] {
+        &self.covered
+    }
+
+    /// Returns the number of states in the stack, including the current one.
+    pub fn depth(&self) -> usize {
+        self.covered.len() + 1
+    }
+
+    pub(crate) fn is_pushing(&self) -> bool {
+        matches!(self.applied, Some(StateStackOperation::Push(_)))
+    }
+
+    pub(crate) fn is_popping(&self) -> bool {
+        matches!(self.applied, Some(StateStackOperation::Pop))
+    }
+}
+
+/// Returns `true` if the transition of `S` being applied pauses the exited state instead of
+/// exiting it.
+pub(crate) fn is_pausing<S: States>(world: &World) -> bool {
+    world
+        .get_resource::<StateStack<S>>()
+        .is_some_and(StateStack::is_pushing)
+}
+
+/// Returns `true` if the transition of `S` being applied resumes the entered state instead of
+/// entering it.
+pub(crate) fn is_resuming<S: States>(world: &World) -> bool {
+    world
+        .get_resource::<StateStack<S>>()
+        .is_some_and(StateStack::is_popping)
+}
+
+/// Registers the systems applying the operations of [`StateStack<S>`] and running the
+/// [`OnPause`] and [`OnResume`] schedules.
+pub(crate) fn register_state_stack_systems<S: FreelyMutableState>(schedule: &mut Schedule) {
+    schedule
+        .add_systems(
+            apply_state_stack::<S>
+                .in_set(StateTransitionSteps::DependentTransitions)
+                .before(ApplyStateTransition::<S>::default()),
+        )
+        .add_systems(
+            last_transition::<S>
+                .pipe(run_pause::<S>)
+                .in_set(ExitSchedules::<S>::default()),
+        )
+        .add_systems(
+            last_transition::<S>
+                .pipe(run_resume::<S>)
+                .in_set(EnterSchedules::<S>::default()),
+        );
+}
+
+/// Turns the operation queued in [`StateStack<S>`] into a pending [`NextState<S>`].
+fn apply_state_stack<S: FreelyMutableState>(
+    mut stack: ResMut<StateStack<S>>,
+    current_state: Option<Res<State<S>>>,
+    next_state: Option<ResMut<NextState<S>>>,
+) {
+    if stack.pending.is_none() && stack.applied.is_none() {
+        return;
+    }
+    stack.applied = None;
+    let Some(operation) = stack.pending.take() else {
+        return;
+    };
+    let (Some(current_state), Some(mut next_state)) = (current_state, next_state) else {
+        return;
+    };
+    match &operation {
+        StateStackOperation::Push(state) => {
+            stack.covered.push(current_state.get().clone());
+            next_state.set(state.clone());
+        }
+        StateStackOperation::Pop => {
+            let Some(state) = stack.covered.pop() else {
+                warn!(
+                    "Tried to pop the last state of the `StateStack<{}>`.",
+                    std::any::type_name::<S>()
+                );
+                return;
+            };
+            next_state.set(state);
+        }
+        StateStackOperation::Replace(state) => next_state.set(state.clone()),
+    }
+    stack.applied = Some(operation);
+}
+
+fn run_pause<S: States>(transition: In<Option<StateTransitionEvent<S>>>, world: &mut World) {
+    let Some(transition) = transition.0 else {
+        return;
+    };
+    if transition.entered == transition.exited || !is_pausing::<S>(world) {
+        return;
+    }
+    let Some(exited) = transition.exited else {
+        return;
+    };
+
+    let _ = world.try_run_schedule(OnPause(exited));
+}
+
+fn run_resume<S: States>(transition: In<Option<StateTransitionEvent<S>>>, world: &mut World) {
+    let Some(transition) = transition.0 else {
+        return;
+    };
+    if transition.entered == transition.exited || !is_resuming::<S>(world) {
+        return;
+    }
+    let Some(entered) = transition.entered else {
+        return;
+    };
+
+    let _ = world.try_run_schedule(OnResume(entered));
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::event::EventRegistry;
+    use bevy_ecs::prelude::*;
+    use bevy_state_macros::States;
+
+    use super::*;
+    use crate as bevy_state;
+    use crate::state::setup_state_transitions_in_world;
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    enum GameState {
+        #[default]
+        InGame,
+        PauseMenu,
+        Settings,
+    }
+
+    #[derive(Resource, Default, Debug)]
+    struct TransitionTracker(Vec<(&'static str, &'static str)>);
+
+    fn track(world: &mut World, label: impl ScheduleLabel, name: (&'static str, &'static str)) {
+        let mut schedule = Schedule::new(label);
+        schedule.add_systems(move |mut tracker: ResMut<TransitionTracker>| tracker.0.push(name));
+        world.add_schedule(schedule);
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        setup_state_transitions_in_world(&mut world, None);
+        EventRegistry::register_event::<StateTransitionEvent<GameState>>(&mut world);
+        world.init_resource::<State<GameState>>();
+        world.init_resource::<NextState<GameState>>();
+        world.init_resource::<StateStack<GameState>>();
+        world.init_resource::<TransitionTracker>();
+        let mut schedules = world.resource_mut::<Schedules>();
+        let apply_changes = schedules.get_mut(StateTransition).unwrap();
+        GameState::register_state(apply_changes);
+        register_state_stack_systems::<GameState>(apply_changes);
+
+        for (state, name) in [
+            (GameState::InGame, "game"),
+            (GameState::PauseMenu, "pause menu"),
+            (GameState::Settings, "settings"),
+        ] {
+            track(&mut world, OnEnter(state.clone()), ("enter", name));
+            track(&mut world, OnExit(state.clone()), ("exit", name));
+            track(&mut world, OnPause(state.clone()), ("pause", name));
+            track(&mut world, OnResume(state), ("resume", name));
+        }
+        world
+    }
+
+    fn transition(world: &mut World, operation: impl FnOnce(&mut StateStack<GameState>)) {
+        operation(&mut world.resource_mut::<StateStack<GameState>>());
+        world.resource_mut::<TransitionTracker>().0.clear();
+        world.run_schedule(StateTransition);
+    }
+
+    fn tracked(world: &World) -> &[(&'static str, &'static str)] {
+        &world.resource::<TransitionTracker>().0
+    }
+
+    #[test]
+    fn push_pauses_and_pop_resumes() {
+        let mut world = world();
+
+        transition(&mut world, |stack| stack.push(GameState::PauseMenu));
+        assert_eq!(world.resource::<State<GameState>>().0, GameState::PauseMenu);
+        assert_eq!(
+            tracked(&world),
+            [("pause", "game"), ("enter", "pause menu")]
+        );
+        let stack = world.resource::<StateStack<GameState>>();
+        assert_eq!(stack.covered(), [GameState::InGame]);
+        assert_eq!(stack.depth(), 2);
+
+        transition(&mut world, |stack| stack.replace(GameState::Settings));
+        assert_eq!(world.resource::<State<GameState>>().0, GameState::Settings);
+        assert_eq!(
+            tracked(&world),
+            [("exit", "pause menu"), ("enter", "settings")]
+        );
+
+        transition(&mut world, StateStack::pop);
+        assert_eq!(world.resource::<State<GameState>>().0, GameState::InGame);
+        assert_eq!(tracked(&world), [("exit", "settings"), ("resume", "game")]);
+        assert!(world
+            .resource::<StateStack<GameState>>()
+            .covered()
+            .is_empty());
+    }
+
+    #[test]
+    fn pop_last_state_is_ignored() {
+        let mut world = world();
+
+        transition(&mut world, StateStack::pop);
+        assert_eq!(world.resource::<State<GameState>>().0, GameState::InGame);
+        assert!(tracked(&world).is_empty());
+
+        // Plain `NextState` transitions are replacements.
+        world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Settings);
+        transition(&mut world, |_| {});
+        assert_eq!(tracked(&world), [("exit", "game"), ("enter", "settings")]);
+        assert_eq!(world.resource::<StateStack<GameState>>().depth(), 1);
+    }
+}
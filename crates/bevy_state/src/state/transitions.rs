@@ -9,7 +9,11 @@ use bevy_ecs::{
     world::World,
 };
 
-use super::{resources::State, states::States};
+use super::{
+    resources::State,
+    stack::{is_pausing, is_resuming},
+    states::States,
+};
 
 /// The label of a [`Schedule`] that **only** runs whenever [`State<S>`] enters the provided state.
 ///
@@ -222,7 +226,8 @@ pub(crate) fn run_enter<S: States>(
     let Some(transition) = transition.0 else {
         return;
     };
-    if transition.entered == transition.exited {
+    // States resumed by a `StateStack` were never exited.
+    if transition.entered == transition.exited || is_resuming::<S>(world) {
         return;
     }
     let Some(entered) = transition.entered else {
@@ -239,7 +244,8 @@ pub(crate) fn run_exit<S: States>(
     let Some(transition) = transition.0 else {
         return;
     };
-    if transition.entered == transition.exited {
+    // States paused by a `StateStack` are not exited.
+    if transition.entered == transition.exited || is_pausing::<S>(world) {
         return;
     }
     let Some(exited) = transition.exited else {
@@ -1,13 +1,21 @@
+use std::any::TypeId;
+
 use bevy_ecs::{
+    bundle::Bundle,
     component::Component,
     entity::Entity,
-    event::EventReader,
-    system::{Commands, Query},
+    event::{Event, EventReader},
+    system::{
+        Commands, EntityCommands, In, IntoObserverSystem, IntoSystem, Query, Res, Resource,
+        SystemId,
+    },
+    world::World,
 };
 #[cfg(feature = "bevy_hierarchy")]
 use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_utils::tracing::warn;
 
-use crate::state::{StateTransitionEvent, States};
+use crate::state::{is_pausing, StateStack, StateTransitionEvent, States};
 
 /// Entities marked with this component will be removed
 /// when the world's state of the matching type no longer matches the supplied value.
@@ -58,10 +66,13 @@ pub struct StateScoped<S: States>(pub S);
 /// Removes entities marked with [`StateScoped<S>`]
 /// when their state no longer matches the world state.
 ///
+/// Entities of a state paused by a [`StateStack<S>`] are kept.
+///
 /// If `bevy_hierarchy` feature is enabled, which it is by default, the despawn will be recursive.
 pub fn clear_state_scoped_entities<S: States>(
     mut commands: Commands,
     mut transitions: EventReader<StateTransitionEvent<S>>,
+    stack: Option<Res<StateStack<S>>>,
     query: Query<(Entity, &StateScoped<S>)>,
 ) {
     // We use the latest event, because state machine internals generate at most 1
@@ -70,7 +81,7 @@ pub fn clear_state_scoped_entities<S: States>(
     let Some(transition) = transitions.read().last() else {
         return;
     };
-    if transition.entered == transition.exited {
+    if transition.entered == transition.exited || stack.is_some_and(|stack| stack.is_pushing()) {
         return;
     }
     let Some(exited) = &transition.exited else {
@@ -85,3 +96,183 @@ pub fn clear_state_scoped_entities<S: States>(
         }
     }
 }
+
+/// The resources removed when the world's state of type `S` exits the state they are scoped to.
+///
+/// To enable this feature remember to configure your application
+/// with [`enable_state_scoped_resources`](crate::app::AppExtStates::enable_state_scoped_resources) on your state(s) of choice.
+/// Resources are usually scoped when inserted with [`StateScopedCommandsExt::insert_state_scoped_resource`].
+///
+/// The resources are removed after the [`OnExit`](crate::state::OnExit) schedule runs, so its systems can still access them.
+/// Resources of a state paused by a [`StateStack<S>`] are kept.
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// #[derive(Resource, Default)]
+/// struct Score(u32);
+///
+/// fn start_level(mut commands: Commands) {
+///     commands.insert_state_scoped_resource(GameState::InGame, Score::default());
+/// }
+///
+/// # struct AppMock;
+/// # impl AppMock {
+/// #     fn init_state<S>(&mut self) {}
+/// #     fn enable_state_scoped_resources<S>(&mut self) {}
+/// #     fn add_systems<S, M>(&mut self, schedule: S, systems: impl IntoSystemConfigs<M>) {}
+/// # }
+/// # let mut app = AppMock;
+///
+/// app.init_state::<GameState>();
+/// app.enable_state_scoped_resources::<GameState>();
+/// app.add_systems(OnEnter(GameState::InGame), start_level);
+/// ```
+#[derive(Resource, Debug)]
+pub struct StateScopedResources<S: States> {
+    scoped: Vec<(S, TypeId, fn(&mut World))>,
+}
+
+impl<S: States> Default for StateScopedResources<S> {
+    fn default() -> Self {
+        Self { scoped: Vec::new() }
+    }
+}
+
+impl<S: States> StateScopedResources<S> {
+    /// Removes the resource `R` when the world's state exits `state`.
+    pub fn scope<R: Resource>(&mut self, state: S) {
+        if !self.is_scoped::<R>(&state) {
+            self.scoped
+                .push((state, TypeId::of::<R>(), remove_resource::<R>));
+        }
+    }
+
+    /// Returns `true` if the resource `R` is removed when the world's state exits `state`.
+    pub fn is_scoped<R: Resource>(&self, state: &S) -> bool {
+        self.scoped
+            .iter()
+            .any(|(scope, type_id, _)| scope == state && *type_id == TypeId::of::<R>())
+    }
+}
+
+fn remove_resource<R: Resource>(world: &mut World) {
+    world.remove_resource::<R>();
+}
+
+/// Removes the resources scoped in [`StateScopedResources<S>`]
+/// when their state no longer matches the world state.
+pub fn clear_state_scoped_resources<S: States>(
+    transition: In<Option<StateTransitionEvent<S>>>,
+    world: &mut World,
+) {
+    let Some(transition) = transition.0 else {
+        return;
+    };
+    if transition.entered == transition.exited || is_pausing::<S>(world) {
+        return;
+    }
+    let Some(exited) = transition.exited else {
+        return;
+    };
+    let Some(mut resources) = world.get_resource_mut::<StateScopedResources<S>>() else {
+        return;
+    };
+    let mut removed = Vec::new();
+    resources.scoped.retain(|(scope, _, remove)| {
+        if *scope == exited {
+            removed.push(*remove);
+        }
+        *scope != exited
+    });
+    for remove in removed {
+        remove(world);
+    }
+}
+
+/// Extension trait for [`Commands`] to add resources, observers and one-shot systems
+/// whose lifetime is bound to a state.
+pub trait StateScopedCommandsExt {
+    /// Inserts a resource that is removed when the world's state exits `state`.
+    ///
+    /// This requires [`enable_state_scoped_resources`](crate::app::AppExtStates::enable_state_scoped_resources),
+    /// see [`StateScopedResources`].
+    fn insert_state_scoped_resource<S: States, R: Resource>(&mut self, state: S, resource: R);
+
+    /// Spawns an [`Observer`](bevy_ecs::observer::Observer) that is despawned when the world's state exits `state`.
+    ///
+    /// This requires [`enable_state_scoped_entities`](crate::app::AppExtStates::enable_state_scoped_entities),
+    /// see [`StateScoped`].
+    fn observe_state_scoped<S: States, E: Event, B: Bundle, M>(
+        &mut self,
+        state: S,
+        observer: impl IntoObserverSystem<E, B, M>,
+    ) -> EntityCommands;
+
+    /// Registers a one-shot system that is removed when the world's state exits `state`.
+    /// The returned [`SystemId`] becomes invalid afterwards.
+    ///
+    /// This requires [`enable_state_scoped_entities`](crate::app::AppExtStates::enable_state_scoped_entities),
+    /// see [`StateScoped`].
+    fn register_state_scoped_system<
+        S: States,
+        I: 'static + Send,
+        O: 'static + Send,
+        M,
+        T: IntoSystem<I, O, M> + 'static,
+    >(
+        &mut self,
+        state: S,
+        system: T,
+    ) -> SystemId<I, O>;
+}
+
+impl StateScopedCommandsExt for Commands<'_, '_> {
+    fn insert_state_scoped_resource<S: States, R: Resource>(&mut self, state: S, resource: R) {
+        self.add(move |world: &mut World| {
+            world.insert_resource(resource);
+            match world.get_resource_mut::<StateScopedResources<S>>() {
+                Some(mut resources) => resources.scope::<R>(state),
+                None => warn!(
+                    "Resource `{}` is scoped to state `{}`, but state scoped resources aren't enabled for it!",
+                    std::any::type_name::<R>(),
+                    std::any::type_name::<S>()
+                ),
+            }
+        });
+    }
+
+    fn observe_state_scoped<S: States, E: Event, B: Bundle, M>(
+        &mut self,
+        state: S,
+        observer: impl IntoObserverSystem<E, B, M>,
+    ) -> EntityCommands {
+        let mut entity = self.observe(observer);
+        entity.insert(StateScoped(state));
+        entity
+    }
+
+    fn register_state_scoped_system<
+        S: States,
+        I: 'static + Send,
+        O: 'static + Send,
+        M,
+        T: IntoSystem<I, O, M> + 'static,
+    >(
+        &mut self,
+        state: S,
+        system: T,
+    ) -> SystemId<I, O> {
+        let id = self.register_one_shot_system(system);
+        self.entity(id.entity()).insert(StateScoped(state));
+        id
+    }
+}
@@ -11,6 +11,9 @@ pub use slice::{ParallelSlice, ParallelSliceMut};
 mod task;
 pub use task::Task;
 
+mod priority;
+pub use priority::TaskPriority;
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
 mod task_pool;
 #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
@@ -0,0 +1,38 @@
+/// The priority of a task spawned on a [`TaskPool`](crate::TaskPool).
+///
+/// Whenever a thread of the pool is free, it runs a task of the highest priority that is ready,
+/// so a steady stream of [`TaskPriority::Background`] tasks can't delay [`TaskPriority::High`]
+/// ones by more than the time it takes to poll a task. Running tasks are never interrupted.
+///
+/// The number of threads running tasks of each priority at the same time can be limited with
+/// [`TaskPoolBuilder::max_concurrency`](crate::TaskPoolBuilder::max_concurrency).
+///
+/// On the single threaded task pool, priorities are ignored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskPriority {
+    /// Latency sensitive work, such as work needed to present the next frame.
+    High,
+    /// The priority of [`TaskPool::spawn`](crate::TaskPool::spawn) and
+    /// [`Scope::spawn`](crate::Scope::spawn).
+    #[default]
+    Normal,
+    /// Work that can be delayed, such as streaming or pathfinding.
+    Background,
+}
+
+impl TaskPriority {
+    /// All the priorities, from the highest to the lowest.
+    pub const ALL: [TaskPriority; 3] = [
+        TaskPriority::High,
+        TaskPriority::Normal,
+        TaskPriority::Background,
+    ];
+
+    #[cfg_attr(
+        any(target_arch = "wasm32", not(feature = "multi_threaded")),
+        allow(dead_code)
+    )]
+    pub(crate) const fn index(self) -> usize {
+        self as usize
+    }
+}
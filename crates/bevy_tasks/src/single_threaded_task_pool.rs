@@ -1,6 +1,8 @@
 use std::sync::Arc;
 use std::{cell::RefCell, future::Future, marker::PhantomData, mem, rc::Rc};
 
+use crate::TaskPriority;
+
 thread_local! {
     static LOCAL_EXECUTOR: async_executor::LocalExecutor<'static> = const { async_executor::LocalExecutor::new() };
 }
@@ -44,6 +46,11 @@ impl TaskPoolBuilder {
         self
     }
 
+    /// No op on the single threaded task pool
+    pub fn max_concurrency(self, _priority: TaskPriority, _max_concurrency: usize) -> Self {
+        self
+    }
+
     /// Creates a new [`TaskPool`]
     pub fn build(self) -> TaskPool {
         TaskPool::new_internal()
@@ -171,6 +178,19 @@ impl TaskPool {
         FakeTask
     }
 
+    /// Spawns a static future onto the thread pool. This is exactly the same as [`TaskPool::spawn`],
+    /// since priorities are ignored on the single threaded task pool.
+    pub fn spawn_with_priority<T>(
+        &self,
+        _priority: TaskPriority,
+        future: impl Future<Output = T> + 'static,
+    ) -> FakeTask
+    where
+        T: 'static,
+    {
+        self.spawn(future)
+    }
+
     /// Spawns a static future on the JS event loop. This is exactly the same as [`TaskPool::spawn`].
     pub fn spawn_local<T>(&self, future: impl Future<Output = T> + 'static) -> FakeTask
     where
@@ -235,6 +255,19 @@ impl<'scope, 'env, T: Send + 'env> Scope<'scope, 'env, T> {
         self.spawn_on_scope(f);
     }
 
+    /// Spawns a scoped future onto the executor, ignoring the priority.
+    ///
+    /// On the single threaded task pool, it just calls [`Scope::spawn_on_scope`].
+    ///
+    /// For more information, see [`TaskPool::scope`].
+    pub fn spawn_with_priority<Fut: Future<Output = T> + 'scope>(
+        &self,
+        _priority: TaskPriority,
+        f: Fut,
+    ) {
+        self.spawn_on_scope(f);
+    }
+
     /// Spawns a scoped future onto the executor. The scope *must* outlive
     /// the provided future. The results of the future will be returned as a part of
     /// [`TaskPool::scope`]'s return value.
@@ -1,20 +1,25 @@
 use std::{
+    cell::RefCell,
     future::Future,
     marker::PhantomData,
     mem,
     panic::AssertUnwindSafe,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
     thread::{self, JoinHandle},
 };
 
 use async_executor::FallibleTask;
 use concurrent_queue::ConcurrentQueue;
-use futures_lite::FutureExt;
+use futures_lite::{future, FutureExt};
 
 use crate::{
     block_on,
     thread_executor::{ThreadExecutor, ThreadExecutorTicker},
-    Task,
+    Task, TaskPriority,
 };
 
 struct CallOnDrop(Option<Arc<dyn Fn() + Send + Sync + 'static>>);
@@ -42,6 +47,9 @@ pub struct TaskPoolBuilder {
 
     on_thread_spawn: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
     on_thread_destroy: Option<Arc<dyn Fn() + Send + Sync + 'static>>,
+
+    /// The maximum number of threads running tasks of each [`TaskPriority`] at the same time.
+    max_concurrency: [Option<usize>; 3],
 }
 
 impl TaskPoolBuilder {
@@ -88,6 +96,17 @@ impl TaskPoolBuilder {
         self
     }
 
+    /// Limits how many threads of the pool can run tasks of the given [`TaskPriority`] at the
+    /// same time. If unset, all the threads can.
+    ///
+    /// This keeps some threads free for tasks of the other priorities, for example to stop
+    /// [`TaskPriority::Background`] tasks from taking over the pool. A limit of `0` is treated
+    /// as `1`, so that the tasks still make progress.
+    pub fn max_concurrency(mut self, priority: TaskPriority, max_concurrency: usize) -> Self {
+        self.max_concurrency[priority.index()] = Some(max_concurrency.max(1));
+        self
+    }
+
     /// Creates a new [`TaskPool`] based on the current options.
     pub fn build(self) -> TaskPool {
         TaskPool::new_internal(self)
@@ -104,10 +123,13 @@ impl TaskPoolBuilder {
 ///
 /// If the result is not required, one may also use [`Task::detach`] and the pool
 /// will still execute a task, even if it is dropped.
+///
+/// Tasks can be given a [`TaskPriority`] with [`TaskPool::spawn_with_priority`] and
+/// [`Scope::spawn_with_priority`].
 #[derive(Debug)]
 pub struct TaskPool {
-    /// The executor for the pool.
-    executor: Arc<async_executor::Executor<'static>>,
+    /// The executors for the pool, one per [`TaskPriority`].
+    executors: Arc<PriorityExecutors<'static>>,
 
     // The inner state of the pool.
     threads: Vec<JoinHandle<()>>,
@@ -133,7 +155,7 @@ impl TaskPool {
     fn new_internal(builder: TaskPoolBuilder) -> Self {
         let (shutdown_tx, shutdown_rx) = async_channel::unbounded::<()>();
 
-        let executor = Arc::new(async_executor::Executor::new());
+        let executors = Arc::new(PriorityExecutors::new(builder.max_concurrency));
 
         let num_threads = builder
             .num_threads
@@ -141,7 +163,7 @@ impl TaskPool {
 
         let threads = (0..num_threads)
             .map(|i| {
-                let ex = Arc::clone(&executor);
+                let executors = Arc::clone(&executors);
                 let shutdown_rx = shutdown_rx.clone();
 
                 let thread_name = if let Some(thread_name) = builder.thread_name.as_deref() {
@@ -173,7 +195,7 @@ impl TaskPool {
                                             local_executor.tick().await;
                                         }
                                     };
-                                    block_on(ex.run(tick_forever.or(shutdown_rx.recv())))
+                                    block_on(executors.run(tick_forever.or(shutdown_rx.recv())))
                                 });
                                 if let Ok(value) = res {
                                     // Use unwrap_err because we expect a Closed error
@@ -188,7 +210,7 @@ impl TaskPool {
             .collect();
 
         Self {
-            executor,
+            executors,
             threads,
             shutdown_tx,
         }
@@ -349,9 +371,9 @@ impl TaskPool {
         // transmute the lifetimes to 'env here to appease the compiler as it is unable to validate safety.
         // Any usages of the references passed into `Scope` must be accessed through
         // the transmuted reference for the rest of this function.
-        let executor: &async_executor::Executor = &self.executor;
+        let executors: &PriorityExecutors = &self.executors;
         // SAFETY: As above, all futures must complete in this function so we can change the lifetime
-        let executor: &'env async_executor::Executor = unsafe { mem::transmute(executor) };
+        let executors: &'env PriorityExecutors = unsafe { mem::transmute(executors) };
         // SAFETY: As above, all futures must complete in this function so we can change the lifetime
         let external_executor: &'env ThreadExecutor<'env> =
             unsafe { mem::transmute(external_executor) };
@@ -366,7 +388,7 @@ impl TaskPool {
         > = unsafe { mem::transmute(&spawned) };
 
         let scope = Scope {
-            executor,
+            executors,
             external_executor,
             scope_executor,
             spawned,
@@ -414,7 +436,7 @@ impl TaskPool {
                 match (external_ticker, tick_task_pool_executor) {
                     (Some(external_ticker), true) => {
                         Self::execute_global_external_scope(
-                            executor,
+                            executors,
                             external_ticker,
                             scope_ticker,
                             get_results,
@@ -427,7 +449,7 @@ impl TaskPool {
                     }
                     // either external_executor is none or it is same as scope_executor
                     (None, true) => {
-                        Self::execute_global_scope(executor, scope_ticker, get_results).await
+                        Self::execute_global_scope(executors, scope_ticker, get_results).await
                     }
                     (None, false) => Self::execute_scope(scope_ticker, get_results).await,
                 }
@@ -437,7 +459,7 @@ impl TaskPool {
 
     #[inline]
     async fn execute_global_external_scope<'scope, 'ticker, T>(
-        executor: &'scope async_executor::Executor<'scope>,
+        executors: &'scope PriorityExecutors<'scope>,
         external_ticker: ThreadExecutorTicker<'scope, 'ticker>,
         scope_ticker: ThreadExecutorTicker<'scope, 'ticker>,
         get_results: impl Future<Output = Vec<T>>,
@@ -453,7 +475,7 @@ impl TaskPool {
                 };
                 // we don't care if it errors. If a scoped task errors it will propagate
                 // to get_results
-                let _result = AssertUnwindSafe(executor.run(tick_forever))
+                let _result = AssertUnwindSafe(executors.run(tick_forever))
                     .catch_unwind()
                     .await
                     .is_ok();
@@ -483,7 +505,7 @@ impl TaskPool {
 
     #[inline]
     async fn execute_global_scope<'scope, 'ticker, T>(
-        executor: &'scope async_executor::Executor<'scope>,
+        executors: &'scope PriorityExecutors<'scope>,
         scope_ticker: ThreadExecutorTicker<'scope, 'ticker>,
         get_results: impl Future<Output = Vec<T>>,
     ) -> Vec<T> {
@@ -494,7 +516,7 @@ impl TaskPool {
                         scope_ticker.tick().await;
                     }
                 };
-                let _result = AssertUnwindSafe(executor.run(tick_forever))
+                let _result = AssertUnwindSafe(executors.run(tick_forever))
                     .catch_unwind()
                     .await
                     .is_ok();
@@ -529,11 +551,27 @@ impl TaskPool {
     ///
     /// If the provided future is non-`Send`, [`TaskPool::spawn_local`] should
     /// be used instead.
+    ///
+    /// The task has the [`TaskPriority::Normal`] priority.
     pub fn spawn<T>(&self, future: impl Future<Output = T> + Send + 'static) -> Task<T>
     where
         T: Send + 'static,
     {
-        Task::new(self.executor.spawn(future))
+        self.spawn_with_priority(TaskPriority::Normal, future)
+    }
+
+    /// Spawns a static future onto the thread pool with the given [`TaskPriority`].
+    ///
+    /// See [`TaskPool::spawn`] for more details.
+    pub fn spawn_with_priority<T>(
+        &self,
+        priority: TaskPriority,
+        future: impl Future<Output = T> + Send + 'static,
+    ) -> Task<T>
+    where
+        T: Send + 'static,
+    {
+        Task::new(self.executors.spawn(priority, future))
     }
 
     /// Spawns a static future on the thread-local async executor for the
@@ -598,7 +636,7 @@ impl Drop for TaskPool {
 /// For more information, see [`TaskPool::scope`].
 #[derive(Debug)]
 pub struct Scope<'scope, 'env: 'scope, T> {
-    executor: &'scope async_executor::Executor<'scope>,
+    executors: &'scope PriorityExecutors<'scope>,
     external_executor: &'scope ThreadExecutor<'scope>,
     scope_executor: &'scope ThreadExecutor<'scope>,
     spawned: &'scope ConcurrentQueue<FallibleTask<Result<T, Box<(dyn std::any::Any + Send)>>>>,
@@ -615,11 +653,24 @@ impl<'scope, 'env, T: Send + 'scope> Scope<'scope, 'env, T> {
     /// For futures that should run on the thread `scope` is called on [`Scope::spawn_on_scope`] should be used
     /// instead.
     ///
+    /// The task has the [`TaskPriority::Normal`] priority.
+    ///
     /// For more information, see [`TaskPool::scope`].
     pub fn spawn<Fut: Future<Output = T> + 'scope + Send>(&self, f: Fut) {
+        self.spawn_with_priority(TaskPriority::Normal, f);
+    }
+
+    /// Spawns a scoped future onto the thread pool with the given [`TaskPriority`].
+    ///
+    /// See [`Scope::spawn`] for more details.
+    pub fn spawn_with_priority<Fut: Future<Output = T> + 'scope + Send>(
+        &self,
+        priority: TaskPriority,
+        f: Fut,
+    ) {
         let task = self
-            .executor
-            .spawn(AssertUnwindSafe(f).catch_unwind())
+            .executors
+            .spawn(priority, AssertUnwindSafe(f).catch_unwind())
             .fallible();
         // ConcurrentQueue only errors when closed or full, but we never
         // close and use an unbounded queue, so it is safe to unwrap
@@ -660,6 +711,143 @@ impl<'scope, 'env, T: Send + 'scope> Scope<'scope, 'env, T> {
     }
 }
 
+/// The executors of a [`TaskPool`], one per [`TaskPriority`].
+#[derive(Debug)]
+struct PriorityExecutors<'a> {
+    executors: [async_executor::Executor<'a>; 3],
+    limits: [Option<Arc<ConcurrencyLimit>>; 3],
+}
+
+impl<'a> PriorityExecutors<'a> {
+    fn new(max_concurrency: [Option<usize>; 3]) -> Self {
+        Self {
+            executors: Default::default(),
+            limits: max_concurrency.map(|max| {
+                max.map(|max| {
+                    Arc::new(ConcurrencyLimit {
+                        max,
+                        active: AtomicUsize::new(0),
+                        waiters: Mutex::new(Vec::new()),
+                    })
+                })
+            }),
+        }
+    }
+
+    fn spawn<T: Send + 'a>(
+        &self,
+        priority: TaskPriority,
+        future: impl Future<Output = T> + Send + 'a,
+    ) -> async_executor::Task<T> {
+        let executor = &self.executors[priority.index()];
+        match &self.limits[priority.index()] {
+            Some(limit) => executor.spawn(limit.clone().limit(future)),
+            None => executor.spawn(future),
+        }
+    }
+
+    /// Runs the executors until `future` completes.
+    ///
+    /// High priority tasks run through [`Executor::run`](async_executor::Executor::run), so that
+    /// threads steal them from each other. The other tasks run one at a time when no high priority
+    /// task is ready, normal priority first.
+    async fn run<T>(&self, future: impl Future<Output = T>) -> T {
+        let [high, normal, background] = &self.executors;
+        let lower_priorities = async {
+            loop {
+                // `high.run` polls this before its own tasks, so yield to let those go first.
+                future::yield_now().await;
+                if !(normal.try_tick() || background.try_tick()) {
+                    normal.tick().or(background.tick()).await;
+                }
+            }
+        };
+        high.run(future.or(lower_priorities)).await
+    }
+}
+
+thread_local! {
+    /// The concurrency limits of the tasks running on this thread.
+    static HELD_LIMITS: RefCell<Vec<*const ConcurrencyLimit>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Limits the number of threads running the tasks of an executor.
+///
+/// A task only holds a permit while it is polled, so waiting tasks and the tasks of other priorities
+/// never hold up the limit.
+#[derive(Debug)]
+struct ConcurrencyLimit {
+    max: usize,
+    active: AtomicUsize,
+    /// The tasks waiting for a permit, which are woken when one is released.
+    waiters: Mutex<Vec<Waker>>,
+}
+
+impl ConcurrencyLimit {
+    /// Wraps `future` so that it is only polled while holding a permit.
+    fn limit<T>(self: Arc<Self>, future: impl Future<Output = T>) -> impl Future<Output = T> {
+        let mut future = Box::pin(future);
+        future::poll_fn(move |cx| {
+            // A thread already running a task of this priority, for example one that runs a scope,
+            // doesn't take another permit for the tasks it runs in the meantime.
+            if self.is_held_by_current_thread() {
+                return future.as_mut().poll(cx);
+            }
+            match self.poll_acquire(cx) {
+                Poll::Ready(_permit) => future.as_mut().poll(cx),
+                Poll::Pending => Poll::Pending,
+            }
+        })
+    }
+
+    fn is_held_by_current_thread(&self) -> bool {
+        let limit: *const Self = self;
+        HELD_LIMITS.with(|held| held.borrow().contains(&limit))
+    }
+
+    fn try_acquire(&self) -> Option<ConcurrencyPermit<'_>> {
+        self.active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| {
+                (active < self.max).then_some(active + 1)
+            })
+            .ok()?;
+        HELD_LIMITS.with(|held| held.borrow_mut().push(self));
+        Some(ConcurrencyPermit(self))
+    }
+
+    fn poll_acquire(&self, cx: &mut Context<'_>) -> Poll<ConcurrencyPermit<'_>> {
+        if let Some(permit) = self.try_acquire() {
+            return Poll::Ready(permit);
+        }
+        self.waiters.lock().unwrap().push(cx.waker().clone());
+        // A permit may have been released before the waker was stored.
+        match self.try_acquire() {
+            Some(permit) => Poll::Ready(permit),
+            None => Poll::Pending,
+        }
+    }
+}
+
+struct ConcurrencyPermit<'a>(&'a ConcurrencyLimit);
+
+impl Drop for ConcurrencyPermit<'_> {
+    fn drop(&mut self) {
+        let limit: *const ConcurrencyLimit = self.0;
+        HELD_LIMITS.with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(i) = held.iter().rposition(|held| *held == limit) {
+                held.remove(i);
+            }
+        });
+        self.0.active.fetch_sub(1, Ordering::Release);
+        // All the waiters are woken, as some of them may have been cancelled or already woken.
+        let waiters = mem::take(&mut *self.0.waiters.lock().unwrap());
+        for waiter in waiters {
+            waiter.wake();
+        }
+    }
+}
+
 impl<'scope, 'env, T> Drop for Scope<'scope, 'env, T>
 where
     T: 'scope,
@@ -922,6 +1110,90 @@ mod tests {
         assert_eq!(count.load(Ordering::Acquire), 200);
     }
 
+    #[test]
+    fn test_priorities() {
+        let pool = TaskPoolBuilder::new().num_threads(1).build();
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        // Keep the only thread busy while the other tasks are spawned.
+        let started = Arc::new(Barrier::new(2));
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let blocker = {
+            let started = started.clone();
+            pool.spawn(async move {
+                started.wait();
+                release_rx.recv().unwrap();
+            })
+        };
+        started.wait();
+
+        let tasks: Vec<_> = [TaskPriority::Background, TaskPriority::High]
+            .into_iter()
+            .flat_map(|priority| (0..5).map(move |_| priority))
+            .map(|priority| {
+                let order = order.clone();
+                pool.spawn_with_priority(priority, async move {
+                    order.lock().unwrap().push(priority);
+                })
+            })
+            .collect();
+        release_tx.send(()).unwrap();
+
+        block_on(blocker);
+        for task in tasks {
+            block_on(task);
+        }
+        let order = order.lock().unwrap();
+        assert_eq!(order[..5], [TaskPriority::High; 5]);
+        assert_eq!(order[5..], [TaskPriority::Background; 5]);
+    }
+
+    #[test]
+    fn test_max_concurrency() {
+        let pool = TaskPoolBuilder::new()
+            .num_threads(4)
+            .max_concurrency(TaskPriority::Background, 1)
+            .build();
+        let active = AtomicI32::new(0);
+        let max_active = AtomicI32::new(0);
+
+        pool.scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn_with_priority(TaskPriority::Background, async {
+                    let now_active = active.fetch_add(1, Ordering::AcqRel) + 1;
+                    max_active.fetch_max(now_active, Ordering::AcqRel);
+                    thread::sleep(std::time::Duration::from_millis(5));
+                    active.fetch_sub(1, Ordering::AcqRel);
+                });
+            }
+        });
+
+        assert_eq!(max_active.load(Ordering::Acquire), 1);
+    }
+
+    #[test]
+    fn test_nested_scopes_with_max_concurrency() {
+        let pool = TaskPoolBuilder::new()
+            .num_threads(1)
+            .max_concurrency(TaskPriority::Background, 1)
+            .build();
+        let count = AtomicI32::new(0);
+
+        pool.scope(|scope| {
+            for priority in [TaskPriority::High, TaskPriority::Background] {
+                scope.spawn_with_priority(priority, async {
+                    pool.scope(|scope| {
+                        scope.spawn_with_priority(TaskPriority::Background, async {
+                            count.fetch_add(1, Ordering::Relaxed);
+                        });
+                    });
+                });
+            }
+        });
+
+        assert_eq!(count.load(Ordering::Acquire), 2);
+    }
+
     // This test will often freeze on other executors.
     #[test]
     fn test_nested_scopes() {
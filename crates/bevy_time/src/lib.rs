@@ -10,6 +10,7 @@
 pub mod common_conditions;
 mod fixed;
 mod real;
+mod scheduler;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
@@ -18,6 +19,7 @@ mod virt;
 
 pub use fixed::*;
 pub use real::*;
+pub use scheduler::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
@@ -26,7 +28,7 @@ pub use virt::*;
 pub mod prelude {
     //! The Bevy Time Prelude.
     #[doc(hidden)]
-    pub use crate::{Fixed, Real, Time, Timer, TimerMode, Virtual};
+    pub use crate::{Fixed, Real, Time, Timer, TimerAction, TimerMode, TimerScheduler, Virtual};
 }
 
 use bevy_app::{prelude::*, RunFixedMainLoop};
@@ -51,7 +53,8 @@ impl Plugin for TimePlugin {
             .init_resource::<Time<Real>>()
             .init_resource::<Time<Virtual>>()
             .init_resource::<Time<Fixed>>()
-            .init_resource::<TimeUpdateStrategy>();
+            .init_resource::<TimeUpdateStrategy>()
+            .init_resource::<TimerScheduler>();
 
         #[cfg(feature = "bevy_reflect")]
         {
@@ -62,8 +65,14 @@ impl Plugin for TimePlugin {
                 .register_type::<Timer>();
         }
 
-        app.add_systems(First, time_system.in_set(TimeSystem))
-            .add_systems(RunFixedMainLoop, run_fixed_main_schedule);
+        app.add_systems(
+            First,
+            (
+                time_system.in_set(TimeSystem),
+                tick_timer_scheduler.after(TimeSystem),
+            ),
+        )
+        .add_systems(RunFixedMainLoop, run_fixed_main_schedule);
 
         // Ensure the events are not dropped until `FixedMain` systems can observe them
         app.add_systems(FixedPostUpdate, signal_event_update_system);
@@ -0,0 +1,551 @@
+use std::collections::BTreeMap;
+
+use bevy_ecs::{
+    event::Event,
+    observer::TriggerTargets,
+    system::{Resource, SystemId},
+    world::World,
+};
+use bevy_utils::{tracing::warn, Duration, HashMap};
+
+use crate::{Real, Time, Timer, TimerMode, Virtual};
+
+/// Identifies a timer scheduled on the [`TimerScheduler`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimerId(u64);
+
+/// Identifies a group of timers of the [`TimerScheduler`].
+///
+/// Groups can be nested: pausing a group pauses the timers of its subgroups, and the relative
+/// speeds of nested groups are multiplied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimerGroup(u64);
+
+/// The clock that advances a timer of the [`TimerScheduler`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TimerClock {
+    /// Advances with [`Time<Virtual>`], following its relative speed and stopping while it is paused.
+    #[default]
+    Virtual,
+    /// Advances with [`Time<Real>`].
+    Real,
+}
+
+/// Options of a timer scheduled with [`TimerScheduler::schedule_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimerOptions {
+    /// The clock advancing the timer.
+    pub clock: TimerClock,
+    /// The group of the timer, if any.
+    pub group: Option<TimerGroup>,
+}
+
+impl TimerOptions {
+    /// Returns these options with the given clock.
+    pub const fn with_clock(mut self, clock: TimerClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Returns these options with the given group.
+    pub const fn in_group(mut self, group: TimerGroup) -> Self {
+        self.group = Some(group);
+        self
+    }
+}
+
+/// What happens when a timer of the [`TimerScheduler`] finishes.
+pub struct TimerAction(Box<dyn FnMut(&mut World) + Send + Sync>);
+
+impl TimerAction {
+    /// Runs `action` on the world.
+    pub fn new(action: impl FnMut(&mut World) + Send + Sync + 'static) -> Self {
+        Self(Box::new(action))
+    }
+
+    /// Runs the system registered with [`World::register_system`].
+    pub fn run_system(system: SystemId) -> Self {
+        Self::new(move |world| {
+            if let Err(error) = world.run_system(system) {
+                warn!("Failed to run the system of a timer: {error}");
+            }
+        })
+    }
+
+    /// Triggers `event` for the observers watching for it.
+    pub fn trigger<E: Event + Clone>(event: E) -> Self {
+        Self::new(move |world| world.trigger(event.clone()))
+    }
+
+    /// Triggers `event` for the observers watching for it on `targets`.
+    pub fn trigger_targets<E: Event + Clone, T: TriggerTargets + Clone>(
+        event: E,
+        targets: T,
+    ) -> Self {
+        Self::new(move |world| world.trigger_targets(event.clone(), targets.clone()))
+    }
+}
+
+impl std::fmt::Debug for TimerAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TimerAction").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct ScheduledTimer {
+    timer: Timer,
+    options: TimerOptions,
+    /// Taken out while the action runs.
+    action: Option<TimerAction>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct GroupState {
+    parent: Option<TimerGroup>,
+    paused: bool,
+    relative_speed: f32,
+}
+
+/// Runs actions after a delay, without needing bespoke components and systems to tick [`Timer`]s.
+///
+/// Each scheduled [`Timer`] is advanced by its [`TimerClock`] once per frame, in
+/// [`First`](bevy_app::First) after [`TimeSystem`](crate::TimeSystem), and its [`TimerAction`] is
+/// run every time it finishes. Timers in [`TimerMode::Once`] are removed once they finished.
+/// Actions are run in the order their timers finished in, as far as that can be known.
+///
+/// Timers can be paused individually through [`TimerScheduler::get_mut`], or by [`TimerGroup`]:
+/// see [`TimerScheduler::add_group`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_time::prelude::*;
+/// # use bevy_time::{TimerAction, TimerScheduler};
+/// #[derive(Event, Clone)]
+/// struct Explode;
+///
+/// fn light_fuse(mut scheduler: ResMut<TimerScheduler>) {
+///     scheduler.schedule(
+///         Timer::from_seconds(3.0, TimerMode::Once),
+///         TimerAction::trigger(Explode),
+///     );
+/// }
+/// # bevy_ecs::system::assert_is_system(light_fuse);
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct TimerScheduler {
+    timers: BTreeMap<TimerId, ScheduledTimer>,
+    groups: BTreeMap<TimerGroup, GroupState>,
+    next_id: u64,
+}
+
+impl TimerScheduler {
+    /// Schedules `timer` on the [`TimerClock::Virtual`] clock, running `action` each time it finishes.
+    pub fn schedule(&mut self, timer: Timer, action: TimerAction) -> TimerId {
+        self.schedule_with(timer, action, TimerOptions::default())
+    }
+
+    /// Schedules `timer` with the given `options`, running `action` each time it finishes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group of the timer doesn't exist.
+    pub fn schedule_with(
+        &mut self,
+        timer: Timer,
+        action: TimerAction,
+        options: TimerOptions,
+    ) -> TimerId {
+        if let Some(group) = options.group {
+            assert!(self.groups.contains_key(&group), "{group:?} does not exist");
+        }
+        let id = TimerId(self.next_id());
+        self.timers.insert(
+            id,
+            ScheduledTimer {
+                timer,
+                options,
+                action: Some(action),
+            },
+        );
+        id
+    }
+
+    /// Schedules `system` to run once after `delay`, on the [`TimerClock::Virtual`] clock.
+    pub fn run_system_after(&mut self, delay: Duration, system: SystemId) -> TimerId {
+        self.schedule(
+            Timer::new(delay, TimerMode::Once),
+            TimerAction::run_system(system),
+        )
+    }
+
+    /// Triggers `event` once after `delay`, on the [`TimerClock::Virtual`] clock.
+    pub fn trigger_after<E: Event + Clone>(&mut self, delay: Duration, event: E) -> TimerId {
+        self.schedule(
+            Timer::new(delay, TimerMode::Once),
+            TimerAction::trigger(event),
+        )
+    }
+
+    /// Cancels a timer. Returns `false` if it was not scheduled anymore.
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        self.timers.remove(&id).is_some()
+    }
+
+    /// Returns `true` if the timer is still scheduled.
+    pub fn contains(&self, id: TimerId) -> bool {
+        self.timers.contains_key(&id)
+    }
+
+    /// Returns the [`Timer`] of a scheduled timer.
+    pub fn get(&self, id: TimerId) -> Option<&Timer> {
+        self.timers.get(&id).map(|scheduled| &scheduled.timer)
+    }
+
+    /// Returns the [`Timer`] of a scheduled timer, to pause or reset it for example.
+    pub fn get_mut(&mut self, id: TimerId) -> Option<&mut Timer> {
+        self.timers
+            .get_mut(&id)
+            .map(|scheduled| &mut scheduled.timer)
+    }
+
+    /// Returns the number of scheduled timers.
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Returns `true` if no timer is scheduled.
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Adds a group of timers, nested in `parent` if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parent` doesn't exist.
+    pub fn add_group(&mut self, parent: Option<TimerGroup>) -> TimerGroup {
+        if let Some(parent) = parent {
+            assert!(
+                self.groups.contains_key(&parent),
+                "{parent:?} does not exist"
+            );
+        }
+        let group = TimerGroup(self.next_id());
+        self.groups.insert(
+            group,
+            GroupState {
+                parent,
+                paused: false,
+                relative_speed: 1.0,
+            },
+        );
+        group
+    }
+
+    /// Removes a group, along with its subgroups and all of their timers.
+    /// Returns `false` if the group didn't exist.
+    pub fn remove_group(&mut self, group: TimerGroup) -> bool {
+        if !self.groups.contains_key(&group) {
+            return false;
+        }
+        let removed: Vec<TimerGroup> = self
+            .groups
+            .keys()
+            .copied()
+            .filter(|&other| self.is_in_group(other, group))
+            .collect();
+        for group in &removed {
+            self.groups.remove(group);
+        }
+        self.timers.retain(|_, scheduled| {
+            !scheduled
+                .options
+                .group
+                .is_some_and(|group| removed.contains(&group))
+        });
+        true
+    }
+
+    /// Pauses the timers of a group and of its subgroups.
+    pub fn pause_group(&mut self, group: TimerGroup) {
+        if let Some(state) = self.groups.get_mut(&group) {
+            state.paused = true;
+        }
+    }
+
+    /// Resumes the timers of a group. Timers stay paused if a parent group is paused.
+    pub fn unpause_group(&mut self, group: TimerGroup) {
+        if let Some(state) = self.groups.get_mut(&group) {
+            state.paused = false;
+        }
+    }
+
+    /// Returns `true` if the group itself is paused, regardless of its parents.
+    pub fn is_group_paused(&self, group: TimerGroup) -> bool {
+        self.groups.get(&group).is_some_and(|state| state.paused)
+    }
+
+    /// Sets the speed at which the timers of a group advance, relative to their clock and to the
+    /// parent group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is negative or not finite.
+    pub fn set_group_relative_speed(&mut self, group: TimerGroup, ratio: f32) {
+        assert!(ratio.is_finite(), "tried to go infinitely fast");
+        assert!(ratio >= 0.0, "tried to go back in time");
+        if let Some(state) = self.groups.get_mut(&group) {
+            state.relative_speed = ratio;
+        }
+    }
+
+    /// Returns the speed of a group relative to its parent, or `1.0` if it doesn't exist.
+    pub fn group_relative_speed(&self, group: TimerGroup) -> f32 {
+        self.groups
+            .get(&group)
+            .map_or(1.0, |state| state.relative_speed)
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Returns `true` if `group` is `ancestor` or one of its subgroups.
+    fn is_in_group(&self, mut group: TimerGroup, ancestor: TimerGroup) -> bool {
+        loop {
+            if group == ancestor {
+                return true;
+            }
+            match self.groups.get(&group).and_then(|state| state.parent) {
+                Some(parent) => group = parent,
+                None => return false,
+            }
+        }
+    }
+
+    /// Advances the timers, returning the ones that finished with the fraction of the frame at
+    /// which they did, once for each time they finished.
+    fn tick(&mut self, virtual_delta: Duration, real_delta: Duration) -> Vec<(f64, TimerId)> {
+        let Self { timers, groups, .. } = self;
+        let mut speeds = HashMap::new();
+        let mut finished = Vec::new();
+        for (&id, scheduled) in timers {
+            let delta = match scheduled.options.clock {
+                TimerClock::Virtual => virtual_delta,
+                TimerClock::Real => real_delta,
+            };
+            let delta = match scheduled.options.group {
+                Some(group) => delta.mul_f32(
+                    *speeds
+                        .entry(group)
+                        .or_insert_with(|| effective_speed(groups, group)),
+                ),
+                None => delta,
+            };
+
+            let remaining = scheduled.timer.remaining();
+            let times = scheduled.timer.tick(delta).times_finished_this_tick();
+            let duration = scheduled.timer.duration();
+            for i in 0..times {
+                let fraction = if delta.is_zero() {
+                    0.0
+                } else {
+                    (remaining + duration * i).as_secs_f64() / delta.as_secs_f64()
+                };
+                finished.push((fraction, id));
+            }
+        }
+        finished.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        finished
+    }
+}
+
+/// Returns the speed of the timers of a group, taking its parents into account.
+fn effective_speed(groups: &BTreeMap<TimerGroup, GroupState>, mut group: TimerGroup) -> f32 {
+    let mut speed = 1.0;
+    while let Some(state) = groups.get(&group) {
+        if state.paused {
+            return 0.0;
+        }
+        speed *= state.relative_speed;
+        match state.parent {
+            Some(parent) => group = parent,
+            None => break,
+        }
+    }
+    speed
+}
+
+/// Advances the timers of the [`TimerScheduler`] and runs the actions of the ones that finished.
+pub fn tick_timer_scheduler(world: &mut World) {
+    let virtual_delta = world.resource::<Time<Virtual>>().delta();
+    let real_delta = world.resource::<Time<Real>>().delta();
+    let Some(mut scheduler) = world.get_resource_mut::<TimerScheduler>() else {
+        return;
+    };
+    let finished = scheduler.tick(virtual_delta, real_delta);
+
+    for (_, id) in finished {
+        // The timer may have been cancelled by a previous action.
+        let Some(mut action) = world
+            .resource_mut::<TimerScheduler>()
+            .timers
+            .get_mut(&id)
+            .and_then(|scheduled| scheduled.action.take())
+        else {
+            continue;
+        };
+
+        (action.0)(world);
+
+        let mut scheduler = world.resource_mut::<TimerScheduler>();
+        let Some(scheduled) = scheduler.timers.get_mut(&id) else {
+            continue;
+        };
+        // `Once` timers are kept if the action reset them.
+        if scheduled.timer.mode() == TimerMode::Once && scheduled.timer.finished() {
+            scheduler.timers.remove(&id);
+        } else {
+            scheduled.action = Some(action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use bevy_app::App;
+    use bevy_ecs::{observer::Trigger, system::ResMut, world::Mut};
+
+    use super::*;
+    use crate::{TimePlugin, TimeUpdateStrategy};
+
+    #[derive(Event, Clone)]
+    struct Ring(&'static str);
+
+    /// Returns an app advancing by 100ms each frame, and the rings triggered in it.
+    fn app() -> (App, Arc<Mutex<Vec<&'static str>>>) {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        let rings = Arc::new(Mutex::new(Vec::new()));
+        let observed = rings.clone();
+        app.observe(move |trigger: Trigger<Ring>| observed.lock().unwrap().push(trigger.event().0));
+        // The first update has a zero delta.
+        app.update();
+        (app, rings)
+    }
+
+    fn timers(app: &mut App) -> Mut<TimerScheduler> {
+        app.world_mut().resource_mut::<TimerScheduler>()
+    }
+
+    fn take(rings: &Mutex<Vec<&'static str>>) -> Vec<&'static str> {
+        std::mem::take(&mut *rings.lock().unwrap())
+    }
+
+    #[test]
+    fn trigger_in_order() {
+        let (mut app, rings) = app();
+        let mut scheduler = timers(&mut app);
+        scheduler.trigger_after(Duration::from_millis(250), Ring("late"));
+        scheduler.trigger_after(Duration::from_millis(150), Ring("early"));
+        let repeating = scheduler.schedule(
+            Timer::new(Duration::from_millis(120), TimerMode::Repeating),
+            TimerAction::trigger(Ring("repeating")),
+        );
+
+        app.update();
+        assert!(take(&rings).is_empty());
+        app.update();
+        assert_eq!(take(&rings), ["repeating", "early"]);
+        app.update();
+        assert_eq!(take(&rings), ["repeating", "late"]);
+
+        let mut scheduler = timers(&mut app);
+        assert_eq!(scheduler.len(), 1);
+        assert!(scheduler.cancel(repeating));
+        app.update();
+        assert!(take(&rings).is_empty());
+    }
+
+    #[test]
+    fn clocks() {
+        let (mut app, rings) = app();
+        let mut scheduler = timers(&mut app);
+        scheduler.trigger_after(Duration::from_millis(150), Ring("virtual"));
+        scheduler.schedule_with(
+            Timer::new(Duration::from_millis(150), TimerMode::Once),
+            TimerAction::trigger(Ring("real")),
+            TimerOptions::default().with_clock(TimerClock::Real),
+        );
+        app.world_mut()
+            .resource_mut::<Time<Virtual>>()
+            .set_relative_speed(0.5);
+
+        app.update();
+        app.update();
+        assert_eq!(take(&rings), ["real"]);
+        app.update();
+        app.update();
+        assert_eq!(take(&rings), ["virtual"]);
+    }
+
+    #[test]
+    fn nested_groups() {
+        let (mut app, rings) = app();
+        let mut scheduler = timers(&mut app);
+        let parent = scheduler.add_group(None);
+        let child = scheduler.add_group(Some(parent));
+        scheduler.set_group_relative_speed(child, 2.0);
+        for (group, name) in [(parent, "parent"), (child, "child")] {
+            scheduler.schedule_with(
+                Timer::new(Duration::from_millis(150), TimerMode::Once),
+                TimerAction::trigger(Ring(name)),
+                TimerOptions::default().in_group(group),
+            );
+        }
+        scheduler.pause_group(parent);
+
+        app.update();
+        app.update();
+        assert!(take(&rings).is_empty());
+
+        timers(&mut app).unpause_group(parent);
+        app.update();
+        assert_eq!(take(&rings), ["child"]);
+
+        let mut scheduler = timers(&mut app);
+        assert!(scheduler.remove_group(parent));
+        assert!(scheduler.is_empty());
+        assert!(!scheduler.remove_group(child));
+    }
+
+    #[test]
+    fn run_system() {
+        #[derive(Resource, Default)]
+        struct Count(u32);
+
+        let (mut app, _) = app();
+        app.init_resource::<Count>();
+        let system = app
+            .world_mut()
+            .register_system(|mut count: ResMut<Count>| count.0 += 1);
+        timers(&mut app).run_system_after(Duration::from_millis(150), system);
+
+        app.update();
+        assert_eq!(app.world().resource::<Count>().0, 0);
+        app.update();
+        assert_eq!(app.world().resource::<Count>().0, 1);
+        app.update();
+        assert_eq!(app.world().resource::<Count>().0, 1);
+    }
+}
@@ -0,0 +1,351 @@
+use bevy_app::{App, FixedFirst, FixedLast, Plugin, PostUpdate};
+use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_hierarchy::{Children, HierarchyQueryExt, Parent};
+use bevy_reflect::{prelude::*, Reflect};
+use bevy_time::{Fixed, Time};
+
+use crate::{
+    components::{GlobalTransform, Transform},
+    plugins::TransformSystem,
+    systems::{propagate_transforms, sync_simple_transforms},
+};
+
+/// Smooths the movement of entities whose [`Transform`] is updated in the fixed timestep
+/// schedules, such as [`FixedUpdate`](bevy_app::FixedUpdate).
+///
+/// The plugin records the [`Transform`] of the entities with a [`TransformInterpolation`]
+/// component before and after each run of [`FixedMain`](bevy_app::FixedMain). At the end of
+/// [`TransformSystem::TransformPropagate`], the [`GlobalTransform`] of these entities and of their
+/// descendants is computed from a visual transform eased using
+/// [`Time<Fixed>::overstep_fraction`](Time::overstep_fraction).
+/// The [`Transform`] itself is left as computed by the fixed timestep systems.
+///
+/// This plugin is not part of the `DefaultPlugins`, and requires the `TimePlugin` and the
+/// [`TransformPlugin`](crate::TransformPlugin).
+#[derive(Default)]
+pub struct TransformInterpolationPlugin;
+
+impl Plugin for TransformInterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TransformInterpolation>()
+            .register_type::<TransformEasing>()
+            .add_systems(FixedFirst, record_start_transforms)
+            .add_systems(FixedLast, record_end_transforms)
+            .add_systems(
+                PostUpdate,
+                interpolate_transforms
+                    .in_set(TransformSystem::TransformPropagate)
+                    .after(sync_simple_transforms)
+                    .after(propagate_transforms),
+            );
+    }
+}
+
+/// How the visual transform is computed from the [`Transform`]s recorded around the last fixed
+/// timestep.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum TransformEasing {
+    /// Interpolates between the [`Transform`] before and after the last fixed timestep.
+    ///
+    /// This is always correct, but lags behind the simulation by up to one timestep.
+    #[default]
+    Interpolate,
+    /// Extrapolates from the [`Transform`] after the last fixed timestep, assuming that the entity
+    /// keeps moving like it did during that timestep.
+    ///
+    /// This doesn't lag, but overshoots when the movement changes.
+    Extrapolate,
+}
+
+/// Enables the interpolation of the [`Transform`] of an entity by the
+/// [`TransformInterpolationPlugin`].
+///
+/// Changes made to the [`Transform`] outside of the fixed timestep schedules are treated as
+/// teleports: the entity is moved there without easing.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct TransformInterpolation {
+    /// How the visual transform is computed.
+    pub easing: TransformEasing,
+    /// The [`Transform`] before the last fixed timestep.
+    start: Option<Transform>,
+    /// The [`Transform`] after the last fixed timestep.
+    end: Option<Transform>,
+    /// The visual transform used for the [`GlobalTransform`], if any.
+    visual: Option<Transform>,
+}
+
+impl TransformInterpolation {
+    /// Interpolates the [`Transform`] of the entity, see [`TransformEasing::Interpolate`].
+    pub const INTERPOLATE: Self = Self::new(TransformEasing::Interpolate);
+
+    /// Extrapolates the [`Transform`] of the entity, see [`TransformEasing::Extrapolate`].
+    pub const EXTRAPOLATE: Self = Self::new(TransformEasing::Extrapolate);
+
+    /// Creates a [`TransformInterpolation`] with the given easing.
+    pub const fn new(easing: TransformEasing) -> Self {
+        Self {
+            easing,
+            start: None,
+            end: None,
+            visual: None,
+        }
+    }
+
+    /// Returns the [`Transform`]s before and after the last fixed timestep, if the entity went
+    /// through one since it got this component or was teleported.
+    pub fn fixed_transforms(&self) -> Option<(Transform, Transform)> {
+        self.start.zip(self.end)
+    }
+
+    /// Computes the visual transform for the given fraction of the next timestep.
+    fn ease(&self, overstep_fraction: f32) -> Option<Transform> {
+        let (start, end) = self.fixed_transforms()?;
+        let t = match self.easing {
+            TransformEasing::Interpolate => overstep_fraction,
+            TransformEasing::Extrapolate => 1.0 + overstep_fraction,
+        };
+        Some(Transform {
+            translation: start.translation.lerp(end.translation, t),
+            rotation: start.rotation.slerp(end.rotation, t),
+            scale: start.scale.lerp(end.scale, t),
+        })
+    }
+
+    /// Forgets the recorded transforms, so that the entity isn't eased until the next timestep.
+    fn teleport(&mut self) {
+        self.start = None;
+        self.end = None;
+    }
+}
+
+fn record_start_transforms(mut query: Query<(&Transform, &mut TransformInterpolation)>) {
+    for (transform, mut interpolation) in &mut query {
+        interpolation.start = Some(*transform);
+    }
+}
+
+fn record_end_transforms(mut query: Query<(&Transform, &mut TransformInterpolation)>) {
+    for (transform, mut interpolation) in &mut query {
+        interpolation.end = Some(*transform);
+    }
+}
+
+/// Computes the [`GlobalTransform`] of the entities from their visual transform.
+fn interpolate_transforms(
+    time: Res<Time<Fixed>>,
+    mut interpolations: Query<(Entity, &Transform, &mut TransformInterpolation)>,
+    mut removed: RemovedComponents<TransformInterpolation>,
+    parent_query: Query<&Parent>,
+    children_query: Query<&Children>,
+    mut transform_query: Query<(&Transform, &mut GlobalTransform)>,
+    mut entities: Local<Vec<Entity>>,
+) {
+    let overstep_fraction = time.overstep_fraction();
+    entities.clear();
+    for (entity, transform, mut interpolation) in &mut interpolations {
+        if interpolation.end.is_some_and(|end| *transform != end) {
+            interpolation.teleport();
+        }
+        interpolation.visual = interpolation.ease(overstep_fraction);
+        entities.push(entity);
+    }
+    // Entities that stop being interpolated are put back at their `Transform`.
+    entities.extend(removed.read());
+
+    for &entity in entities.iter() {
+        // Descendants of interpolated entities are updated along with their ancestor.
+        if parent_query
+            .iter_ancestors(entity)
+            .any(|ancestor| interpolations.contains(ancestor))
+        {
+            continue;
+        }
+        let parent = parent_query
+            .get(entity)
+            .and_then(|parent| transform_query.get(parent.get()))
+            .map_or(GlobalTransform::IDENTITY, |(_, global_transform)| {
+                *global_transform
+            });
+        propagate_visual(
+            &parent,
+            &interpolations,
+            &children_query,
+            &mut transform_query,
+            entity,
+        );
+    }
+}
+
+/// Updates the [`GlobalTransform`] of `entity` and of its descendants, using the visual transform
+/// of the interpolated ones.
+fn propagate_visual(
+    parent: &GlobalTransform,
+    interpolations: &Query<(Entity, &Transform, &mut TransformInterpolation)>,
+    children_query: &Query<&Children>,
+    transform_query: &mut Query<(&Transform, &mut GlobalTransform)>,
+    entity: Entity,
+) {
+    let Ok((transform, mut global_transform)) = transform_query.get_mut(entity) else {
+        return;
+    };
+    let visual = interpolations
+        .get(entity)
+        .ok()
+        .and_then(|(_, _, interpolation)| interpolation.visual);
+    let new_global_transform = parent.mul_transform(visual.unwrap_or(*transform));
+    global_transform.set_if_neq(new_global_transform);
+
+    let Ok(children) = children_query.get(entity) else {
+        return;
+    };
+    for &child in children {
+        propagate_visual(
+            &new_global_transform,
+            interpolations,
+            children_query,
+            transform_query,
+            child,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy_app::{FixedUpdate, Last};
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_math::Vec3;
+    use bevy_time::{TimePlugin, TimeUpdateStrategy};
+
+    use super::*;
+    use crate::{bundles::TransformBundle, TransformPlugin};
+
+    fn app(easing: TransformEasing) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, TransformPlugin, TransformInterpolationPlugin))
+            .insert_resource(Time::<Fixed>::from_seconds(1.0))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                0.25,
+            )))
+            .add_systems(
+                FixedUpdate,
+                |mut query: Query<&mut Transform, With<TransformInterpolation>>| {
+                    for mut transform in &mut query {
+                        transform.translation.x += 4.0;
+                    }
+                },
+            )
+            .init_resource::<TransformChanged>()
+            .add_systems(
+                Last,
+                |query: Query<(), (Changed<Transform>, With<TransformInterpolation>)>,
+                 mut changed: ResMut<TransformChanged>| {
+                    changed.0 = !query.is_empty();
+                },
+            );
+        let entity = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                TransformInterpolation::new(easing),
+            ))
+            .id();
+        (app, entity)
+    }
+
+    /// Whether the [`Transform`] of the interpolated entity changed during the last update.
+    #[derive(Resource, Default)]
+    struct TransformChanged(bool);
+
+    fn translation_x(app: &App, entity: Entity) -> (f32, f32) {
+        let world = app.world();
+        (
+            world.get::<Transform>(entity).unwrap().translation.x,
+            world
+                .get::<GlobalTransform>(entity)
+                .unwrap()
+                .translation()
+                .x,
+        )
+    }
+
+    #[test]
+    fn interpolate() {
+        let (mut app, entity) = app(TransformEasing::Interpolate);
+
+        // The first update has a zero delta, the fixed timestep runs on the fifth one.
+        for _ in 0..5 {
+            app.update();
+        }
+        // Only the `GlobalTransform` is eased.
+        assert_eq!(translation_x(&app, entity), (4.0, 0.0));
+
+        let child = app
+            .world_mut()
+            .spawn(TransformBundle::from_transform(Transform::from_xyz(
+                0.5, 0.0, 0.0,
+            )))
+            .set_parent(entity)
+            .id();
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            app.update();
+            seen.push((
+                translation_x(&app, entity),
+                translation_x(&app, child).1,
+                app.world().resource::<TransformChanged>().0,
+            ));
+        }
+        assert_eq!(
+            seen,
+            [
+                // The `Transform` is only changed by the fixed timestep.
+                ((4.0, 1.0), 1.5, false),
+                ((4.0, 2.0), 2.5, false),
+                ((4.0, 3.0), 3.5, false),
+                ((8.0, 4.0), 4.5, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn extrapolate() {
+        let (mut app, entity) = app(TransformEasing::Extrapolate);
+        for _ in 0..6 {
+            app.update();
+        }
+        assert_eq!(translation_x(&app, entity), (4.0, 5.0));
+    }
+
+    #[test]
+    fn teleport() {
+        let (mut app, entity) = app(TransformEasing::Interpolate);
+        for _ in 0..6 {
+            app.update();
+        }
+        assert_eq!(translation_x(&app, entity), (4.0, 1.0));
+
+        app.world_mut()
+            .get_mut::<Transform>(entity)
+            .unwrap()
+            .translation = Vec3::splat(10.0);
+        app.update();
+        assert_eq!(translation_x(&app, entity), (10.0, 10.0));
+
+        // The entity is eased again after the next fixed timestep.
+        for _ in 0..3 {
+            app.update();
+        }
+        let transform = app.world().get::<TransformInterpolation>(entity).unwrap();
+        assert_eq!(
+            transform
+                .fixed_transforms()
+                .map(|(start, end)| (start.translation.x, end.translation.x)),
+            Some((10.0, 14.0))
+        );
+    }
+}
@@ -24,6 +24,9 @@ pub mod plugins;
 /// Helpers related to computing global transforms
 #[cfg(feature = "bevy-support")]
 pub mod helper;
+/// Interpolation of transforms updated in the fixed timestep schedules
+#[cfg(feature = "bevy-support")]
+pub mod interpolation;
 /// Systems responsible for transform propagation
 #[cfg(feature = "bevy-support")]
 pub mod systems;
@@ -36,8 +39,13 @@ pub mod prelude {
     #[cfg(feature = "bevy-support")]
     #[doc(hidden)]
     pub use crate::{
-        bundles::TransformBundle, commands::BuildChildrenTransformExt, helper::TransformHelper,
-        plugins::TransformPlugin, plugins::TransformSystem, traits::TransformPoint,
+        bundles::TransformBundle,
+        commands::BuildChildrenTransformExt,
+        helper::TransformHelper,
+        interpolation::{TransformInterpolation, TransformInterpolationPlugin},
+        plugins::TransformPlugin,
+        plugins::TransformSystem,
+        traits::TransformPoint,
     };
 }
 
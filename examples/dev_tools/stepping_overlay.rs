@@ -0,0 +1,57 @@
+//! Showcase how to step through the systems of an app with the stepping overlay.
+//!
+//! Press ` to enable stepping, S to run the next system and Space to run the rest of the frame.
+//! Click on a system to set or clear a breakpoint on it.
+
+use bevy::{dev_tools::stepping::SteppingOverlayPlugin, prelude::*};
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, SteppingOverlayPlugin::default()))
+        .init_resource::<Counter>()
+        .add_systems(Startup, setup)
+        .add_systems(Update, (increment, double, display).chain())
+        .run();
+}
+
+#[derive(Resource, Default)]
+struct Counter(u64);
+
+#[derive(Component)]
+struct CounterText;
+
+fn setup(mut commands: Commands) {
+    // We need to spawn a camera (2d or 3d) to see the overlay
+    commands.spawn(Camera2dBundle::default());
+
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 40.0,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+        CounterText,
+    ));
+}
+
+fn increment(mut counter: ResMut<Counter>) {
+    counter.0 += 1;
+}
+
+fn double(mut counter: ResMut<Counter>) {
+    counter.0 = (counter.0 * 2) % 1_000_000;
+}
+
+fn display(counter: Res<Counter>, mut text: Query<&mut Text, With<CounterText>>) {
+    for mut text in &mut text {
+        text.sections[0].value = format!("Counter: {}", counter.0);
+    }
+}
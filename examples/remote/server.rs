@@ -0,0 +1,78 @@
+//! A Bevy app that you can connect to with the Bevy Remote Protocol and
+//! inspect or modify from another process.
+//!
+//! Run this example, then send requests to it, for example with `curl`:
+//!
+//! ```sh
+//! curl -X POST http://127.0.0.1:15702 -d '{
+//!     "jsonrpc": "2.0",
+//!     "id": 0,
+//!     "method": "bevy/query",
+//!     "params": { "data": { "components": ["server::Cube"] } }
+//! }'
+//! ```
+
+use bevy::prelude::*;
+use bevy::remote::{http::RemoteHttpPlugin, RemotePlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(RemotePlugin::default())
+        .add_plugins(RemoteHttpPlugin::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, rotate)
+        .register_type::<Cube>()
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // circular base
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Circle::new(4.0)),
+        material: materials.add(Color::WHITE),
+        transform: Transform::from_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+        ..default()
+    });
+
+    // cube, whose speed can be changed remotely through `bevy/insert`
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+            material: materials.add(Color::srgb_u8(124, 144, 255)),
+            transform: Transform::from_xyz(0.0, 0.5, 0.0),
+            ..default()
+        },
+        Cube(1.0),
+    ));
+
+    // light
+    commands.spawn(PointLightBundle {
+        point_light: PointLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..default()
+    });
+
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-2.5, 4.5, 9.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+}
+
+fn rotate(mut query: Query<(&mut Transform, &Cube)>, time: Res<Time>) {
+    for (mut transform, cube) in &mut query {
+        transform.rotate_y(cube.0 * time.delta_seconds());
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Cube(f32);
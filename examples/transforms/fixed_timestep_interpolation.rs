@@ -0,0 +1,60 @@
+//! Illustrates how to smooth the movement of objects that are moved in the fixed timestep schedules.
+//!
+//! The fixed timestep runs at 8 Hz, so the square without interpolation visibly stutters.
+
+use bevy::{prelude::*, transform::interpolation::TransformEasing};
+
+#[derive(Component)]
+struct Velocity(Vec3);
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, TransformInterpolationPlugin))
+        .insert_resource(Time::<Fixed>::from_hz(8.0))
+        .add_systems(Startup, setup)
+        .add_systems(FixedUpdate, bounce)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let squares = [
+        (Color::srgb(0.9, 0.3, 0.3), None),
+        (
+            Color::srgb(0.3, 0.9, 0.3),
+            Some(TransformInterpolation::new(TransformEasing::Interpolate)),
+        ),
+        (
+            Color::srgb(0.3, 0.3, 0.9),
+            Some(TransformInterpolation::new(TransformEasing::Extrapolate)),
+        ),
+    ];
+    for (i, (color, interpolation)) in squares.into_iter().enumerate() {
+        let mut square = commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(50.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, 100.0 - 100.0 * i as f32, 0.0),
+                ..default()
+            },
+            Velocity(Vec3::X * 400.0),
+        ));
+        // Only the entities with a `TransformInterpolation` component are interpolated.
+        if let Some(interpolation) = interpolation {
+            square.insert(interpolation);
+        }
+    }
+}
+
+fn bounce(time: Res<Time>, mut squares: Query<(&mut Transform, &mut Velocity)>) {
+    for (mut transform, mut velocity) in &mut squares {
+        transform.translation += velocity.0 * time.delta_seconds();
+        if transform.translation.x.abs() > 300.0 {
+            velocity.0 = -velocity.0;
+        }
+    }
+}